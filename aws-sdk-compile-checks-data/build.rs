@@ -0,0 +1,279 @@
+//! Turns the CSV with required properties into compact static data, so macro expansions do not have to parse the CSV every time.
+//!
+//! The generated code contains
+//! - `SERVICES`, the sorted names of all services
+//! - `PROPS`, every distinct list of required properties (many methods share the same list, e.g. `resource_arn tags`)
+//! - `REQUIRED_PROPS`, a perfect hash map from method name to a range in a binary blob.
+//!   That range holds the postcard encoded (service index, properties index, docs URL) for the method, decoded only when the method is looked up
+//! - with the `sorted-lookup` feature, `SORTED_REQUIRED_PROPS`, every (method, service, properties, docs URL) sorted by method and service
+//! - `TYPE_REQUIRED_PROPS`, a perfect hash map from the name of a type in the `types` modules (e.g. `Replica`) to its (service, properties)
+//! - `FLUENT_BUILDERS`, a perfect hash map from the name of a fluent builder (e.g. `SendMessageFluentBuilder`) to its (service, method)
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use crate::exceptions::{parse_exception, Requirement};
+use crate::rows::{fluent_builder_of, parse_row, records, Row};
+
+#[path = "src/rows.rs"]
+mod rows;
+#[path = "src/exceptions.rs"]
+#[allow(dead_code)] // the lookups are for the macro, we only need the parsing
+mod exceptions;
+
+const REQUIRED_PROPS_CSV: &str = "required_properties_info/required_props_info.csv";
+const REQUIRED_TYPE_PROPS_CSV: &str = "required_properties_info/required_type_props_info.csv";
+const EXCEPTIONS_CSV: &str = "required_properties_info/exceptions.csv";
+const GENERATED_TABLE: &str = "required_props_table.rs";
+const GENERATED_BLOB: &str = "required_props.bin";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", REQUIRED_PROPS_CSV);
+    println!("cargo:rerun-if-changed={}", REQUIRED_TYPE_PROPS_CSV);
+    println!("cargo:rerun-if-changed={}", EXCEPTIONS_CSV);
+    println!("cargo:rerun-if-changed=src/rows.rs");
+    println!("cargo:rerun-if-changed=src/exceptions.rs");
+
+    let csv = fs::read_to_string(REQUIRED_PROPS_CSV).expect("csv with required properties to be present");
+    let (mut table, blob) = generate_table(&csv);
+    let type_csv = fs::read_to_string(REQUIRED_TYPE_PROPS_CSV).expect("csv with required properties of types to be present");
+    table.push_str(&generate_type_table(&type_csv));
+    check_columns("required_properties_info/batch_variants.csv", 3..=3);
+    check_columns("required_properties_info/conditionally_required.csv", 4..=4);
+    check_columns("required_properties_info/deprecated.csv", 3..=4);
+    check_columns("required_properties_info/exclusive_properties.csv", 4..=4);
+    check_columns("required_properties_info/iam_actions.csv", 3..=3);
+    check_columns("required_properties_info/iam_prefixes.csv", 2..=2);
+    check_columns("required_properties_info/operation_metadata.csv", 3..=3);
+    check_columns("required_properties_info/partitions.csv", 2..=2);
+    check_columns("required_properties_info/policies.csv", 4..=4);
+    check_columns("required_properties_info/property_ranges.csv", 5..=5);
+    check_columns("required_properties_info/recommended_props.csv", 4..=4);
+    check_columns("required_properties_info/requirement_changes.csv", 5..=5);
+    check_columns("required_properties_info/service_data_versions.csv", 3..=3);
+    check_columns("required_properties_info/service_regions.csv", 4..=4);
+    check_columns("required_properties_info/waiters.csv", 3..=3);
+
+    let out_dir = env::var("OUT_DIR").expect("cargo to set OUT_DIR for build scripts");
+    fs::write(Path::new(&out_dir).join(GENERATED_TABLE), table).expect("to be able to write the generated table");
+    fs::write(Path::new(&out_dir).join(GENERATED_BLOB), blob).expect("to be able to write the generated blob");
+}
+
+struct Entry {
+    required_props: Vec<String>,
+    docs_url: String, // empty when we do not know it
+    builder: String,
+}
+
+/// Groups the rows (`service,method,prop1 prop2,docs url,fluent builder`, the url and builder being optional) by method (or type) and service.
+/// Without a builder in the row, we use the name smithy-rs generates for the method.
+/// Sorted, so the output is stable between builds.
+///
+/// Fails the build with every malformed row, because otherwise a bad row only surfaces when the macro looks up the method
+fn group_by_name(csv: &str, file: &str) -> BTreeMap<String, BTreeMap<String, Entry>> {
+    let mut by_name: BTreeMap<String, BTreeMap<String, Entry>> = BTreeMap::new();
+    let mut first_lines: BTreeMap<(String, String), usize> = BTreeMap::new();
+    let mut errors = vec![];
+
+    for record in records(csv) {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                errors.push(format!("{}:{}: {}", file, e.line, e.message));
+                continue;
+            }
+        };
+        let Row { service, name, required_props, docs_url, builder } = match parse_row(&record) {
+            Ok(row) => row,
+            Err(e) => {
+                errors.push(format!("{}:{}: {} (in `{}`)", file, record.line, e, record.fields.join(",")));
+                continue;
+            }
+        };
+        if let Some(first_line) = first_lines.insert((service.clone(), name.clone()), record.line) {
+            errors.push(format!("{}:{}: `{},{}` was already defined on line {}", file, record.line, service, name, first_line));
+            continue;
+        }
+        let builder = if builder.is_empty() { fluent_builder_of(&name) } else { builder };
+        by_name.entry(name).or_default().insert(service, Entry { required_props, docs_url, builder });
+    }
+
+    fail_on_errors(file, errors);
+    by_name
+}
+
+/// Removes the properties the service does not need after all, and adds those it needs although the documentation marks them as optional.
+/// A method without any required properties keeps its entry, so it still tells the SDKs that have it apart.
+///
+/// Fails the build when an exception does not match the data, because it is probably outdated
+fn apply_exceptions(by_method: &mut BTreeMap<String, BTreeMap<String, Entry>>, csv: &str) {
+    let mut errors = vec![];
+
+    for record in records(csv) {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                errors.push(format!("{}:{}: {}", EXCEPTIONS_CSV, e.line, e.message));
+                continue;
+            }
+        };
+        let requirement = match parse_exception(&record) {
+            Ok(requirement) => requirement,
+            Err(e) => {
+                errors.push(format!("{}:{}: {} (in `{}`)", EXCEPTIONS_CSV, record.line, e, record.fields.join(",")));
+                continue;
+            }
+        };
+        let (service, method, property) = (record.field(0), record.field(1), record.field(2));
+        let entry = by_method.get_mut(method).and_then(|services| services.get_mut(service));
+        let result = match (requirement, entry) {
+            (Requirement::Optional, Some(entry)) => match entry.required_props.iter().position(|p| p == property) {
+                Some(index) => {
+                    entry.required_props.remove(index);
+                    Ok(())
+                }
+                None => Err(format!("`{}` is not required by `{},{}`, so there is nothing to suppress", property, service, method)),
+            },
+            (Requirement::Optional, None) => Err(format!("`{},{}` is not in {}", service, method, REQUIRED_PROPS_CSV)),
+            (Requirement::Required, Some(entry)) if entry.required_props.iter().any(|p| p == property) => {
+                Err(format!("`{}` is already required by `{},{}`", property, service, method))
+            }
+            (Requirement::Required, Some(entry)) => {
+                entry.required_props.push(property.to_string());
+                Ok(())
+            }
+            (Requirement::Required, None) => {
+                let entry = Entry {
+                    required_props: vec![property.to_string()],
+                    docs_url: String::new(),
+                    builder: fluent_builder_of(method),
+                };
+                by_method.entry(method.to_string()).or_default().insert(service.to_string(), entry);
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            errors.push(format!("{}:{}: {}", EXCEPTIONS_CSV, record.line, e));
+        }
+    }
+
+    fail_on_errors(EXCEPTIONS_CSV, errors);
+}
+
+/// The other data files are read by the macro itself, we only check that they are valid CSV with the expected number of columns
+fn check_columns(file: &str, columns: RangeInclusive<usize>) {
+    println!("cargo:rerun-if-changed={}", file);
+    let csv = fs::read_to_string(file).unwrap_or_else(|e| panic!("{} to be present: {}", file, e));
+    let errors = records(&csv)
+        .filter_map(|record| match record {
+            Ok(record) if columns.contains(&record.fields.len()) => None,
+            Ok(record) => Some(format!(
+                "{}:{}: expected {} to {} columns, found {} (in `{}`)",
+                file,
+                record.line,
+                columns.start(),
+                columns.end(),
+                record.fields.len(),
+                record.fields.join(",")
+            )),
+            Err(e) => Some(format!("{}:{}: {}", file, e.line, e.message)),
+        })
+        .collect();
+    fail_on_errors(file, errors);
+}
+
+fn fail_on_errors(file: &str, errors: Vec<String>) {
+    if !errors.is_empty() {
+        panic!("{} has {} malformed row(s):\n{}", file, errors.len(), errors.join("\n"));
+    }
+}
+
+fn generate_table(csv: &str) -> (String, Vec<u8>) {
+    let mut by_method = group_by_name(csv, REQUIRED_PROPS_CSV);
+    let exceptions = fs::read_to_string(EXCEPTIONS_CSV).expect("csv with exceptions to be present");
+    apply_exceptions(&mut by_method, &exceptions);
+    let sorted_lookup = env::var_os("CARGO_FEATURE_SORTED_LOOKUP").is_some();
+
+    let services: Vec<&str> = by_method.values().flat_map(|s| s.keys().map(String::as_str)).collect::<BTreeSet<_>>().into_iter().collect();
+    let mut props: BTreeMap<Vec<&str>, u32> = BTreeMap::new();
+    let mut blob: Vec<u8> = vec![];
+    let mut map = phf_codegen::Map::new();
+
+    for (method_name, services_for_method) in &by_method {
+        let indexes: Vec<(u32, u32, &str)> = services_for_method
+            .iter()
+            .map(|(service_name, entry)| {
+                let service_index = services.binary_search(&service_name.as_str()).expect("every service to be in the list of services");
+                let next_props_index = props.len() as u32;
+                let props_index = *props.entry(entry.required_props.iter().map(String::as_str).collect()).or_insert(next_props_index);
+                (service_index as u32, props_index, entry.docs_url.as_str())
+            })
+            .collect();
+        let start = blob.len();
+        blob.extend(postcard::to_allocvec(&indexes).expect("indexes to be serializable"));
+        map.entry(method_name.as_str(), &format!("({}, {})", start, blob.len()));
+    }
+
+    let mut props_in_order: Vec<(u32, Vec<&str>)> = props.into_iter().map(|(p, i)| (i, p)).collect();
+    props_in_order.sort_unstable();
+
+    let mut table = String::new();
+    writeln!(table, "static SERVICES: &[&str] = &{:?};", services).unwrap();
+    writeln!(table, "#[cfg_attr(feature = \"sorted-lookup\", allow(dead_code))]").unwrap();
+    writeln!(table, "static PROPS: &[&[&str]] = &[").unwrap();
+    for (_, required_props) in props_in_order {
+        writeln!(table, "    &{:?},", required_props).unwrap();
+    }
+    writeln!(table, "];").unwrap();
+    writeln!(table, "#[cfg_attr(feature = \"sorted-lookup\", allow(dead_code))]").unwrap();
+    writeln!(table, "static METHODS_BLOB: &[u8] = include_bytes!(concat!(env!(\"OUT_DIR\"), \"/{}\"));", GENERATED_BLOB).unwrap();
+    writeln!(table, "#[cfg_attr(feature = \"sorted-lookup\", allow(dead_code))]").unwrap();
+    writeln!(table, "static REQUIRED_PROPS: StaticRequiredProperties = {};", map.build()).unwrap();
+    table.push_str(&generate_builder_table(&by_method));
+    if sorted_lookup {
+        table.push_str(&generate_sorted_table(&by_method));
+    }
+    (table, blob)
+}
+
+/// Types have far fewer entries than methods, so a map straight to the (service, properties) is fine
+fn generate_type_table(csv: &str) -> String {
+    let mut map = phf_codegen::Map::new();
+    for (type_name, services_for_type) in group_by_name(csv, REQUIRED_TYPE_PROPS_CSV) {
+        let entries: Vec<String> = services_for_type
+            .iter()
+            .map(|(service_name, entry)| format!("({:?}, &{:?})", service_name, entry.required_props))
+            .collect();
+        map.entry(type_name, &format!("&[{}]", entries.join(", ")));
+    }
+    format!("static TYPE_REQUIRED_PROPS: StaticTypeRequiredProperties = {};\n", map.build())
+}
+
+/// Builders with the same name exist in several SDKs (e.g. `SendMessageFluentBuilder` in sqs and connectparticipant)
+fn generate_builder_table(by_method: &BTreeMap<String, BTreeMap<String, Entry>>) -> String {
+    let mut by_builder: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+    for (method_name, services_for_method) in by_method {
+        for (service_name, entry) in services_for_method {
+            by_builder.entry(&entry.builder).or_default().push((service_name, method_name));
+        }
+    }
+    let mut map = phf_codegen::Map::new();
+    for (builder, operations) in &by_builder {
+        map.entry(*builder, &format!("&{:?}", operations));
+    }
+    format!("static FLUENT_BUILDERS: StaticFluentBuilders = {};\n", map.build())
+}
+
+fn generate_sorted_table(by_method: &BTreeMap<String, BTreeMap<String, Entry>>) -> String {
+    let mut sorted = String::from("static SORTED_REQUIRED_PROPS: &[(&str, &str, &[&str], &str)] = &[\n");
+    for (method_name, services_for_method) in by_method {
+        for (service_name, entry) in services_for_method {
+            writeln!(sorted, "    ({:?}, {:?}, &{:?}, {:?}),", method_name, service_name, entry.required_props, entry.docs_url).unwrap();
+        }
+    }
+    sorted.push_str("];\n");
+    sorted
+}
@@ -0,0 +1,77 @@
+use crate::rows::records;
+
+/// Services (or single operations) that are only available in some regions of a partition, as `service,method,partition,region region`,
+/// with `*` as the method for all the operations of a service, e.g. `apprunner,*,aws,eu-west-1 us-east-1`.
+/// Generated from the endpoints of botocore, plus the rows in `input/service_regions.csv` of `aws-sdk-retrieve-required`.
+/// A service without a row for a partition is either available in all its regions or not known to be available at all
+const SERVICE_REGIONS: &str = include_str!("../required_properties_info/service_regions.csv");
+/// The regions of every partition, as `partition,region region`, e.g. `aws-cn,cn-north-1 cn-northwest-1`
+const PARTITIONS: &str = include_str!("../required_properties_info/partitions.csv");
+const ALL_OPERATIONS: &str = "*";
+
+/// The regions of the partition of `region` (e.g. `aws-cn` for `cn-north-1`) the method of the SDK is available in,
+/// or `None` when we know of no restriction in that partition. A row for the method itself wins over the row for the whole service
+pub fn available_regions(sdk: &str, method: &str, region: &str) -> Option<Vec<&'static str>> {
+    parse_available_regions(SERVICE_REGIONS, PARTITIONS, sdk, method, region)
+}
+
+fn parse_available_regions(
+    service_regions: &'static str,
+    partitions: &'static str,
+    sdk: &str,
+    method: &str,
+    region: &str,
+) -> Option<Vec<&'static str>> {
+    let partition = partition_of(partitions, region)?;
+    let rows: Vec<_> = records(service_regions)
+        .filter_map(Result::ok)
+        .filter(|r| {
+            r.fields.len() == 4 && r.field(0) == sdk && r.field(2) == partition && (r.field(1) == method || r.field(1) == ALL_OPERATIONS)
+        })
+        .collect();
+    rows.iter()
+        .find(|r| r.field(1) == method)
+        .or_else(|| rows.first())
+        .map(|r| r.static_field(3).split_whitespace().collect())
+}
+
+/// E.g. `aws-us-gov` for `us-gov-west-1`, `None` for regions that are in no partition, like `aws-global`
+fn partition_of(partitions: &'static str, region: &str) -> Option<&'static str> {
+    records(partitions)
+        .filter_map(Result::ok)
+        .find(|r| r.fields.len() == 2 && r.field(1).split_whitespace().any(|r| r == region))
+        .map(|r| r.static_field(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGIONS: &str = "apprunner,*,aws,eu-west-1 us-east-1\napprunner,list_services,aws,us-east-1\nqbusiness,invalid\n";
+    const TEST_PARTITIONS: &str = "aws,eu-south-1 eu-west-1 us-east-1\naws-cn,cn-north-1 cn-northwest-1\n";
+
+    #[test]
+    fn parse_available_regions_prefers_the_method() {
+        assert_eq!(
+            parse_available_regions(REGIONS, TEST_PARTITIONS, "apprunner", "create_service", "eu-south-1"),
+            Some(vec!["eu-west-1", "us-east-1"])
+        );
+        assert_eq!(parse_available_regions(REGIONS, TEST_PARTITIONS, "apprunner", "list_services", "eu-west-1"), Some(vec!["us-east-1"]));
+        assert_eq!(parse_available_regions(REGIONS, TEST_PARTITIONS, "qbusiness", "create_application", "eu-west-1"), None);
+        assert_eq!(parse_available_regions(REGIONS, TEST_PARTITIONS, "sqs", "send_message", "eu-west-1"), None);
+    }
+
+    #[test]
+    fn parse_available_regions_is_unknown_in_other_partitions() {
+        assert_eq!(parse_available_regions(REGIONS, TEST_PARTITIONS, "apprunner", "create_service", "cn-north-1"), None);
+        assert_eq!(parse_available_regions(REGIONS, TEST_PARTITIONS, "apprunner", "create_service", "aws-global"), None);
+    }
+
+    #[test]
+    fn service_regions_data_is_valid() {
+        assert!(available_regions("apprunner", "create_service", "eu-south-1").is_some_and(|r| r.contains(&"eu-west-1")));
+        assert_eq!(available_regions("sqs", "send_message", "eu-south-1"), None);
+        assert_eq!(partition_of(PARTITIONS, "us-gov-west-1"), Some("aws-us-gov"));
+        assert!(records(SERVICE_REGIONS).all(|r| r.is_ok_and(|r| r.fields.len() == 4)));
+    }
+}
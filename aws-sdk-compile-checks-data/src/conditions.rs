@@ -0,0 +1,60 @@
+use crate::rows::records;
+
+/// Properties that are only required in some situations, as `service,method,property,condition`.
+/// The condition completes the sentence "required when ...", and is maintained by hand because the documentation does not mark these.
+/// Quote the condition when it contains a comma
+const CONDITIONALLY_REQUIRED: &str = include_str!("../required_properties_info/conditionally_required.csv");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionallyRequired {
+    pub property: &'static str,
+    pub condition: &'static str,
+}
+
+/// The properties of the method that are required under some condition, in the order of the data
+pub fn conditionally_required(sdk: &str, method: &str) -> Vec<ConditionallyRequired> {
+    parse_conditions(CONDITIONALLY_REQUIRED, sdk, method)
+}
+
+fn parse_conditions(conditions: &'static str, sdk: &str, method: &str) -> Vec<ConditionallyRequired> {
+    records(conditions)
+        .filter_map(Result::ok)
+        .filter(|r| r.fields.len() == 4 && r.field(0) == sdk && r.field(1) == method)
+        .map(|r| ConditionallyRequired {
+            property: r.static_field(2),
+            condition: r.static_field(3),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONDITIONS: &str = "sqs,send_message,message_group_id,the queue is a FIFO queue\nsqs,send_message,invalid\nsns,publish,message_group_id,\"the topic is FIFO, which needs a group\"\n";
+
+    #[test]
+    fn parse_conditions_for_method_of_sdk() {
+        assert_eq!(
+            parse_conditions(CONDITIONS, "sqs", "send_message"),
+            vec![ConditionallyRequired {
+                property: "message_group_id",
+                condition: "the queue is a FIFO queue",
+            }]
+        );
+        assert!(parse_conditions(CONDITIONS, "connectparticipant", "send_message").is_empty());
+    }
+
+    #[test]
+    fn parse_conditions_keeps_commas_in_the_condition() {
+        let actual = parse_conditions(CONDITIONS, "sns", "publish");
+
+        assert_eq!(actual[0].condition, "the topic is FIFO, which needs a group");
+    }
+
+    #[test]
+    fn conditionally_required_data_is_valid() {
+        assert_eq!(conditionally_required("sqs", "send_message").len(), 2);
+        assert!(records(CONDITIONALLY_REQUIRED).all(|r| r.is_ok_and(|r| r.fields.len() == 4)));
+    }
+}
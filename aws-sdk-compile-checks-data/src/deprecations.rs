@@ -0,0 +1,57 @@
+use crate::rows::records;
+
+/// Deprecated operations and properties, as `service,method,property,note`.
+/// The property is empty when the operation itself is deprecated, the note (often with a replacement) can be empty as well, and is quoted when it contains a comma
+const DEPRECATED: &str = include_str!("../required_properties_info/deprecated.csv");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deprecation {
+    pub property: Option<&'static str>, // `None` when the operation is deprecated
+    pub note: Option<&'static str>,
+}
+
+/// Deprecations of the method and its properties, in the order of the data
+pub fn deprecations(sdk: &str, method: &str) -> Vec<Deprecation> {
+    parse_deprecations(DEPRECATED, sdk, method)
+}
+
+fn parse_deprecations(deprecations: &'static str, sdk: &str, method: &str) -> Vec<Deprecation> {
+    records(deprecations)
+        .filter_map(Result::ok)
+        .filter(|r| r.fields.len() >= 3 && r.field(0) == sdk && r.field(1) == method)
+        .map(|r| Deprecation {
+            property: Some(r.static_field(2)).filter(|p| !p.is_empty()),
+            note: Some(r.static_field(3)).filter(|n| !n.is_empty()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPRECATIONS: &str = "s3,get_bucket_lifecycle,,\"use `get_bucket_lifecycle_configuration` instead, it supports filters\"\ns3,list_objects,marker,\nsqs,send_message\n";
+
+    #[test]
+    fn parse_deprecated_operation_with_note() {
+        assert_eq!(
+            parse_deprecations(DEPRECATIONS, "s3", "get_bucket_lifecycle"),
+            vec![Deprecation {
+                property: None,
+                note: Some("use `get_bucket_lifecycle_configuration` instead, it supports filters"),
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_deprecated_property_without_note() {
+        assert_eq!(
+            parse_deprecations(DEPRECATIONS, "s3", "list_objects"),
+            vec![Deprecation {
+                property: Some("marker"),
+                note: None,
+            }]
+        );
+        assert!(parse_deprecations(DEPRECATIONS, "sqs", "send_message").is_empty());
+    }
+}
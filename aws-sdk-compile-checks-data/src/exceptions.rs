@@ -0,0 +1,104 @@
+use crate::rows::{is_snake_case, records, Record};
+
+/// Curated corrections of the documentation, as `service,method,property,required|optional,rationale`.
+/// `optional` suppresses a property the documentation marks as required, but the service (or SDK) tolerates,
+/// `required` adds one the documentation marks as optional. Quote the rationale when it contains a comma.
+/// `build.rs` merges these into the required properties, the rationale explains the difference in our errors
+const EXCEPTIONS: &str = include_str!("../required_properties_info/exceptions.csv");
+
+/// What the service actually needs, as opposed to what the documentation says
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Requirement {
+    Required,
+    Optional,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exception {
+    pub property: &'static str,
+    pub requirement: Requirement,
+    pub rationale: &'static str,
+}
+
+/// The exceptions for the method of the given SDK, in the order of the data
+pub fn exceptions(sdk: &str, method: &str) -> Vec<Exception> {
+    parse_exceptions(EXCEPTIONS, sdk, method)
+}
+
+fn parse_exceptions(exceptions: &'static str, sdk: &str, method: &str) -> Vec<Exception> {
+    records(exceptions)
+        .filter_map(Result::ok)
+        .filter(|r| r.field(0) == sdk && r.field(1) == method)
+        .filter_map(|r| {
+            let requirement = parse_exception(&r).ok()?;
+            Some(Exception {
+                property: r.static_field(2),
+                requirement,
+                rationale: r.static_field(4),
+            })
+        })
+        .collect()
+}
+
+/// Checks the record of an exception, returning what the service actually needs
+pub fn parse_exception(record: &Record) -> Result<Requirement, String> {
+    if record.fields.len() != 5 {
+        return Err(format!("expected 5 columns, found {}", record.fields.len()));
+    }
+    let (service, method, property, requirement, rationale) = (record.field(0), record.field(1), record.field(2), record.field(3), record.field(4));
+    if !is_snake_case(service) || !is_snake_case(method) {
+        return Err(format!("expected a lowercase service and method, found `{},{}`", service, method));
+    }
+    if !is_snake_case(property.strip_prefix("r#").unwrap_or(property)) {
+        return Err(format!("expected a snake_case property name, found `{}`", property));
+    }
+    if rationale.is_empty() {
+        return Err("expected a rationale, to explain why the documentation is wrong".to_string());
+    }
+    match requirement {
+        "required" => Ok(Requirement::Required),
+        "optional" => Ok(Requirement::Optional),
+        _ => Err(format!("expected `required` or `optional`, found `{}`", requirement)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXCEPTIONS: &str = "glacier,list_vaults,account_id,optional,\"the SDK sets it to `-`, when it is missing\"\nsqs,send_message,delay,maybe,unclear\nsqs,send_message,message_body,required,the service rejects empty messages\n";
+
+    #[test]
+    fn parse_exceptions_for_method_of_sdk() {
+        assert_eq!(
+            parse_exceptions(EXCEPTIONS, "glacier", "list_vaults"),
+            vec![Exception {
+                property: "account_id",
+                requirement: Requirement::Optional,
+                rationale: "the SDK sets it to `-`, when it is missing",
+            }]
+        );
+        assert!(parse_exceptions(EXCEPTIONS, "glacier", "list_jobs").is_empty());
+    }
+
+    #[test]
+    fn parse_exceptions_skips_invalid_requirements() {
+        let actual = parse_exceptions(EXCEPTIONS, "sqs", "send_message");
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].requirement, Requirement::Required);
+    }
+
+    #[test]
+    fn parse_exception_reports_what_is_wrong() {
+        let record = records("sqs,send_message,MessageBody,required,because").next().unwrap().unwrap();
+
+        assert_eq!(parse_exception(&record).unwrap_err(), "expected a snake_case property name, found `MessageBody`");
+    }
+
+    #[test]
+    fn exceptions_data_is_valid() {
+        assert!(records(super::EXCEPTIONS).all(|r| r.is_ok_and(|r| parse_exception(&r).is_ok())));
+        assert_eq!(exceptions("glacier", "upload_archive")[0].requirement, Requirement::Optional);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::rows::records;
+
+/// Groups of properties of which at most one can be set, as `service,method,properties,reason`, the properties separated by spaces.
+/// The reason explains why the service rejects the combination, and is maintained by hand because the documentation does not mark these.
+/// Quote the reason when it contains a comma
+const EXCLUSIVE_PROPERTIES: &str = include_str!("../required_properties_info/exclusive_properties.csv");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExclusiveGroup {
+    pub properties: Vec<&'static str>,
+    pub reason: &'static str,
+}
+
+/// The groups of mutually exclusive properties of the method, in the order of the data
+pub fn exclusive_groups(sdk: &str, method: &str) -> Vec<ExclusiveGroup> {
+    parse_exclusive_groups(EXCLUSIVE_PROPERTIES, sdk, method)
+}
+
+fn parse_exclusive_groups(groups: &'static str, sdk: &str, method: &str) -> Vec<ExclusiveGroup> {
+    records(groups)
+        .filter_map(Result::ok)
+        .filter(|r| r.fields.len() == 4 && r.field(0) == sdk && r.field(1) == method)
+        .map(|r| ExclusiveGroup {
+            properties: r.static_field(2).split_whitespace().collect(),
+            reason: r.static_field(3),
+        })
+        .filter(|group| group.properties.len() > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GROUPS: &str = "dynamodb,scan,scan_filter filter_expression,the legacy parameter cannot be combined with its expression\ndynamodb,scan,select,a group of one\nsqs,send_message,invalid\n";
+
+    #[test]
+    fn parse_exclusive_groups_for_method_of_sdk() {
+        assert_eq!(
+            parse_exclusive_groups(GROUPS, "dynamodb", "scan"),
+            vec![ExclusiveGroup {
+                properties: vec!["scan_filter", "filter_expression"],
+                reason: "the legacy parameter cannot be combined with its expression",
+            }]
+        );
+        assert!(parse_exclusive_groups(GROUPS, "sqs", "send_message").is_empty());
+    }
+
+    #[test]
+    fn exclusive_properties_data_is_valid() {
+        assert_eq!(exclusive_groups("dynamodb", "query").len(), 3);
+        assert_eq!(exclusive_groups("sns", "publish")[0].properties, vec!["topic_arn", "target_arn", "phone_number"]);
+        assert!(records(EXCLUSIVE_PROPERTIES).all(|r| r.is_ok_and(|r| r.fields.len() == 4 && r.field(2).split_whitespace().count() > 1)));
+    }
+}
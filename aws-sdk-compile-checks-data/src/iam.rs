@@ -0,0 +1,73 @@
+use crate::rows::records;
+
+/// The prefix of the IAM actions of SDKs whose prefix is not their name, as `service,prefix`, e.g. `cloudwatchlogs,logs`.
+/// Maintained by hand
+const IAM_PREFIXES: &str = include_str!("../required_properties_info/iam_prefixes.csv");
+/// Operations whose IAM action is not named after them, as `service,method,action action`, e.g. `lambda,invoke,lambda:InvokeFunction`.
+/// Like the required properties, an action can be a group of alternatives separated by `|`, of which one is enough.
+/// Maintained by hand
+const IAM_ACTIONS: &str = include_str!("../required_properties_info/iam_actions.csv");
+
+/// The IAM actions a call of the method needs, e.g. `sqs:SendMessage` for SQS `send_message`.
+/// Most actions are the prefix of the SDK followed by the name of the operation, the others are in our data
+pub fn iam_actions(sdk: &str, method: &str) -> Vec<String> {
+    parse_iam_actions(IAM_ACTIONS, IAM_PREFIXES, sdk, method)
+}
+
+fn parse_iam_actions(actions: &'static str, prefixes: &'static str, sdk: &str, method: &str) -> Vec<String> {
+    let listed: Vec<String> = records(actions)
+        .filter_map(Result::ok)
+        .filter(|r| r.fields.len() == 3 && r.field(0) == sdk && r.field(1) == method)
+        .flat_map(|r| r.static_field(2).split_whitespace())
+        .map(|action| action.to_string())
+        .collect();
+    if !listed.is_empty() {
+        return listed;
+    }
+    let prefix = records(prefixes)
+        .filter_map(Result::ok)
+        .find(|r| r.fields.len() == 2 && r.field(0) == sdk)
+        .map(|r| r.static_field(1))
+        .unwrap_or(sdk);
+    vec![format!("{}:{}", prefix, operation_name(method))]
+}
+
+/// The name of the operation in the API of the service, e.g. `ListObjectsV2` for `list_objects_v2`
+fn operation_name(method: &str) -> String {
+    method
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACTIONS: &str = "lambda,invoke,lambda:InvokeFunction\ns3,copy_object,s3:GetObject s3:PutObject\nsqs,invalid\n";
+    const PREFIXES: &str = "cloudwatchlogs,logs\n";
+
+    #[test]
+    fn parse_iam_actions_of_method() {
+        assert_eq!(parse_iam_actions(ACTIONS, PREFIXES, "lambda", "invoke"), vec!["lambda:InvokeFunction"]);
+        assert_eq!(parse_iam_actions(ACTIONS, PREFIXES, "s3", "copy_object"), vec!["s3:GetObject", "s3:PutObject"]);
+        assert_eq!(parse_iam_actions(ACTIONS, PREFIXES, "sqs", "send_message"), vec!["sqs:SendMessage"]);
+        assert_eq!(parse_iam_actions(ACTIONS, PREFIXES, "cloudwatchlogs", "put_log_events"), vec!["logs:PutLogEvents"]);
+    }
+
+    #[test]
+    fn iam_actions_data_is_valid() {
+        assert_eq!(iam_actions("s3", "list_objects_v2"), vec!["s3:ListBucket"]);
+        assert_eq!(iam_actions("bedrockruntime", "invoke_model"), vec!["bedrock:InvokeModel"]);
+        assert!(records(IAM_ACTIONS).all(|r| r.is_ok_and(|r| r.fields.len() == 3 && r.field(2).split(['|', ' ']).all(|a| a.contains(':')))));
+    }
+
+    #[test]
+    fn operation_name_of_methods() {
+        assert_eq!(operation_name("list_objects_v2"), "ListObjectsV2");
+        assert_eq!(operation_name("send_message"), "SendMessage");
+    }
+}
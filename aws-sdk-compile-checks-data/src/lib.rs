@@ -0,0 +1,15 @@
+#![doc = include_str!("../README.md")]
+
+pub mod availability;
+pub mod conditions;
+pub mod deprecations;
+pub mod exceptions;
+pub mod exclusive;
+pub mod iam;
+pub mod operations;
+pub mod policies;
+pub mod ranges;
+pub mod recommendations;
+pub mod required_properties;
+pub mod requirement_changes;
+pub mod rows;
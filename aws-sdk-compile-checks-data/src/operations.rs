@@ -0,0 +1,212 @@
+use std::sync::OnceLock;
+
+use crate::rows::records;
+
+/// What operations support besides `send`, as `service,method,capability capability`, e.g. `s3,head_object,presign waiter`,
+/// or `dynamodb,import_table,idempotency_token=client_token` for the setter of an idempotency token.
+/// Operations without any of these capabilities are not listed
+const OPERATION_METADATA: &str = include_str!("../required_properties_info/operation_metadata.csv");
+/// Operations that have a variant for many items at once, as `service,method,batch method`, e.g. `sqs,send_message,send_message_batch`.
+/// Maintained by hand
+const BATCH_VARIANTS: &str = include_str!("../required_properties_info/batch_variants.csv");
+/// The waiters of the clients, as `service,waiter,operation`, e.g. `dynamodb,wait_until_table_exists,describe_table`
+const WAITERS: &str = include_str!("../required_properties_info/waiters.csv");
+
+const SEND: &str = "send";
+const INTO_PAGINATOR: &str = "into_paginator";
+const PRESIGNED: &str = "presigned";
+const WAIT: &str = "wait"; // ends the chain of a waiter, e.g. `wait_until_table_exists().table_name(name).wait(max_wait)`
+const IDEMPOTENCY_TOKEN_PREFIX: &str = "idempotency_token="; // followed by the setter of the token, e.g. `idempotency_token=client_token`
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Paginator, // `into_paginator()` instead of `send()`
+    Presign,   // `presigned(config)` instead of `send()`
+    Waiter,    // the client has a `wait_until_...` that uses this operation
+    IdempotencyToken(&'static str), // accepts a token that makes retries safe, with the name of its setter, e.g. `client_token`
+}
+
+#[derive(Debug, PartialEq)]
+struct OperationMetadata {
+    service: &'static str,
+    method: &'static str,
+    capabilities: Vec<Capability>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Waiter {
+    service: &'static str,
+    waiter: &'static str,
+    operation: &'static str,
+}
+
+/// The capabilities of the method in the given SDK
+pub fn capabilities(sdk: &str, method: &str) -> Vec<Capability> {
+    metadata()
+        .iter()
+        .find(|m| m.service == sdk && m.method == method)
+        .map(|m| m.capabilities.clone())
+        .unwrap_or_default()
+}
+
+/// The setter of the idempotency token of the method in the given SDK, e.g. `client_token` for DynamoDB `import_table`
+pub fn idempotency_token(sdk: &str, method: &str) -> Option<&'static str> {
+    capabilities(sdk, method).into_iter().find_map(|c| match c {
+        Capability::IdempotencyToken(setter) => Some(setter),
+        _ => None,
+    })
+}
+
+/// The (service, operation) of the waiters with this name, e.g. (`dynamodb`, `describe_table`) for `wait_until_table_exists`.
+/// A waiter requires the same properties as its operation
+pub fn waiter_operations(waiter: &str) -> Vec<(&'static str, &'static str)> {
+    waiters().iter().filter(|w| w.waiter == waiter).map(|w| (w.service, w.operation)).collect()
+}
+
+/// The operation that does the same for many items at once, e.g. `batch_write_item` for DynamoDB `put_item`
+pub fn batch_variant(sdk: &str, method: &str) -> Option<&'static str> {
+    parse_batch_variant(BATCH_VARIANTS, sdk, method)
+}
+
+/// The calls that can end a chain of the method, in any of the SDKs that have it.
+/// We need these before we know the SDK, to tell where a chain ends
+pub fn terminators(method: &str) -> Vec<&'static str> {
+    if waiters().iter().any(|w| w.waiter == method) {
+        return vec![WAIT];
+    }
+    let capabilities: Vec<Capability> = metadata().iter().filter(|m| m.method == method).flat_map(|m| m.capabilities.iter().copied()).collect();
+    let mut terminators = vec![SEND];
+    if capabilities.contains(&Capability::Paginator) {
+        terminators.push(INTO_PAGINATOR);
+    }
+    if capabilities.contains(&Capability::Presign) {
+        terminators.push(PRESIGNED);
+    }
+    terminators
+}
+
+fn metadata() -> &'static [OperationMetadata] {
+    // parsed once, and only when a chain is checked
+    static METADATA: OnceLock<Vec<OperationMetadata>> = OnceLock::new();
+    METADATA.get_or_init(|| parse_metadata(OPERATION_METADATA))
+}
+
+fn parse_batch_variant(batch_variants: &'static str, sdk: &str, method: &str) -> Option<&'static str> {
+    records(batch_variants)
+        .filter_map(Result::ok)
+        .find(|r| r.fields.len() == 3 && r.field(0) == sdk && r.field(1) == method)
+        .map(|r| r.static_field(2))
+}
+
+fn waiters() -> &'static [Waiter] {
+    static WAITERS_OF_CLIENTS: OnceLock<Vec<Waiter>> = OnceLock::new();
+    WAITERS_OF_CLIENTS.get_or_init(|| parse_waiters(WAITERS))
+}
+
+fn parse_waiters(waiters: &'static str) -> Vec<Waiter> {
+    records(waiters)
+        .filter_map(Result::ok)
+        .filter(|r| r.fields.len() == 3)
+        .map(|r| Waiter {
+            service: r.static_field(0),
+            waiter: r.static_field(1),
+            operation: r.static_field(2),
+        })
+        .collect()
+}
+
+fn parse_metadata(metadata: &'static str) -> Vec<OperationMetadata> {
+    records(metadata)
+        .filter_map(Result::ok)
+        .filter(|r| r.fields.len() == 3)
+        .map(|r| {
+            let (service, method, capabilities) = (r.static_field(0), r.static_field(1), r.static_field(2));
+            let capabilities = capabilities
+                .split_whitespace()
+                .filter_map(|c| match c {
+                    "paginator" => Some(Capability::Paginator),
+                    "presign" => Some(Capability::Presign),
+                    "waiter" => Some(Capability::Waiter),
+                    c => c.strip_prefix(IDEMPOTENCY_TOKEN_PREFIX).filter(|setter| !setter.is_empty()).map(Capability::IdempotencyToken),
+                })
+                .collect();
+            OperationMetadata { service, method, capabilities }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_metadata_skips_unknown_capabilities_and_invalid_lines() {
+        let actual = parse_metadata("s3,head_object,presign teleport waiter\ns3,invalid\ndynamodb,import_table,idempotency_token=client_token idempotency_token=\n");
+
+        assert_eq!(
+            actual,
+            vec![
+                OperationMetadata {
+                    service: "s3",
+                    method: "head_object",
+                    capabilities: vec![Capability::Presign, Capability::Waiter],
+                },
+                OperationMetadata {
+                    service: "dynamodb",
+                    method: "import_table",
+                    capabilities: vec![Capability::IdempotencyToken("client_token")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn terminators_of_methods() {
+        assert_eq!(terminators("send_message"), vec!["send"]);
+        assert_eq!(terminators("list_objects_v2"), vec!["send", "into_paginator"]);
+        assert_eq!(terminators("get_object"), vec!["send", "presigned"]);
+        assert_eq!(terminators("wait_until_table_exists"), vec!["wait"]);
+    }
+
+    #[test]
+    fn parse_waiters_skips_invalid_lines() {
+        let actual = parse_waiters("s3,wait_until_bucket_exists,head_bucket\ns3,wait_until_object_exists\n");
+
+        assert_eq!(
+            actual,
+            vec![Waiter {
+                service: "s3",
+                waiter: "wait_until_bucket_exists",
+                operation: "head_bucket",
+            }]
+        );
+    }
+
+    #[test]
+    fn batch_variant_of_method_in_sdk() {
+        assert_eq!(parse_batch_variant("sqs,send_message,send_message_batch\nsqs,delete_message\n", "sqs", "send_message"), Some("send_message_batch"));
+        assert_eq!(parse_batch_variant("sqs,send_message,send_message_batch\nsqs,delete_message\n", "sqs", "delete_message"), None);
+        assert_eq!(batch_variant("dynamodb", "put_item"), Some("batch_write_item"));
+        assert_eq!(batch_variant("connectparticipant", "send_message"), None);
+    }
+
+    #[test]
+    fn waiter_operations_of_waiter() {
+        assert_eq!(waiter_operations("wait_until_table_exists"), vec![("dynamodb", "describe_table")]);
+        assert_eq!(waiter_operations("wait_until_object_not_exists"), vec![("s3", "head_object")]);
+        assert!(waiter_operations("describe_table").is_empty());
+    }
+
+    #[test]
+    fn capabilities_of_method_in_sdk() {
+        assert_eq!(capabilities("s3", "head_object"), vec![Capability::Presign, Capability::Waiter]);
+        assert!(capabilities("sqs", "send_message").is_empty());
+    }
+
+    #[test]
+    fn idempotency_token_of_method_in_sdk() {
+        assert_eq!(idempotency_token("dynamodb", "transact_write_items"), Some("client_request_token"));
+        assert_eq!(idempotency_token("sagemaker", "create_model_package"), Some("client_token"));
+        assert_eq!(idempotency_token("dynamodb", "put_item"), None);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::rows::records;
+
+/// Properties that a policy requires on top of the properties the SDK requires, as `policy,service,method,prop1 prop2`.
+/// Like the required properties, a property can be a group of alternatives separated by `|`.
+/// Maintained by hand, for the policies the macro ships with (e.g. `policy = encryption`)
+const POLICIES: &str = include_str!("../required_properties_info/policies.csv");
+
+/// Separates the setter of a map from the key it needs, e.g. `attributes:KmsMasterKeyId`
+const MAP_KEY_SEPARATOR: char = ':';
+
+/// The properties the policy requires for the method of the SDK, empty when the policy has nothing to say about it
+pub fn policy_props(policy: &str, sdk: &str, method: &str) -> Vec<&'static str> {
+    parse_policy_props(POLICIES, policy, sdk, method)
+}
+
+/// The setter and key of a property that is an entry of a map, e.g. (`attributes`, `KmsMasterKeyId`) for `attributes:KmsMasterKeyId`.
+/// The key is the variant of an enum (e.g. `QueueAttributeName::KmsMasterKeyId`) or the string that is passed as first argument
+pub fn map_key(property: &str) -> Option<(&str, &str)> {
+    property.split_once(MAP_KEY_SEPARATOR)
+}
+
+fn parse_policy_props(policies: &'static str, policy: &str, sdk: &str, method: &str) -> Vec<&'static str> {
+    records(policies)
+        .filter_map(Result::ok)
+        .filter(|r| r.fields.len() == 4 && r.field(0) == policy && r.field(1) == sdk && r.field(2) == method)
+        .flat_map(|r| r.static_field(3).split_whitespace())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICIES_FOR_TESTS: &str = "encryption,s3,put_object,server_side_encryption|ssekms_key_id\nencryption,sqs,create_queue\nlogging,s3,put_object,expected_bucket_owner tagging\n";
+
+    #[test]
+    fn parse_policy_props_for_method_of_sdk() {
+        assert_eq!(parse_policy_props(POLICIES_FOR_TESTS, "encryption", "s3", "put_object"), vec!["server_side_encryption|ssekms_key_id"]);
+        assert_eq!(parse_policy_props(POLICIES_FOR_TESTS, "logging", "s3", "put_object"), vec!["expected_bucket_owner", "tagging"]);
+        assert!(parse_policy_props(POLICIES_FOR_TESTS, "encryption", "sqs", "create_queue").is_empty());
+        assert!(parse_policy_props(POLICIES_FOR_TESTS, "encryption", "s3", "get_object").is_empty());
+    }
+
+    #[test]
+    fn map_key_of_property() {
+        assert_eq!(map_key("attributes:KmsMasterKeyId"), Some(("attributes", "KmsMasterKeyId")));
+        assert_eq!(map_key("server_side_encryption"), None);
+    }
+
+    #[test]
+    fn encryption_policy_data_is_valid() {
+        assert_eq!(policy_props("encryption", "sqs", "create_queue"), vec!["attributes:KmsMasterKeyId|attributes:SqsManagedSseEnabled"]);
+        assert!(records(POLICIES).all(|r| r.is_ok_and(|r| r.fields.len() == 4)));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::rows::records;
+
+/// Numeric properties with a minimum and maximum, as `service,method,property,min,max`, both bounds inclusive.
+/// Values outside the range are rejected by the service, and are maintained by hand from the API reference of the service
+const PROPERTY_RANGES: &str = include_str!("../required_properties_info/property_ranges.csv");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyRange {
+    pub property: &'static str,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl PropertyRange {
+    pub fn contains(&self, value: i64) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+/// The properties of the method that only accept values in a range, in the order of the data
+pub fn property_ranges(sdk: &str, method: &str) -> Vec<PropertyRange> {
+    parse_property_ranges(PROPERTY_RANGES, sdk, method)
+}
+
+fn parse_property_ranges(ranges: &'static str, sdk: &str, method: &str) -> Vec<PropertyRange> {
+    records(ranges)
+        .filter_map(Result::ok)
+        .filter(|r| r.fields.len() == 5 && r.field(0) == sdk && r.field(1) == method)
+        .filter_map(|r| {
+            Some(PropertyRange {
+                property: r.static_field(2),
+                min: r.field(3).parse().ok()?,
+                max: r.field(4).parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RANGES: &str = "sqs,receive_message,max_number_of_messages,1,10\nsqs,receive_message,wait_time_seconds,zero,20\nsqs,send_message,delay_seconds,0\n";
+
+    #[test]
+    fn parse_property_ranges_for_method_of_sdk() {
+        assert_eq!(
+            parse_property_ranges(RANGES, "sqs", "receive_message"),
+            vec![PropertyRange {
+                property: "max_number_of_messages",
+                min: 1,
+                max: 10,
+            }]
+        );
+        assert!(parse_property_ranges(RANGES, "sqs", "send_message").is_empty());
+    }
+
+    #[test]
+    fn property_range_contains_both_bounds() {
+        let range = PropertyRange {
+            property: "delay_seconds",
+            min: 0,
+            max: 900,
+        };
+
+        assert!(range.contains(0));
+        assert!(range.contains(900));
+        assert!(!range.contains(-1));
+        assert!(!range.contains(901));
+    }
+
+    #[test]
+    fn property_ranges_data_is_valid() {
+        assert_eq!(property_ranges("sqs", "receive_message").len(), 3);
+        assert!(records(PROPERTY_RANGES).all(|r| r.is_ok_and(|r| {
+            let bounds: (Result<i64, _>, Result<i64, _>) = (r.field(3).parse(), r.field(4).parse());
+            r.fields.len() == 5 && matches!(bounds, (Ok(min), Ok(max)) if min <= max)
+        })));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::rows::records;
+
+/// Properties that are not required, but that most calls should set, as `service,method,property,reason`.
+/// The reason explains what happens without the property, and is maintained by hand.
+/// Quote the reason when it contains a comma
+const RECOMMENDED_PROPS: &str = include_str!("../required_properties_info/recommended_props.csv");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recommended {
+    pub property: &'static str,
+    pub reason: &'static str,
+}
+
+/// The properties we recommend for the method, in the order of the data
+pub fn recommended_props(sdk: &str, method: &str) -> Vec<Recommended> {
+    parse_recommendations(RECOMMENDED_PROPS, sdk, method)
+}
+
+fn parse_recommendations(recommendations: &'static str, sdk: &str, method: &str) -> Vec<Recommended> {
+    records(recommendations)
+        .filter_map(Result::ok)
+        .filter(|r| r.fields.len() == 4 && r.field(0) == sdk && r.field(1) == method)
+        .map(|r| Recommended {
+            property: r.static_field(2),
+            reason: r.static_field(3),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECOMMENDATIONS: &str = "s3,put_object,content_type,\"without it, the type is unknown\"\ns3,put_object,invalid\nsqs,send_message,message_attributes,they carry metadata\n";
+
+    #[test]
+    fn parse_recommendations_for_method_of_sdk() {
+        assert_eq!(
+            parse_recommendations(RECOMMENDATIONS, "s3", "put_object"),
+            vec![Recommended {
+                property: "content_type",
+                reason: "without it, the type is unknown",
+            }]
+        );
+        assert!(parse_recommendations(RECOMMENDATIONS, "s3", "get_object").is_empty());
+    }
+
+    #[test]
+    fn recommended_props_data_is_valid() {
+        assert_eq!(recommended_props("s3", "put_object").len(), 1);
+        assert!(records(RECOMMENDED_PROPS).all(|r| r.is_ok_and(|r| r.fields.len() == 4)));
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use crate::rows::FLUENT_BUILDER_SUFFIX;
+
+/// Names of all AWS SDK crates we know about (without `aws-sdk-`), including those without rows in our data
+const KNOWN_SDKS: &str = include_str!("../required_properties_info/known_sdks");
+const DATA_VERSION: &str = include_str!("../required_properties_info/data_version"); // month of retrieval and SDK version, e.g. `2024-03,1.18.0`
+/// `service,month,SDK version` for the SDKs whose data is from another snapshot than `DATA_VERSION`,
+/// like those retrieved from the documentation of a newer version, e.g. `s3tables,2026-10,1.71.0`
+const SERVICE_DATA_VERSIONS: &str = include_str!("../required_properties_info/service_data_versions.csv");
+
+/// Separates the alternatives of a group of properties where at least one is required, e.g. `topic_arn|target_arn|phone_number`
+const ONE_OF_SEPARATOR: char = '|';
+/// The input of an operation is named after it, like its fluent builder, e.g. `SendMessageInput` and `SendMessageFluentBuilder`
+const INPUT_SUFFIX: &str = "Input";
+
+/// SDKs that have a given method, with the properties they require for it.
+/// Identical lists of properties point to the same static slice.
+pub type PropsBySdk = HashMap<&'static str, &'static [&'static str]>;
+/// Method name to the range in `METHODS_BLOB` with the (index in `SERVICES`, index in `PROPS`, docs URL) of that method
+type StaticRequiredProperties = phf::Map<&'static str, (usize, usize)>;
+/// Name of a type in the `types` module of the SDKs to the SDKs that have it, with the properties its builder requires
+type StaticTypeRequiredProperties = phf::Map<&'static str, &'static [(&'static str, &'static [&'static str])]>;
+/// Name of a fluent builder to the (service, method) of the operations that have a builder with that name
+type StaticFluentBuilders = phf::Map<&'static str, &'static [(&'static str, &'static str)]>;
+/// (method, service, properties, docs URL), the URL being empty when we do not know it
+#[cfg(feature = "sorted-lookup")]
+type SortedEntry = (&'static str, &'static str, &'static [&'static str], &'static str);
+
+// generated by `build.rs` from `required_properties_info/required_props_info.csv`, with the corrections of `exceptions.csv`
+include!(concat!(env!("OUT_DIR"), "/required_props_table.rs"));
+
+/// Whether any SDK has a method with this name.
+/// Backed by static data generated at build time, so only the methods we look up are decoded
+pub fn contains_method(method: &str) -> bool {
+    #[cfg(not(feature = "sorted-lookup"))]
+    return REQUIRED_PROPS.contains_key(method);
+    #[cfg(feature = "sorted-lookup")]
+    return !entries_for_method(SORTED_REQUIRED_PROPS, method).is_empty();
+}
+
+/// The SDKs that have a method with this name, with the properties they require for it
+pub fn required_props(method: &str) -> Option<PropsBySdk> {
+    #[cfg(not(feature = "sorted-lookup"))]
+    return REQUIRED_PROPS.get(method).map(|(start, end)| decode_method(&METHODS_BLOB[*start..*end]));
+    #[cfg(feature = "sorted-lookup")]
+    return {
+        let entries = entries_for_method(SORTED_REQUIRED_PROPS, method);
+        (!entries.is_empty()).then(|| entries.iter().map(|(_, service_name, required_props, _)| (*service_name, *required_props)).collect())
+    };
+}
+
+/// Link to the documentation of the builder of the method in the given SDK, if the data has one
+pub fn docs_url(method: &str, sdk: &str) -> Option<&'static str> {
+    #[cfg(not(feature = "sorted-lookup"))]
+    let docs_url = {
+        let (start, end) = REQUIRED_PROPS.get(method)?;
+        decode_entries(&METHODS_BLOB[*start..*end])
+            .into_iter()
+            .find(|(service_index, _, _)| SERVICES[*service_index as usize] == sdk)
+            .map(|(_, _, docs_url)| docs_url)
+    };
+    #[cfg(feature = "sorted-lookup")]
+    let docs_url = entries_for_method(SORTED_REQUIRED_PROPS, method)
+        .iter()
+        .find(|(_, service_name, _, _)| *service_name == sdk)
+        .map(|(_, _, _, docs_url)| *docs_url);
+    docs_url.filter(|url| !url.is_empty())
+}
+
+/// Whether the SDK has at least one method in our data
+pub fn contains_sdk(sdk: &str) -> bool {
+    SERVICES.binary_search(&sdk).is_ok()
+}
+
+/// The (SDK, method) of the operations with a fluent builder of this name, e.g. (`sqs`, `send_message`) for `SendMessageFluentBuilder`
+pub fn operations_of_builder(builder: &str) -> &'static [(&'static str, &'static str)] {
+    FLUENT_BUILDERS.get(builder).copied().unwrap_or_default()
+}
+
+/// The (SDK, method) of the operations with an input of this name, e.g. (`sqs`, `send_message`) for `SendMessageInput`.
+/// The builder of an input (for the low-level API, e.g. `SendMessageInput::builder()`) requires the same properties as the fluent builder
+pub fn operations_of_input(input: &str) -> &'static [(&'static str, &'static str)] {
+    match input.strip_suffix(INPUT_SUFFIX) {
+        Some(operation) if !operation.is_empty() => operations_of_builder(&format!("{}{}", operation, FLUENT_BUILDER_SUFFIX)),
+        _ => &[],
+    }
+}
+
+/// SDKs that have a type (in their `types` module) with the given name, with the properties its builder requires
+pub fn type_required_props(type_name: &str) -> Option<PropsBySdk> {
+    TYPE_REQUIRED_PROPS.get(type_name).map(|services| services.iter().copied().collect())
+}
+
+/// Whether an AWS SDK with this name (without `aws-sdk-`) exists, even when our data has nothing for it
+pub fn is_known_sdk(sdk: &str) -> bool {
+    KNOWN_SDKS.lines().any(|known| known.trim() == sdk)
+}
+
+/// The AWS SDKs whose name (without `aws-sdk-`) starts with the given prefix, e.g. `dynamodb` and `dynamodbstreams` for `dynamo`
+pub fn known_sdks_starting_with(prefix: &str) -> impl Iterator<Item = &'static str> + '_ {
+    KNOWN_SDKS.lines().map(str::trim).filter(move |known| known.starts_with(prefix))
+}
+
+/// The properties that can satisfy a required property: the property itself, or every alternative of an 'at least one of' group
+pub fn alternatives(required_prop: &str) -> impl Iterator<Item = &str> {
+    required_prop.split(ONE_OF_SEPARATOR)
+}
+
+/// Describes the snapshot of AWS SDK data, so users can tell whether a finding might be caused by stale data
+pub fn data_version() -> String {
+    describe_data_version(DATA_VERSION)
+}
+
+/// Like [`data_version`], for the snapshot the data of the SDK comes from
+pub fn data_version_of(sdk: &str) -> String {
+    describe_data_version(service_data_version(SERVICE_DATA_VERSIONS, sdk).unwrap_or(DATA_VERSION))
+}
+
+/// The SDK version of the documentation our data was retrieved from, e.g. `1.18.0`
+pub fn data_sdk_version() -> Option<&'static str> {
+    sdk_version_of_data_version(DATA_VERSION)
+}
+
+/// Like [`data_sdk_version`], for the documentation the data of the SDK was retrieved from
+pub fn data_sdk_version_of(sdk: &str) -> Option<&'static str> {
+    sdk_version_of_data_version(service_data_version(SERVICE_DATA_VERSIONS, sdk).unwrap_or(DATA_VERSION))
+}
+
+/// The `month,SDK version` of the SDK, when its data is from another snapshot
+fn service_data_version<'a>(versions: &'a str, sdk: &str) -> Option<&'a str> {
+    versions.lines().find_map(|line| line.strip_prefix(sdk)?.strip_prefix(','))
+}
+
+fn sdk_version_of_data_version(version: &str) -> Option<&str> {
+    version.trim().split_once(',').map(|(_, sdk_version)| sdk_version)
+}
+
+/// The month our data was retrieved, e.g. `2024-03`, which sorts like a date
+pub fn data_month() -> &'static str {
+    month_of_data_version(DATA_VERSION)
+}
+
+fn month_of_data_version(version: &str) -> &str {
+    let version = version.trim();
+    version.split_once(',').map_or(version, |(month, _)| month)
+}
+
+fn describe_data_version(version: &str) -> String {
+    match version.trim().split_once(',') {
+        Some((month, sdk_version)) => format!("aws-sdk data {}, SDK {}", month, sdk_version),
+        None => format!("aws-sdk data {}", version.trim()),
+    }
+}
+
+/// The (consecutive) entries of a method in the sorted table, found with two binary searches
+#[cfg(feature = "sorted-lookup")]
+fn entries_for_method<'a>(sorted: &'a [SortedEntry], method: &str) -> &'a [SortedEntry] {
+    let start = sorted.partition_point(|(m, _, _, _)| *m < method);
+    let end = start + sorted[start..].partition_point(|(m, _, _, _)| *m == method);
+    &sorted[start..end]
+}
+
+#[cfg_attr(feature = "sorted-lookup", allow(dead_code))]
+fn decode_method(bytes: &'static [u8]) -> PropsBySdk {
+    decode_entries(bytes)
+        .into_iter()
+        .map(|(service_index, props_index, _)| (SERVICES[service_index as usize], PROPS[props_index as usize]))
+        .collect()
+}
+
+/// The URLs are borrowed from the blob, so decoding does not copy them
+#[cfg_attr(feature = "sorted-lookup", allow(dead_code))]
+fn decode_entries(bytes: &'static [u8]) -> Vec<(u32, u32, &'static str)> {
+    postcard::from_bytes(bytes).expect("data generated by build.rs to be valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_table_contains_the_csv_data() {
+        assert!(contains_method("send_message"));
+        assert!(!contains_method("not_an_sdk_method"));
+        assert_eq!(required_props("receive_message").unwrap().get("sqs"), Some(&&["queue_url"][..]));
+        let send_message = required_props("send_message").unwrap();
+        assert_eq!(send_message.keys().count(), 2);
+        assert_eq!(send_message.get("sqs"), Some(&&["queue_url", "message_body"][..]));
+        assert!(contains_sdk("connectparticipant"));
+        assert!(!contains_sdk("not_an_sdk"));
+        assert!(known_sdks_starting_with("dynamo").any(|sdk| sdk == "dynamodbstreams"));
+        assert_eq!(docs_url("send_message", "not_an_sdk"), None);
+        assert_eq!(docs_url("not_an_sdk_method", "sqs"), None);
+    }
+
+    #[test]
+    #[cfg(not(feature = "sorted-lookup"))]
+    fn test_generated_table_shares_identical_property_lists() {
+        let tag_resource = required_props("tag_resource").unwrap();
+        let (ecs, sns) = (tag_resource["ecs"], tag_resource["sns"]);
+        assert_eq!(ecs, sns);
+        assert!(std::ptr::eq(ecs, sns));
+    }
+
+    #[test]
+    #[cfg(feature = "sorted-lookup")]
+    fn test_sorted_lookup_finds_the_same_properties_as_the_hash_map() {
+        for (method, (start, end)) in REQUIRED_PROPS.entries() {
+            assert_eq!(required_props(method), Some(decode_method(&METHODS_BLOB[*start..*end])), "different properties for {}", method);
+        }
+        assert_eq!(required_props("not_an_sdk_method"), None);
+    }
+
+    #[test]
+    fn test_generated_table_contains_at_least_one_of_groups() {
+        let publish = required_props("publish").unwrap();
+        assert_eq!(publish.get("sns"), Some(&&["message", "topic_arn|target_arn|phone_number"][..]));
+        assert_eq!(alternatives(publish["sns"][1]).collect::<Vec<_>>(), vec!["topic_arn", "target_arn", "phone_number"]);
+    }
+
+    #[test]
+    fn test_generated_table_contains_bedrock_operations() {
+        assert_eq!(required_props("converse").unwrap().get("bedrockruntime"), Some(&&["model_id"][..]));
+        assert_eq!(required_props("invoke_model").unwrap().get("bedrockruntime"), Some(&&["model_id"][..]));
+        assert_eq!(
+            required_props("create_guardrail").unwrap().get("bedrock"),
+            Some(&&["name", "blocked_input_messaging", "blocked_outputs_messaging"][..])
+        );
+    }
+
+    #[test]
+    fn test_generated_table_contains_recently_launched_sdks() {
+        assert_eq!(required_props("create_table_bucket").unwrap().get("s3tables"), Some(&&["name"][..]));
+        assert_eq!(required_props("query_vectors").unwrap().get("s3vectors"), Some(&&["top_k", "query_vector"][..]));
+        assert!(is_known_sdk("s3vectors"));
+    }
+
+    #[test]
+    fn test_generated_table_applies_the_exceptions() {
+        assert_eq!(required_props("upload_archive").unwrap().get("glacier"), Some(&&["vault_name"][..]));
+        assert_eq!(required_props("list_vaults").unwrap().get("glacier"), Some(&&[][..]));
+    }
+
+    #[test]
+    fn test_generated_builder_table_maps_builders_to_operations() {
+        let mut send_message = operations_of_builder("SendMessageFluentBuilder").to_vec();
+        send_message.sort_unstable();
+        assert_eq!(send_message, vec![("connectparticipant", "send_message"), ("sqs", "send_message")]);
+        assert_eq!(operations_of_builder("ListObjectsV2FluentBuilder"), &[("s3", "list_objects_v2")]);
+        assert!(operations_of_builder("NotAFluentBuilder").is_empty());
+    }
+
+    #[test]
+    fn test_operations_of_input_are_those_of_the_fluent_builder() {
+        let mut send_message = operations_of_input("SendMessageInput").to_vec();
+        send_message.sort_unstable();
+        assert_eq!(send_message, vec![("connectparticipant", "send_message"), ("sqs", "send_message")]);
+        assert_eq!(operations_of_input("ListObjectsV2Input"), &[("s3", "list_objects_v2")]);
+        assert!(operations_of_input("Input").is_empty());
+        assert!(operations_of_input("SendMessageOutput").is_empty());
+    }
+
+    #[test]
+    fn test_generated_type_table_contains_the_csv_data() {
+        let put = type_required_props("Put").unwrap();
+
+        assert_eq!(put.get("dynamodb"), Some(&&["item", "table_name"][..]));
+        assert_eq!(type_required_props("Tag").unwrap().get("s3"), Some(&&["key", "value"][..]));
+        assert!(type_required_props("Tag").unwrap().len() > 1);
+        assert_eq!(type_required_props("TransactWriteItem").unwrap().get("dynamodb"), Some(&&["condition_check|put|delete|update"][..]));
+        assert!(type_required_props("NotAType").is_none());
+    }
+
+    #[test]
+    fn test_known_sdks_include_those_without_data() {
+        assert!(is_known_sdk("qldbsession"));
+        assert!(!contains_sdk("qldbsession"));
+        assert!(!is_known_sdk("not_an_sdk"));
+    }
+
+    #[test]
+    fn test_describe_data_version_with_month_and_sdk_version() {
+        assert_eq!(describe_data_version("2024-03,1.18.0\n"), "aws-sdk data 2024-03, SDK 1.18.0");
+    }
+
+    #[test]
+    fn test_service_data_version_only_for_listed_sdks() {
+        let versions = "s3tables,2026-10,1.71.0\ns3vectors,2026-10,1.42.0\n";
+
+        assert_eq!(service_data_version(versions, "s3tables"), Some("2026-10,1.71.0"));
+        assert_eq!(service_data_version(versions, "s3"), None);
+        assert_eq!(describe_data_version(service_data_version(versions, "s3vectors").unwrap()), "aws-sdk data 2026-10, SDK 1.42.0");
+    }
+
+    #[test]
+    fn test_month_of_data_version_with_and_without_sdk_version() {
+        assert_eq!(month_of_data_version("2024-03,1.18.0\n"), "2024-03");
+        assert_eq!(month_of_data_version("2024-03\n"), "2024-03");
+    }
+}
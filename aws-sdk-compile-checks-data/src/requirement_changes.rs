@@ -0,0 +1,70 @@
+use std::sync::OnceLock;
+
+use crate::rows::records;
+
+/// Properties that became required (or stopped being required) in a given SDK version, as `service,method,property,required|optional,version`
+const REQUIREMENT_CHANGES: &str = include_str!("../required_properties_info/requirement_changes.csv");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Required,
+    Optional,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequirementChange {
+    pub service: &'static str,
+    pub method: &'static str,
+    pub property: &'static str,
+    pub change: Change,
+    pub version: &'static str,
+}
+
+/// Every change in our data, in the order it was recorded
+pub fn requirement_changes() -> &'static [RequirementChange] {
+    // parsed once, and only when a missing property needs notes
+    static CHANGES: OnceLock<Vec<RequirementChange>> = OnceLock::new();
+    CHANGES.get_or_init(|| parse_changes(REQUIREMENT_CHANGES))
+}
+
+fn parse_changes(changes: &'static str) -> Vec<RequirementChange> {
+    records(changes)
+        .filter_map(Result::ok)
+        .filter(|record| record.fields.len() == 5)
+        .filter_map(|record| {
+            let change = match record.field(3) {
+                "required" => Change::Required,
+                "optional" => Change::Optional,
+                _ => return None,
+            };
+            Some(RequirementChange {
+                service: record.static_field(0),
+                method: record.static_field(1),
+                property: record.static_field(2),
+                change,
+                version: record.static_field(4),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_changes_skips_invalid_lines() {
+        let actual = parse_changes("s3,put_object,bucket,required\ns3,put_object,bucket,maybe,1.0.0\nsqs,send_message,queue_url,required,1.3.0\n");
+
+        assert_eq!(
+            actual,
+            vec![RequirementChange {
+                service: "sqs",
+                method: "send_message",
+                property: "queue_url",
+                change: Change::Required,
+                version: "1.3.0",
+            }]
+        );
+    }
+}
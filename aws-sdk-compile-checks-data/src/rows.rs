@@ -0,0 +1,299 @@
+//! Reading of our CSV files, shared by `build.rs` (for our own data), the hand-maintained data and the plugin datasets
+
+use std::borrow::Cow;
+
+pub(crate) const FLUENT_BUILDER_SUFFIX: &str = "FluentBuilder";
+const QUOTE: char = '"';
+
+/// A record of a CSV file, with the line it starts on, for error messages
+#[derive(Debug, PartialEq)]
+pub struct Record<'a> {
+    pub line: usize,
+    pub fields: Vec<Cow<'a, str>>, // only owned when the field contains an escaped quote
+}
+
+impl Record<'_> {
+    /// The trimmed field, or an empty string when the record does not have that many fields
+    pub fn field(&self, index: usize) -> &str {
+        self.fields.get(index).map(|f| f.trim()).unwrap_or_default()
+    }
+}
+
+impl Record<'static> {
+    /// Like [`Record::field`], for data that lives as long as the macro. Only fields with an escaped quote have to be leaked
+    #[allow(dead_code)] // not needed by build.rs
+    pub fn static_field(&self, index: usize) -> &'static str {
+        match self.fields.get(index) {
+            Some(Cow::Borrowed(field)) => field.trim(),
+            Some(Cow::Owned(field)) => field.clone().leak().trim(),
+            None => "",
+        }
+    }
+}
+
+/// A CSV file that could not be read, at the given line
+#[derive(Debug, PartialEq)]
+pub struct CsvError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// The records of a CSV file as described in RFC 4180: fields with commas, quotes or line breaks are quoted, and quotes in them are doubled.
+/// Records can have a different number of fields, and empty lines are skipped.
+/// After an error, like a quoted field that is never closed, there are no more records, because we would not know where the next one starts
+pub fn records(csv: &str) -> impl Iterator<Item = Result<Record<'_>, CsvError>> {
+    let mut reader = Reader {
+        csv,
+        position: 0,
+        line: 1,
+        failed: false,
+    };
+    std::iter::from_fn(move || reader.next_record())
+}
+
+struct Reader<'a> {
+    csv: &'a str,
+    position: usize,
+    line: usize,
+    failed: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn next_record(&mut self) -> Option<Result<Record<'a>, CsvError>> {
+        if self.failed {
+            return None;
+        }
+        self.skip_empty_lines();
+        if self.position >= self.csv.len() {
+            return None;
+        }
+        let line = self.line;
+        let mut fields = vec![];
+        loop {
+            match self.next_field() {
+                Ok((field, last)) => {
+                    fields.push(field);
+                    if last {
+                        return Some(Ok(Record { line, fields }));
+                    }
+                }
+                Err(message) => {
+                    self.failed = true;
+                    return Some(Err(CsvError { line: self.line, message }));
+                }
+            }
+        }
+    }
+
+    fn skip_empty_lines(&mut self) {
+        while self.position < self.csv.len() {
+            let rest = &self.csv[self.position..];
+            let Some(end) = rest.find('\n') else {
+                if rest.trim().is_empty() {
+                    self.position = self.csv.len();
+                }
+                return;
+            };
+            if !rest[..end].trim().is_empty() {
+                return;
+            }
+            self.position += end + 1;
+            self.line += 1;
+        }
+    }
+
+    /// The field, and whether it is the last one of its record
+    fn next_field(&mut self) -> Result<(Cow<'a, str>, bool), String> {
+        let rest = &self.csv[self.position..];
+        let Some(quoted) = rest.strip_prefix(QUOTE) else {
+            let end = rest.find([',', '\n']).unwrap_or(rest.len());
+            let field = rest[..end].trim_end_matches('\r');
+            if field.contains(QUOTE) {
+                return Err(format!("`{}` contains a quote, so it should be quoted as a whole, with the quote doubled", field));
+            }
+            self.position += end;
+            return self.end_of_field(Cow::Borrowed(field));
+        };
+
+        let mut unescaped: Option<String> = None; // only allocated when we encounter an escaped quote
+        let mut start = 0;
+        loop {
+            let Some(quote) = quoted[start..].find(QUOTE).map(|offset| start + offset) else {
+                return Err("a quoted field is never closed".to_string());
+            };
+            self.line += quoted[start..quote].matches('\n').count();
+            if quoted[quote + 1..].starts_with(QUOTE) {
+                unescaped.get_or_insert_with(String::new).push_str(&quoted[start..=quote]);
+                start = quote + 2;
+                continue;
+            }
+            let field = match unescaped {
+                Some(mut value) => {
+                    value.push_str(&quoted[start..quote]);
+                    Cow::Owned(value)
+                }
+                None => Cow::Borrowed(&quoted[..quote]),
+            };
+            self.position += quote + 2; // the opening and closing quotes
+            return self.end_of_field(field);
+        }
+    }
+
+    fn end_of_field(&mut self, field: Cow<'a, str>) -> Result<(Cow<'a, str>, bool), String> {
+        let rest = &self.csv[self.position..];
+        let (length, last) = if rest.is_empty() {
+            (0, true)
+        } else if rest.starts_with(',') {
+            (1, false)
+        } else if rest.starts_with("\r\n") {
+            (2, true)
+        } else if rest.starts_with('\n') {
+            (1, true)
+        } else {
+            return Err("expected a `,` or the end of the line after a quoted field".to_string());
+        };
+        self.position += length;
+        if last && length > 0 {
+            self.line += 1;
+        }
+        Ok((field, last))
+    }
+}
+
+/// A row of `service,method,prop1 prop2,docs url,fluent builder`, the url and builder being optional
+pub struct Row {
+    pub service: String,
+    pub name: String, // method, or type for the builders in the `types` modules
+    pub required_props: Vec<String>,
+    pub docs_url: String, // empty when we do not know it
+    pub builder: String,  // name of the fluent builder of a method, e.g. `SendMessageFluentBuilder`, empty when we do not know it
+}
+
+pub fn parse_row(record: &Record) -> Result<Row, String> {
+    if !(3..=5).contains(&record.fields.len()) {
+        return Err(format!("expected 3 to 5 columns, found {}", record.fields.len()));
+    }
+    let (service, name, required_props, docs_url, builder) = (record.field(0), record.field(1), record.field(2), record.field(3), record.field(4));
+    if !docs_url.is_empty() && !docs_url.starts_with("https://") {
+        return Err(format!("expected the docs url to start with `https://`, found `{}`", docs_url));
+    }
+    if !builder.is_empty() && !is_fluent_builder(builder) {
+        return Err(format!("expected the name of a fluent builder, e.g. `SendMessageFluentBuilder`, found `{}`", builder));
+    }
+    if !is_snake_case(service) {
+        return Err(format!("expected a lowercase service name, found `{}`", service));
+    }
+    if name.is_empty() {
+        return Err("expected a method or type name, found an empty column".to_string());
+    }
+    let required_props: Vec<String> = required_props.split_whitespace().map(|p| p.to_string()).collect();
+    if required_props.is_empty() {
+        return Err("expected one or more required properties".to_string());
+    }
+    // a `|` separates the options of an 'at least one of' group, and properties can be raw identifiers, e.g. `r#type`
+    if let Some(prop) = required_props
+        .iter()
+        .flat_map(|p| p.split('|'))
+        .find(|p| !is_snake_case(p.strip_prefix("r#").unwrap_or(p)))
+    {
+        return Err(format!("expected snake_case property names, found `{}`", prop));
+    }
+    Ok(Row {
+        service: service.to_string(),
+        name: name.to_string(),
+        required_props,
+        docs_url: docs_url.to_string(),
+        builder: builder.to_string(),
+    })
+}
+
+fn is_fluent_builder(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_uppercase()) && value.ends_with(FLUENT_BUILDER_SUFFIX) && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// The name smithy-rs gives the fluent builder of a method, e.g. `SendMessageFluentBuilder` for `send_message`
+pub fn fluent_builder_of(method: &str) -> String {
+    let pascal_case: String = method
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_ascii_uppercase()).into_iter().chain(chars)
+        })
+        .collect();
+    format!("{}{}", pascal_case, FLUENT_BUILDER_SUFFIX)
+}
+
+pub(crate) fn is_snake_case(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_lowercase()) && value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(csv: &str) -> Vec<Vec<String>> {
+        records(csv).map(|r| r.unwrap().fields.into_iter().map(|f| f.into_owned()).collect()).collect()
+    }
+
+    #[test]
+    fn records_with_a_different_number_of_fields_and_empty_lines() {
+        let csv = "sqs,receive_message,queue_url\n\n  \ns3,get_object,bucket key,https://docs.rs,GetObjectFluentBuilder\r\nsns,publish,";
+
+        assert_eq!(
+            fields(csv),
+            vec![
+                vec!["sqs", "receive_message", "queue_url"],
+                vec!["s3", "get_object", "bucket key", "https://docs.rs", "GetObjectFluentBuilder"],
+                vec!["sns", "publish", ""],
+            ]
+        );
+        let lines: Vec<usize> = records(csv).map(|r| r.unwrap().line).collect();
+        assert_eq!(lines, vec![1, 4, 5]);
+    }
+
+    #[test]
+    fn records_with_quoted_fields() {
+        let csv = "s3,list_objects,,\"use `list_objects_v2`, it is \"\"better\"\"\"\nsqs,\"multiple\nlines\",x\nsns,publish,y";
+
+        let records: Vec<Record> = records(csv).map(Result::unwrap).collect();
+
+        assert_eq!(records[0].field(3), "use `list_objects_v2`, it is \"better\"");
+        assert_eq!(records[1].field(1), "multiple\nlines");
+        assert!(matches!(records[1].fields[1], Cow::Borrowed(_)));
+        assert_eq!(records[2].line, 4);
+    }
+
+    #[test]
+    fn records_stop_at_a_quoted_field_that_is_never_closed() {
+        let actual: Vec<_> = records("sqs,send_message,queue_url\nsqs,\"send_message,queue_url\nsns,publish,message").collect();
+
+        assert_eq!(actual.len(), 2);
+        assert_eq!(
+            actual[1],
+            Err(CsvError {
+                line: 2,
+                message: "a quoted field is never closed".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn records_reject_stray_quotes() {
+        let unquoted: Vec<_> = records("sqs,send\"message,queue_url").collect();
+        let after_closing_quote: Vec<_> = records("sqs,\"send_message\"s,queue_url").collect();
+
+        assert_eq!(unquoted[0].as_ref().unwrap_err().message, "`send\"message` contains a quote, so it should be quoted as a whole, with the quote doubled");
+        assert_eq!(after_closing_quote[0].as_ref().unwrap_err().message, "expected a `,` or the end of the line after a quoted field");
+    }
+
+    #[test]
+    fn parse_row_with_optional_columns() {
+        let record = records("s3,get_object, bucket key ,,GetObjectFluentBuilder").next().unwrap().unwrap();
+
+        let row = parse_row(&record).unwrap();
+
+        assert_eq!(row.required_props, vec!["bucket", "key"]);
+        assert_eq!(row.docs_url, "");
+        assert_eq!(row.builder, "GetObjectFluentBuilder");
+    }
+}
@@ -0,0 +1,101 @@
+//! The modules of the macro, compiled as a regular library for the fuzz targets, because a proc-macro crate can only export its macros.
+//! Only the parts of `required_props` that do not read files run here, so the fuzzer does not depend on the environment
+#![allow(dead_code)]
+
+use syn::{Item, ItemFn};
+
+use crate::attributes::Attributes;
+use crate::findings::UsageFinds;
+use crate::pipeline::Sources;
+use crate::required_properties::create_required_props_map;
+
+#[path = "../../src/assertions.rs"]
+mod assertions;
+#[path = "../../src/attributes.rs"]
+mod attributes;
+#[path = "../../src/availability.rs"]
+mod availability;
+#[path = "../../src/baseline.rs"]
+mod baseline;
+#[path = "../../src/builders.rs"]
+mod builders;
+#[path = "../../src/datasets.rs"]
+mod datasets;
+#[path = "../../src/denied.rs"]
+mod denied;
+#[path = "../../src/diagnostics.rs"]
+mod diagnostics;
+#[path = "../../src/export.rs"]
+mod export;
+#[path = "../../src/findings.rs"]
+mod findings;
+#[path = "../../src/iam.rs"]
+mod iam;
+#[path = "../../src/instrument.rs"]
+mod instrument;
+#[path = "../../src/literals.rs"]
+mod literals;
+#[path = "../../src/manifests.rs"]
+mod manifests;
+#[path = "../../src/pipeline.rs"]
+mod pipeline;
+#[path = "../../src/profiles.rs"]
+mod profiles;
+#[path = "../../src/registered.rs"]
+mod registered;
+#[path = "../../src/renames.rs"]
+mod renames;
+#[path = "../../src/required_properties.rs"]
+mod required_properties;
+#[path = "../../src/resources.rs"]
+mod resources;
+#[path = "../../src/runtime_checks.rs"]
+mod runtime_checks;
+#[path = "../../src/versions.rs"]
+mod versions;
+#[path = "../../src/visitor.rs"]
+mod visitor;
+
+/// Checks every function in the source, with the arguments of its `required_props` attribute when it has one that parses
+pub fn check_source(source: &str) {
+    let Ok(file) = syn::parse_str::<syn::File>(source) else {
+        return;
+    };
+    for item in file.items {
+        if let Item::Fn(item) = item {
+            let attributes = item
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("required_props"))
+                .map(|attr| attr.parse_args::<Attributes>())
+                .unwrap_or_else(|| syn::parse_str::<Attributes>(""));
+            if let Ok(attributes) = attributes {
+                check_function(&attributes, &item);
+            }
+        }
+    }
+}
+
+/// Parses the arguments of a `required_props` attribute, e.g. `sdk = sqs, warn(timeout)`
+pub fn parse_attributes(source: &str) {
+    if let Ok(attributes) = syn::parse_str::<Attributes>(source) {
+        let _ = attributes.all_sdks();
+    }
+}
+
+/// What `required_props` does with the function, up to the emitted diagnostics
+fn check_function(attributes: &Attributes, item: &ItemFn) {
+    let sources = Sources {
+        required_props: create_required_props_map(),
+        builder_rules: &[],
+        denied_sdks: &[],
+        known_resources: None,
+        iam_policies: None,
+    };
+    let Ok(checked) = pipeline::check_function(&mut item.clone(), attributes, sources) else {
+        return;
+    };
+    for finding in checked.findings {
+        let _ = UsageFinds::into_diagnostic(finding).emit();
+    }
+}
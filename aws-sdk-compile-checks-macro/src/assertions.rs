@@ -0,0 +1,85 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote_spanned;
+
+use crate::visitor::CheckedChain;
+
+const RAW_PREFIX: &str = "r#";
+
+/// For every checked chain, a function that is never called, but only compiles when the fluent builder has a setter for every
+/// property our data requires. When the data has drifted from the SDK version you use, the missing setter is an error at the call.
+/// We use the `set_` variants, because they take an `Option` instead of a generic argument, so `None` is always enough
+pub(crate) fn setter_assertions(chains: &[CheckedChain]) -> TokenStream {
+    let mut asserted: Vec<&CheckedChain> = vec![];
+    for chain in chains {
+        if !asserted.iter().any(|a| a.client_crate == chain.client_crate && a.method == chain.method && a.required == chain.required) {
+            asserted.push(chain);
+        }
+    }
+    asserted.into_iter().map(setter_assertion).collect()
+}
+
+fn setter_assertion(chain: &CheckedChain) -> TokenStream {
+    let span = chain.span;
+    let client_crate = Ident::new(&chain.client_crate, span);
+    let method = ident(&chain.method, span);
+    let setters = chain
+        .required
+        .iter()
+        .map(|required| Ident::new(&format!("set_{}", required.strip_prefix(RAW_PREFIX).unwrap_or(required)), span));
+
+    quote_spanned!(span=>
+        const _: () = {
+            #[allow(dead_code)]
+            fn required_props_data_names_existing_setters(client: &#client_crate::Client) {
+                let _ = client.#method()#(.#setters(::core::option::Option::None))*;
+            }
+        };
+    )
+}
+
+fn ident(name: &str, span: Span) -> Ident {
+    match name.strip_prefix(RAW_PREFIX) {
+        Some(raw) => Ident::new_raw(raw, span),
+        None => Ident::new(name, span),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(method: &str, required: Vec<&'static str>) -> CheckedChain {
+        CheckedChain {
+            span: Span::call_site(),
+            client_crate: "aws_sdk_sqs".to_string(),
+            method: method.to_string(),
+            required,
+        }
+    }
+
+    #[test]
+    fn setter_assertions_calls_the_set_variant_of_every_required_property() {
+        let actual = setter_assertions(&[chain("send_message", vec!["queue_url", "message_body"])]).to_string();
+
+        assert!(actual.contains("client : & aws_sdk_sqs :: Client"));
+        assert!(actual.contains(
+            "client . send_message () . set_queue_url (:: core :: option :: Option :: None) . set_message_body (:: core :: option :: Option :: None)"
+        ));
+    }
+
+    #[test]
+    fn setter_assertions_strips_the_raw_prefix_of_properties() {
+        let actual = setter_assertions(&[chain("create_analyzer", vec!["analyzer_name", "r#type"])]).to_string();
+
+        assert!(actual.contains(". set_type (:: core :: option :: Option :: None)"));
+    }
+
+    #[test]
+    fn setter_assertions_skips_identical_chains() {
+        let chains = [chain("receive_message", vec!["queue_url"]), chain("receive_message", vec!["queue_url"])];
+
+        let actual = setter_assertions(&chains).to_string();
+
+        assert_eq!(actual.matches("const _").count(), 1);
+    }
+}
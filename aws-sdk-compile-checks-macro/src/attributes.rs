@@ -1,56 +1,768 @@
-use proc_macro2::Ident;
+use std::collections::HashMap;
+
 use proc_macro2::Span;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::token::Comma;
-use syn::Token;
+use syn::token::{Comma, Paren};
+use syn::{Ident, LitBool, LitInt, LitStr, Token};
+
+use crate::literals::edit_distance;
 
 pub(crate) mod kw {
     syn::custom_keyword!(sdk);
+    syn::custom_keyword!(assume_sdk);
+    syn::custom_keyword!(clients);
+    syn::custom_keyword!(only_clients);
+    syn::custom_keyword!(ignore_clients);
+    syn::custom_keyword!(warn);
+    syn::custom_keyword!(assert_setters);
+    syn::custom_keyword!(policy);
+    syn::custom_keyword!(max);
+    syn::custom_keyword!(recommend);
+    syn::custom_keyword!(runtime_checks);
+    syn::custom_keyword!(profile);
+    syn::custom_keyword!(max_errors);
+    syn::custom_keyword!(min_data);
+    syn::custom_keyword!(instrument);
+}
+
+const ATTRIBUTES: [&str; 15] = [
+    "sdk", "assume_sdk", "clients", "only_clients", "ignore_clients", "warn", "policy", "max", "max_errors", "recommend", "instrument", "profile", "min_data", "assert_setters", "runtime_checks",
+];
+const ALLOWED_ATTRIBUTES: &str = "the allowed attributes are `sdk`, `assume_sdk`, `clients`, `only_clients`, `ignore_clients`, `warn`, `policy`, `max`, `max_errors`, `recommend`, `instrument`, `profile`, `min_data`, `assert_setters` and `runtime_checks`";
+const ATTRIBUTE_GRAMMAR: &str = "attributes are separated by `,` and written as `name = value` (like `sdk = sqs, s3`), `name(...)` (like `warn(timeout)`) or just `assert_setters`";
+const MAX_ATTRIBUTE_DISTANCE: usize = 2; // to suggest an attribute for a typo, e.g. `sdk` for `skd`
+const EXPECTED_SDKS: &str = "expected one or more SDKs, separated by `,` after keyword `sdk`, e.g. `sdk = sqs,s3`";
+const EXPECTED_ASSUME_SDK: &str = "expected a single SDK after `assume_sdk =`, e.g. `assume_sdk = sqs`";
+const EXPECTED_CLIENTS: &str = "expected one or more receivers mapped to their SDK, e.g. `clients(client = sqs)`";
+const EXPECTED_ONLY_CLIENTS: &str = "expected one or more receivers, e.g. `only_clients(sqs_client)`";
+const EXPECTED_IGNORE_CLIENTS: &str = "expected one or more receivers, e.g. `ignore_clients(metrics_client)`";
+const EXPECTED_WARNINGS: &str = "expected one or more kinds of warnings, e.g. `warn(conditional)`";
+const EXPECTED_MAX_VALUES: &str = "expected one or more setters with their maximum value, e.g. `max(read_capacity_units = 100)`";
+const EXPECTED_RECOMMEND: &str = "expected `true` or `false` after `recommend =`, e.g. `recommend = true`";
+const EXPECTED_INSTRUMENT: &str = "expected `true` or `false` after `instrument =`, e.g. `instrument = true`";
+const EXPECTED_RUNTIME_CHECKS: &str = "expected `true` or `false` after `runtime_checks =`, e.g. `runtime_checks = true`";
+const EXPECTED_ASSERT_SETTERS: &str = "expected `true` or `false` after `assert_setters =`, e.g. `assert_setters = false`, or just `assert_setters`";
+const EXPECTED_PROFILE: &str = "expected the name of a profile after `profile =`, e.g. `profile = \"strict-prod\"`";
+const EXPECTED_MIN_DATA: &str = "expected a month after `min_data =`, e.g. `min_data = \"2024-06\"`";
+const EXPECTED_MAX_ERRORS: &str = "expected a number of at least 1 after `max_errors =`, e.g. `max_errors = 5`";
+const ALLOWED_POLICIES: &str = "expected a policy after `policy =`, the allowed policies are `encryption`";
+const ALLOWED_WARNINGS: &str = "the allowed warnings are `conditional`, `deprecated`, `unawaited`, `empty`, `unwrap`, `pagination`, `queue_url`, `s3_key`, `duplicate`, `batch`, `concurrent`, `unused_sdk`, `hardcoded_resource`, `timeout`, `idempotency` and `sqs_delete`";
+
+/// Warnings that are only emitted when asked for with `warn(...)`, because they are often not applicable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptInWarning {
+    Conditional,       // properties that are only required in some situations, e.g. for FIFO queues
+    Deprecated,        // operations and properties that are deprecated
+    Unawaited,         // `send()` calls whose future is dropped without being awaited
+    Empty,             // required properties that are set to an empty string literal, e.g. `queue_url("")`
+    Unwrap,            // `unwrap()` or `expect(...)` on the result of `send().await`, which panics on errors of AWS
+    Pagination,        // paginated operations called with `send()`, without looking at the token for the next page
+    QueueUrl,          // string literals passed to `queue_url` that do not look like the URL of a queue, e.g. its name
+    S3Key,             // S3 object keys that start with a `/`, which S3 keeps as part of the key
+    Duplicate,         // setters that are called more than once in a chain, of which only the last value is kept
+    Batch,             // calls inside a loop of operations that have a batch variant, e.g. `send_message` instead of `send_message_batch`
+    Concurrent,        // calls that are awaited one after the other, although the second does not use the result of the first
+    UnusedSdk,         // SDKs in the `sdk` attribute that no call or client of the function matches
+    HardcodedResource, // string literals passed to setters of resources, e.g. `table_name("orders")`, instead of a value from the config
+    Timeout,           // `send().await` outside of a `timeout(...)`, when no config of the function sets a timeout
+    Idempotency,       // operations that accept an idempotency token, e.g. `client_token`, called without one
+    SqsDelete,         // SQS `receive_message` in a function that never deletes the messages, nor passes on their receipt handles
+}
+
+/// Maintained sets of properties that are required on top of those the SDK requires, enabled with `policy = ...`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    Encryption, // encryption at rest, e.g. `server_side_encryption` on S3 `put_object`
+}
+
+impl Policy {
+    /// The name in the attribute, which is also the name of its rules in the data
+    pub fn name(&self) -> &'static str {
+        match self {
+            Policy::Encryption => "encryption",
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct Attributes {
     pub span: Span,
     pub sdks: Vec<String>,
+    pub assume_sdk: Option<String>, // used for calls whose SDK we cannot tell otherwise, instead of reporting them as unknown
+    pub clients: HashMap<String, String>, // name of the receiver to its SDK
+    pub only_clients: Vec<String>, // when not empty, only calls on these receivers are checked
+    pub ignore_clients: Vec<String>, // calls on these receivers are never checked, e.g. a wrapper with methods named like SDK operations
+    pub warnings: Vec<OptInWarning>,
+    pub policies: Vec<Policy>,
+    pub max_values: Vec<(String, i64)>, // setter to the highest integer literal we accept for it, e.g. `read_capacity_units` to 100
+    // the flags are `None` when the attribute does not set them, so they can come from a profile
+    pub recommend: Option<bool>, // also warn about properties that are not required, but recommended
+    pub instrument: Option<bool>, // wrap the `send().await` of every call we know the SDK of in a `tracing` span
+    pub assert_setters: Option<bool>, // generate code that fails to compile when our data names setters that your SDK version does not have
+    pub runtime_checks: Option<bool>, // in debug builds, assert that builders passed into the function have their required properties when they are sent
+    pub max_errors: Option<usize>, // show only this many errors, with a note about the others
+    pub profile: Option<LitStr>, // a named set of arguments in the `Cargo.toml` of the crate or its workspace, see `profiles`
+    pub min_data: Option<LitStr>, // the oldest month of data we may check against, e.g. `2024-06`
+}
+
+impl Attributes {
+    /// Adds the arguments of the profile to those of the attribute. Receivers the attribute maps itself keep their SDK,
+    /// and flags the attribute sets itself, like `recommend = false`, win over those of the profile
+    pub fn with_profile(mut self, profile: Attributes) -> Self {
+        self.sdks.extend(profile.sdks);
+        self.assume_sdk = self.assume_sdk.or(profile.assume_sdk);
+        for (receiver, sdk) in profile.clients {
+            self.clients.entry(receiver).or_insert(sdk);
+        }
+        self.only_clients.extend(profile.only_clients);
+        self.ignore_clients.extend(profile.ignore_clients);
+        self.warnings.extend(profile.warnings);
+        self.policies.extend(profile.policies);
+        self.max_values.extend(profile.max_values);
+        self.recommend = self.recommend.or(profile.recommend);
+        self.instrument = self.instrument.or(profile.instrument);
+        self.assert_setters = self.assert_setters.or(profile.assert_setters);
+        self.runtime_checks = self.runtime_checks.or(profile.runtime_checks);
+        self.max_errors = self.max_errors.or(profile.max_errors);
+        self.min_data = self.min_data.or(profile.min_data);
+        self
+    }
+
+    /// All SDKs mentioned in the attributes
+    pub fn all_sdks(&self) -> Vec<String> {
+        self.sdks.iter().chain(&self.assume_sdk).chain(self.clients.values()).cloned().collect()
+    }
 }
 
 impl Parse for Attributes {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if input.is_empty() {
-            return Ok(Attributes {
-                span: input.span(),
-                sdks: vec![],
-            });
-        }
+        let mut sdks = vec![];
+        let mut assume_sdk = None;
+        let mut clients = HashMap::new();
+        let mut only_clients = vec![];
+        let mut ignore_clients = vec![];
+        let mut warnings = vec![];
+        let mut policies = vec![];
+        let mut max_values = vec![];
+        let mut recommend = None;
+        let mut instrument = None;
+        let mut assert_setters = None;
+        let mut runtime_checks = None;
+        let mut profile = None;
+        let mut max_errors = None;
+        let mut min_data = None;
 
-        let sdk_keyword: kw::sdk = input
-            .parse()
-            .map_err(|_| syn::Error::new(input.span(), "the only allowed attribute is `sdk`"))?;
-        let _equals_token: Token![=] = input.parse().map_err(|_| {
-            syn::Error::new(
-                sdk_keyword.span(),
-                "expected `sdk` to be followed by a `=` and one or more SDKs, e.g. `sdk = sqs`",
-            )
-        })?;
-        let sdks: Punctuated<Ident, Comma> = Punctuated::parse_terminated(input).map_err(|_| {
-            syn::Error::new(
-                input.span(),
-                "expected one or more SDKs, separated by `,` after keyword `sdk`, e.g. `sdk = sqs,s3`",
-            )
-        })?;
-
-        if sdks.is_empty() {
-            return Err(syn::Error::new(
-                sdk_keyword.span(),
-                "expected one or more SDKs, separated by `,` after keyword `sdk`, e.g. `sdk = sqs,s3`",
-            ));
+        while !input.is_empty() {
+            if input.peek2(Token![:]) {
+                // e.g. `sdk: sqs`, written like a field instead of with `=`
+                return Err(misspelled_attribute(input));
+            } else if input.peek(kw::sdk) {
+                sdks.extend(parse_sdks(input)?);
+            } else if input.peek(kw::assume_sdk) {
+                assume_sdk = Some(parse_assume_sdk(input)?);
+            } else if input.peek(kw::clients) {
+                clients.extend(parse_clients(input)?);
+            } else if input.peek(kw::only_clients) {
+                let keyword: kw::only_clients = input.parse()?;
+                only_clients.extend(parse_receivers(input, keyword.span(), EXPECTED_ONLY_CLIENTS)?);
+            } else if input.peek(kw::ignore_clients) {
+                let keyword: kw::ignore_clients = input.parse()?;
+                ignore_clients.extend(parse_receivers(input, keyword.span(), EXPECTED_IGNORE_CLIENTS)?);
+            } else if input.peek(kw::warn) {
+                warnings.extend(parse_warnings(input)?);
+            } else if input.peek(kw::policy) {
+                policies.push(parse_policy(input)?);
+            } else if input.peek(kw::max) {
+                max_values.extend(parse_max_values(input)?);
+            } else if input.peek(kw::recommend) {
+                recommend = Some(parse_recommend(input)?);
+            } else if input.peek(kw::instrument) {
+                instrument = Some(parse_instrument(input)?);
+            } else if input.peek(kw::runtime_checks) {
+                runtime_checks = Some(parse_runtime_checks(input)?);
+            } else if input.peek(kw::max_errors) {
+                max_errors = Some(parse_max_errors(input)?);
+            } else if input.peek(kw::profile) {
+                profile = Some(parse_profile(input)?);
+            } else if input.peek(kw::min_data) {
+                min_data = Some(parse_min_data(input)?);
+            } else if input.peek(kw::assert_setters) {
+                assert_setters = Some(parse_assert_setters(input)?);
+            } else {
+                return Err(misspelled_attribute(input));
+            }
+
+            if !input.is_empty() {
+                let _comma: Comma = input.parse()?;
+            }
         }
 
         Ok(Attributes {
             span: input.span(),
-            sdks: sdks.iter().map(|ident| ident.to_string()).collect(),
+            sdks,
+            assume_sdk,
+            clients,
+            only_clients,
+            ignore_clients,
+            warnings,
+            policies,
+            max_values,
+            recommend,
+            instrument,
+            assert_setters,
+            runtime_checks,
+            max_errors,
+            profile,
+            min_data,
+        })
+    }
+}
+
+fn parse_sdks(input: ParseStream) -> syn::Result<Vec<String>> {
+    let sdk_keyword: kw::sdk = input.parse()?;
+    let _equals_token: Token![=] = input.parse().map_err(|_| {
+        syn::Error::new(
+            sdk_keyword.span(),
+            "expected `sdk` to be followed by a `=` and one or more SDKs, e.g. `sdk = sqs`",
+        )
+    })?;
+
+    let mut sdks = vec![];
+    loop {
+        if input.is_empty() || (input.peek(Comma) && is_start_of_next_attribute(input)) {
+            break;
+        }
+        if !sdks.is_empty() {
+            let _comma: Comma = input.parse().map_err(|_| syn::Error::new(input.span(), EXPECTED_SDKS))?;
+            if input.is_empty() {
+                break; // trailing comma
+            }
+        }
+        let sdk: Ident = input.parse().map_err(|_| syn::Error::new(input.span(), EXPECTED_SDKS))?;
+        sdks.push(sdk.to_string());
+    }
+
+    if sdks.is_empty() {
+        return Err(syn::Error::new(sdk_keyword.span(), EXPECTED_SDKS));
+    }
+    Ok(sdks)
+}
+
+fn parse_assume_sdk(input: ParseStream) -> syn::Result<String> {
+    let keyword: kw::assume_sdk = input.parse()?;
+    let _equals_token: Token![=] = input.parse().map_err(|_| syn::Error::new(keyword.span(), EXPECTED_ASSUME_SDK))?;
+    let sdk: Ident = input.parse().map_err(|_| syn::Error::new(keyword.span(), EXPECTED_ASSUME_SDK))?;
+    Ok(sdk.to_string())
+}
+
+/// The source of the attribute with `sdk = <sdk>` added, e.g. `#[required_props(sdk = amplify)]` for `#[required_props]`,
+/// or `#[required_props(warn(timeout), sdk = amplify)]` for `#[required_props(warn(timeout))]`
+pub(crate) fn with_sdk(attribute: &str, sdk: &str) -> Option<String> {
+    let without_bracket = attribute.trim_end().strip_suffix(']')?.trim_end();
+    let Some(arguments) = without_bracket.strip_suffix(')').map(str::trim_end) else {
+        return Some(format!("{}(sdk = {})]", without_bracket, sdk));
+    };
+    let separator = match arguments.chars().last() {
+        Some('(') => "",
+        Some(',') => " ",
+        _ => ", ",
+    };
+    Some(format!("{}{}sdk = {})]", arguments, separator, sdk))
+}
+
+/// After a comma, another attribute starts with a keyword followed by `=` or parentheses (or `:` by mistake), or is the `assert_setters` flag, while an SDK is just a name
+fn is_start_of_next_attribute(input: ParseStream) -> bool {
+    input.peek2(kw::assert_setters) || (input.peek2(Ident) && (input.peek3(Token![=]) || input.peek3(Paren) || input.peek3(Token![:])))
+}
+
+/// The error for an attribute we do not know, or one that is followed by `:` instead of `=`,
+/// with the closest attribute and the value that was given as a suggestion, e.g. `did you mean `sdk = sqs`?` for `skd = sqs` or `sdk: sqs`
+fn misspelled_attribute(input: ParseStream) -> syn::Error {
+    let fork = input.fork();
+    let Ok(name) = fork.parse::<Ident>() else {
+        return syn::Error::new(input.span(), format!("{}\n{}", ALLOWED_ATTRIBUTES, ATTRIBUTE_GRAMMAR));
+    };
+    let name_as_string = name.to_string();
+    let known = ATTRIBUTES.contains(&name_as_string.as_str());
+    let suggested = match known {
+        true => Some(name_as_string.as_str()),
+        false => ATTRIBUTES
+            .iter()
+            .map(|attribute| (*attribute, edit_distance(&name_as_string, attribute)))
+            .filter(|(_, distance)| *distance <= MAX_ATTRIBUTE_DISTANCE)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(attribute, _)| attribute),
+    };
+
+    let colon_span = fork.peek(Token![:]).then(|| fork.span());
+    let value = if fork.parse::<Token![=]>().is_ok() || fork.parse::<Token![:]>().is_ok() {
+        fork.parse::<proc_macro2::TokenTree>().ok().map(|value| format!(" = {}", value))
+    } else if fork.peek(Paren) {
+        fork.parse::<proc_macro2::TokenTree>().ok().map(|group| group.to_string())
+    } else {
+        None
+    };
+    let suggestion = suggested.map(|attribute| format!("{}{}", attribute, value.unwrap_or_default()));
+
+    let (span, problem) = match (known, colon_span) {
+        (true, Some(colon_span)) => (colon_span, format!("expected `=` after `{}`, not `:`", name)),
+        _ => (name.span(), format!("unknown attribute `{}`", name)),
+    };
+    let message = match suggestion {
+        Some(suggestion) => format!("{}, did you mean `{}`?\n{}", problem, suggestion, ATTRIBUTE_GRAMMAR),
+        None => format!("{}, {}\n{}", problem, ALLOWED_ATTRIBUTES, ATTRIBUTE_GRAMMAR),
+    };
+    syn::Error::new(span, message)
+}
+
+fn parse_clients(input: ParseStream) -> syn::Result<Vec<(String, String)>> {
+    let clients_keyword: kw::clients = input.parse()?;
+    let content;
+    syn::parenthesized!(content in input);
+    let mappings: Punctuated<ClientMapping, Comma> =
+        Punctuated::parse_terminated(&content).map_err(|_| syn::Error::new(clients_keyword.span(), EXPECTED_CLIENTS))?;
+
+    if mappings.is_empty() {
+        return Err(syn::Error::new(clients_keyword.span(), EXPECTED_CLIENTS));
+    }
+    Ok(mappings.into_iter().map(|m| (m.receiver.to_string(), m.sdk.to_string())).collect())
+}
+
+fn parse_receivers(input: ParseStream, keyword: Span, expected: &str) -> syn::Result<Vec<String>> {
+    let content;
+    syn::parenthesized!(content in input);
+    let receivers: Punctuated<Ident, Comma> = Punctuated::parse_terminated(&content).map_err(|_| syn::Error::new(keyword, expected))?;
+
+    if receivers.is_empty() {
+        return Err(syn::Error::new(keyword, expected));
+    }
+    Ok(receivers.into_iter().map(|r| r.to_string()).collect())
+}
+
+fn parse_warnings(input: ParseStream) -> syn::Result<Vec<OptInWarning>> {
+    let warn_keyword: kw::warn = input.parse()?;
+    let content;
+    syn::parenthesized!(content in input);
+    let kinds: Punctuated<Ident, Comma> =
+        Punctuated::parse_terminated(&content).map_err(|_| syn::Error::new(warn_keyword.span(), EXPECTED_WARNINGS))?;
+
+    if kinds.is_empty() {
+        return Err(syn::Error::new(warn_keyword.span(), EXPECTED_WARNINGS));
+    }
+    kinds
+        .into_iter()
+        .map(|kind| match kind.to_string().as_str() {
+            "conditional" => Ok(OptInWarning::Conditional),
+            "deprecated" => Ok(OptInWarning::Deprecated),
+            "unawaited" => Ok(OptInWarning::Unawaited),
+            "empty" => Ok(OptInWarning::Empty),
+            "unwrap" => Ok(OptInWarning::Unwrap),
+            "pagination" => Ok(OptInWarning::Pagination),
+            "queue_url" => Ok(OptInWarning::QueueUrl),
+            "s3_key" => Ok(OptInWarning::S3Key),
+            "duplicate" => Ok(OptInWarning::Duplicate),
+            "batch" => Ok(OptInWarning::Batch),
+            "concurrent" => Ok(OptInWarning::Concurrent),
+            "unused_sdk" => Ok(OptInWarning::UnusedSdk),
+            "hardcoded_resource" => Ok(OptInWarning::HardcodedResource),
+            "timeout" => Ok(OptInWarning::Timeout),
+            "idempotency" => Ok(OptInWarning::Idempotency),
+            "sqs_delete" => Ok(OptInWarning::SqsDelete),
+            _ => Err(syn::Error::new(kind.span(), ALLOWED_WARNINGS)),
         })
+        .collect()
+}
+
+fn parse_policy(input: ParseStream) -> syn::Result<Policy> {
+    let policy_keyword: kw::policy = input.parse()?;
+    let _equals_token: Token![=] = input.parse().map_err(|_| syn::Error::new(policy_keyword.span(), ALLOWED_POLICIES))?;
+    let policy: Ident = input.parse().map_err(|_| syn::Error::new(policy_keyword.span(), ALLOWED_POLICIES))?;
+    match policy.to_string().as_str() {
+        "encryption" => Ok(Policy::Encryption),
+        _ => Err(syn::Error::new(policy.span(), ALLOWED_POLICIES)),
+    }
+}
+
+fn parse_recommend(input: ParseStream) -> syn::Result<bool> {
+    let recommend_keyword: kw::recommend = input.parse()?;
+    let _equals_token: Token![=] = input.parse().map_err(|_| syn::Error::new(recommend_keyword.span(), EXPECTED_RECOMMEND))?;
+    let recommend: LitBool = input.parse().map_err(|_| syn::Error::new(recommend_keyword.span(), EXPECTED_RECOMMEND))?;
+    Ok(recommend.value)
+}
+
+fn parse_instrument(input: ParseStream) -> syn::Result<bool> {
+    let instrument_keyword: kw::instrument = input.parse()?;
+    let _equals_token: Token![=] = input.parse().map_err(|_| syn::Error::new(instrument_keyword.span(), EXPECTED_INSTRUMENT))?;
+    let instrument: LitBool = input.parse().map_err(|_| syn::Error::new(instrument_keyword.span(), EXPECTED_INSTRUMENT))?;
+    Ok(instrument.value)
+}
+
+fn parse_runtime_checks(input: ParseStream) -> syn::Result<bool> {
+    let runtime_checks_keyword: kw::runtime_checks = input.parse()?;
+    let _equals_token: Token![=] = input.parse().map_err(|_| syn::Error::new(runtime_checks_keyword.span(), EXPECTED_RUNTIME_CHECKS))?;
+    let runtime_checks: LitBool = input.parse().map_err(|_| syn::Error::new(runtime_checks_keyword.span(), EXPECTED_RUNTIME_CHECKS))?;
+    Ok(runtime_checks.value)
+}
+
+/// Just `assert_setters`, or `assert_setters = false` to turn off the flag of a profile
+fn parse_assert_setters(input: ParseStream) -> syn::Result<bool> {
+    let assert_setters_keyword: kw::assert_setters = input.parse()?;
+    if input.parse::<Option<Token![=]>>()?.is_none() {
+        return Ok(true);
+    }
+    let assert_setters: LitBool = input.parse().map_err(|_| syn::Error::new(assert_setters_keyword.span(), EXPECTED_ASSERT_SETTERS))?;
+    Ok(assert_setters.value)
+}
+
+fn parse_max_errors(input: ParseStream) -> syn::Result<usize> {
+    let max_errors_keyword: kw::max_errors = input.parse()?;
+    let expected = || syn::Error::new(max_errors_keyword.span(), EXPECTED_MAX_ERRORS);
+    let _equals_token: Token![=] = input.parse().map_err(|_| expected())?;
+    let max_errors: LitInt = input.parse().map_err(|_| expected())?;
+    max_errors.base10_parse().ok().filter(|max| *max > 0).ok_or_else(expected)
+}
+
+fn parse_profile(input: ParseStream) -> syn::Result<LitStr> {
+    let profile_keyword: kw::profile = input.parse()?;
+    let _equals_token: Token![=] = input.parse().map_err(|_| syn::Error::new(profile_keyword.span(), EXPECTED_PROFILE))?;
+    input.parse().map_err(|_| syn::Error::new(profile_keyword.span(), EXPECTED_PROFILE))
+}
+
+/// A month written like `2024-06`, so it can be compared with the month of our data as a string
+fn parse_min_data(input: ParseStream) -> syn::Result<LitStr> {
+    let min_data_keyword: kw::min_data = input.parse()?;
+    let _equals_token: Token![=] = input.parse().map_err(|_| syn::Error::new(min_data_keyword.span(), EXPECTED_MIN_DATA))?;
+    let month: LitStr = input.parse().map_err(|_| syn::Error::new(min_data_keyword.span(), EXPECTED_MIN_DATA))?;
+    match month.value().split_once('-') {
+        Some((year, month_of_year))
+            if year.len() == 4
+                && year.chars().all(|c| c.is_ascii_digit())
+                && month_of_year.len() == 2
+                && month_of_year.parse::<u8>().is_ok_and(|m| (1..=12).contains(&m)) =>
+        {
+            Ok(month)
+        }
+        _ => Err(syn::Error::new(month.span(), EXPECTED_MIN_DATA)),
+    }
+}
+
+fn parse_max_values(input: ParseStream) -> syn::Result<Vec<(String, i64)>> {
+    let max_keyword: kw::max = input.parse()?;
+    let content;
+    syn::parenthesized!(content in input);
+    let max_values: Punctuated<MaxValue, Comma> =
+        Punctuated::parse_terminated(&content).map_err(|_| syn::Error::new(max_keyword.span(), EXPECTED_MAX_VALUES))?;
+
+    if max_values.is_empty() {
+        return Err(syn::Error::new(max_keyword.span(), EXPECTED_MAX_VALUES));
+    }
+    Ok(max_values.into_iter().map(|m| (m.setter.to_string(), m.max)).collect())
+}
+
+struct MaxValue {
+    setter: Ident,
+    max: i64,
+}
+
+impl Parse for MaxValue {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let setter = input.parse()?;
+        let _equals_token: Token![=] = input.parse()?;
+        let max: LitInt = input.parse()?;
+        Ok(MaxValue { setter, max: max.base10_parse()? })
+    }
+}
+
+struct ClientMapping {
+    receiver: Ident,
+    sdk: Ident,
+}
+
+impl Parse for ClientMapping {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let receiver = input.parse()?;
+        let _equals_token: Token![=] = input.parse()?;
+        let sdk = input.parse()?;
+        Ok(ClientMapping { receiver, sdk })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn parse_empty_attributes() {
+        let attributes: Attributes = syn::parse2(quote!()).unwrap();
+
+        assert!(attributes.sdks.is_empty());
+        assert!(attributes.clients.is_empty());
+    }
+
+    #[test]
+    fn parse_sdks_with_trailing_comma() {
+        let attributes: Attributes = syn::parse2(quote!(sdk = sqs, s3,)).unwrap();
+
+        assert_eq!(attributes.sdks, vec!["sqs", "s3"]);
+    }
+
+    #[test]
+    fn parse_sdks_followed_by_clients() {
+        let attributes: Attributes = syn::parse2(quote!(sdk = sqs, s3, clients(client = amplify, other = s3))).unwrap();
+
+        assert_eq!(attributes.sdks, vec!["sqs", "s3"]);
+        assert_eq!(
+            attributes.clients,
+            HashMap::from([("client".to_string(), "amplify".to_string()), ("other".to_string(), "s3".to_string())])
+        );
+    }
+
+    #[test]
+    fn parse_clients_followed_by_sdks() {
+        let attributes: Attributes = syn::parse2(quote!(clients(client = amplify), sdk = sqs)).unwrap();
+
+        assert_eq!(attributes.sdks, vec!["sqs"]);
+        assert_eq!(attributes.all_sdks().len(), 2);
+    }
+
+    #[test]
+    fn parse_misspelled_attribute_suggests_the_closest_one_with_its_value() {
+        let error = |tokens| syn::parse2::<Attributes>(tokens).unwrap_err().to_string();
+
+        assert_eq!(error(quote!(skd = sqs)), format!("unknown attribute `skd`, did you mean `sdk = sqs`?\n{}", ATTRIBUTE_GRAMMAR));
+        assert_eq!(error(quote!(sdk = s3, warns(timeout))), format!("unknown attribute `warns`, did you mean `warn(timeout)`?\n{}", ATTRIBUTE_GRAMMAR));
+        assert_eq!(error(quote!(fake)), format!("unknown attribute `fake`, {}\n{}", ALLOWED_ATTRIBUTES, ATTRIBUTE_GRAMMAR));
+    }
+
+    #[test]
+    fn parse_attribute_with_a_colon_suggests_an_equals_sign() {
+        let error = |tokens| syn::parse2::<Attributes>(tokens).unwrap_err().to_string();
+
+        assert_eq!(error(quote!(sdk: sqs)), format!("expected `=` after `sdk`, not `:`, did you mean `sdk = sqs`?\n{}", ATTRIBUTE_GRAMMAR));
+        assert_eq!(
+            error(quote!(sdk = s3, profile: "strict-prod")),
+            format!("expected `=` after `profile`, not `:`, did you mean `profile = \"strict-prod\"`?\n{}", ATTRIBUTE_GRAMMAR)
+        );
+    }
+
+    #[test]
+    fn parse_sdks_followed_by_assumed_sdk() {
+        let attributes: Attributes = syn::parse2(quote!(sdk = s3, dynamodb, assume_sdk = sqs)).unwrap();
+
+        assert_eq!(attributes.sdks, vec!["s3", "dynamodb"]);
+        assert_eq!(attributes.assume_sdk, Some("sqs".to_string()));
+        assert_eq!(attributes.all_sdks(), vec!["s3", "dynamodb", "sqs"]);
+    }
+
+    #[test]
+    fn parse_assumed_sdk_without_an_sdk_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(assume_sdk = "sqs"));
+
+        assert_eq!(result.unwrap_err().to_string(), EXPECTED_ASSUME_SDK);
+    }
+
+    #[test]
+    fn parse_sdks_followed_by_warnings() {
+        let attributes: Attributes = syn::parse2(quote!(sdk = sqs, warn(conditional, deprecated, unawaited, empty))).unwrap();
+
+        assert_eq!(attributes.sdks, vec!["sqs"]);
+        assert_eq!(
+            attributes.warnings,
+            vec![OptInWarning::Conditional, OptInWarning::Deprecated, OptInWarning::Unawaited, OptInWarning::Empty]
+        );
+    }
+
+    #[test]
+    fn parse_sdks_followed_by_assert_setters() {
+        let attributes: Attributes = syn::parse2(quote!(sdk = sqs, s3, assert_setters)).unwrap();
+
+        assert_eq!(attributes.sdks, vec!["sqs", "s3"]);
+        assert_eq!(attributes.assert_setters, Some(true));
+    }
+
+    #[test]
+    fn parse_unknown_warning_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(warn(everything)));
+
+        assert_eq!(result.unwrap_err().to_string(), ALLOWED_WARNINGS);
+    }
+
+    #[test]
+    fn parse_policy_followed_by_sdks() {
+        let attributes: Attributes = syn::parse2(quote!(policy = encryption, sdk = s3)).unwrap();
+
+        assert_eq!(attributes.policies, vec![Policy::Encryption]);
+        assert_eq!(attributes.sdks, vec!["s3"]);
+    }
+
+    #[test]
+    fn parse_max_values_followed_by_sdks() {
+        let attributes: Attributes = syn::parse2(quote!(max(read_capacity_units = 100, shard_count = 10), sdk = dynamodb)).unwrap();
+
+        assert_eq!(attributes.max_values, vec![("read_capacity_units".to_string(), 100), ("shard_count".to_string(), 10)]);
+        assert_eq!(attributes.sdks, vec!["dynamodb"]);
+    }
+
+    #[test]
+    fn parse_max_values_without_a_number_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(max(read_capacity_units = many)));
+
+        assert_eq!(result.unwrap_err().to_string(), EXPECTED_MAX_VALUES);
+    }
+
+    #[test]
+    fn parse_recommend_followed_by_sdks() {
+        let attributes: Attributes = syn::parse2(quote!(recommend = true, sdk = s3)).unwrap();
+
+        assert_eq!(attributes.recommend, Some(true));
+        assert_eq!(attributes.sdks, vec!["s3"]);
+    }
+
+    #[test]
+    fn parse_recommend_without_a_bool_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(recommend = yes));
+
+        assert_eq!(result.unwrap_err().to_string(), EXPECTED_RECOMMEND);
+    }
+
+    #[test]
+    fn parse_instrument_followed_by_warnings() {
+        let attributes: Attributes = syn::parse2(quote!(instrument = true, warn(timeout))).unwrap();
+
+        assert_eq!(attributes.instrument, Some(true));
+        assert_eq!(attributes.warnings, vec![OptInWarning::Timeout]);
+    }
+
+    #[test]
+    fn parse_runtime_checks_followed_by_sdks() {
+        let attributes: Attributes = syn::parse2(quote!(runtime_checks = true, sdk = sqs)).unwrap();
+
+        assert_eq!(attributes.runtime_checks, Some(true));
+        assert_eq!(attributes.sdks, vec!["sqs"]);
+    }
+
+    #[test]
+    fn parse_runtime_checks_without_a_bool_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(runtime_checks));
+
+        assert_eq!(result.unwrap_err().to_string(), EXPECTED_RUNTIME_CHECKS);
+    }
+
+    #[test]
+    fn parse_profile_followed_by_warnings() {
+        let attributes: Attributes = syn::parse2(quote!(profile = "strict-prod", warn(batch))).unwrap();
+
+        assert_eq!(attributes.profile.map(|p| p.value()), Some("strict-prod".to_string()));
+        assert_eq!(attributes.warnings, vec![OptInWarning::Batch]);
+    }
+
+    #[test]
+    fn with_profile_adds_the_arguments_of_the_profile() {
+        let attributes: Attributes = syn::parse2(quote!(profile = "strict-prod", warn(batch), clients(queue = sqs))).unwrap();
+        let profile: Attributes = syn::parse2(quote!(warn(unwrap), clients(queue = connectparticipant, bucket = s3), recommend = true)).unwrap();
+
+        let actual = attributes.with_profile(profile);
+
+        assert_eq!(actual.warnings, vec![OptInWarning::Batch, OptInWarning::Unwrap]);
+        assert_eq!(actual.clients, HashMap::from([("queue".to_string(), "sqs".to_string()), ("bucket".to_string(), "s3".to_string())]));
+        assert_eq!(actual.recommend, Some(true));
+    }
+
+    #[test]
+    fn with_profile_keeps_the_flags_of_the_attribute() {
+        let attributes: Attributes = syn::parse2(quote!(profile = "strict-prod", recommend = false, assert_setters = false)).unwrap();
+        let profile: Attributes = syn::parse2(quote!(recommend = true, instrument = true, assert_setters)).unwrap();
+
+        let actual = attributes.with_profile(profile);
+
+        assert_eq!(actual.recommend, Some(false));
+        assert_eq!(actual.assert_setters, Some(false));
+        assert_eq!(actual.instrument, Some(true));
+        assert_eq!(actual.runtime_checks, None);
+    }
+
+    #[test]
+    fn parse_profile_without_a_string_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(profile = strict));
+
+        assert_eq!(result.unwrap_err().to_string(), EXPECTED_PROFILE);
+    }
+
+    #[test]
+    fn parse_max_errors_followed_by_max_values() {
+        let attributes: Attributes = syn::parse2(quote!(max_errors = 5, max(shard_count = 4))).unwrap();
+
+        assert_eq!(attributes.max_errors, Some(5));
+        assert_eq!(attributes.max_values, vec![("shard_count".to_string(), 4)]);
+    }
+
+    #[test]
+    fn parse_max_errors_of_zero_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(max_errors = 0));
+
+        assert_eq!(result.unwrap_err().to_string(), EXPECTED_MAX_ERRORS);
+    }
+
+    #[test]
+    fn with_sdk_adds_the_sdk_to_the_arguments() {
+        assert_eq!(with_sdk("#[required_props]", "amplify"), Some("#[required_props(sdk = amplify)]".to_string()));
+        assert_eq!(with_sdk("#[required_props()]", "amplify"), Some("#[required_props(sdk = amplify)]".to_string()));
+        assert_eq!(
+            with_sdk("#[required_props(warn(timeout))]", "amplify"),
+            Some("#[required_props(warn(timeout), sdk = amplify)]".to_string())
+        );
+        assert_eq!(with_sdk("#[required_props(sdk = s3,)]", "amplify"), Some("#[required_props(sdk = s3, sdk = amplify)]".to_string()));
+        assert_eq!(with_sdk("required_props", "amplify"), None);
+    }
+
+    #[test]
+    fn parse_min_data_followed_by_sdks() {
+        let attributes: Attributes = syn::parse2(quote!(min_data = "2024-06", sdk = sqs)).unwrap();
+
+        assert_eq!(attributes.min_data.map(|m| m.value()), Some("2024-06".to_string()));
+        assert_eq!(attributes.sdks, vec!["sqs".to_string()]);
+    }
+
+    #[test]
+    fn parse_min_data_that_is_not_a_month_fails() {
+        for min_data in [quote!(min_data = "2024-13"), quote!(min_data = "June 2024"), quote!(min_data = 2024)] {
+            let result: syn::Result<Attributes> = syn::parse2(min_data);
+
+            assert_eq!(result.unwrap_err().to_string(), EXPECTED_MIN_DATA);
+        }
+    }
+
+    #[test]
+    fn parse_unknown_policy_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(policy = everything));
+
+        assert_eq!(result.unwrap_err().to_string(), ALLOWED_POLICIES);
+    }
+
+    #[test]
+    fn parse_only_and_ignore_clients_followed_by_sdks() {
+        let attributes: Attributes = syn::parse2(quote!(only_clients(sqs_client, s3_client), ignore_clients(metrics_client), sdk = sqs)).unwrap();
+
+        assert_eq!(attributes.only_clients, vec!["sqs_client", "s3_client"]);
+        assert_eq!(attributes.ignore_clients, vec!["metrics_client"]);
+        assert_eq!(attributes.sdks, vec!["sqs"]);
+    }
+
+    #[test]
+    fn parse_ignore_clients_without_receivers_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(ignore_clients()));
+
+        assert_eq!(result.unwrap_err().to_string(), EXPECTED_IGNORE_CLIENTS);
+    }
+
+    #[test]
+    fn parse_clients_without_mappings_fails() {
+        let result: syn::Result<Attributes> = syn::parse2(quote!(clients()));
+
+        assert_eq!(result.unwrap_err().to_string(), EXPECTED_CLIENTS);
     }
 }
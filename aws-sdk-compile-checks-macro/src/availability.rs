@@ -0,0 +1,144 @@
+use aws_sdk_compile_checks_data::availability::available_regions;
+
+use crate::findings::{UnavailableUsage, Usage, UsageFinds};
+use crate::literals::validate_region;
+use crate::registered::tracked_env_var;
+use crate::visitor::SdkCall;
+
+/// The region(s) the code runs in, separated by `,`, e.g. `eu-west-1` or `us-gov-west-1,us-gov-east-1`.
+/// Used for functions that do not pass a region literal to `region`
+const REGION_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_REGION";
+
+/// A region the calls of a function go to, with a description of where we found it
+#[derive(Debug, PartialEq)]
+pub(crate) struct TargetRegion {
+    pub(crate) region: String,
+    pub(crate) source: String,
+}
+
+/// The regions of the function: those passed as literals to `region`, or else the configured ones.
+/// Errors when a configured region does not exist, because then nothing would be checked
+pub(crate) fn target_regions(region_literals: Vec<String>) -> Result<Vec<TargetRegion>, String> {
+    if !region_literals.is_empty() {
+        return Ok(region_literals
+            .into_iter()
+            .map(|region| TargetRegion {
+                source: format!("`.region(\"{}\")`", region),
+                region,
+            })
+            .collect());
+    }
+    let Some(configured) = tracked_env_var(REGION_ENV_VAR) else {
+        return Ok(vec![]);
+    };
+    parse_configured_regions(&configured.to_string_lossy())
+}
+
+fn parse_configured_regions(configured: &str) -> Result<Vec<TargetRegion>, String> {
+    configured
+        .split(',')
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(|region| match validate_region(region) {
+            Ok(_) => Ok(TargetRegion {
+                region: region.to_string(),
+                source: format!("`{}`", REGION_ENV_VAR),
+            }),
+            Err(Some(suggestion)) => Err(format!("`{}` is not an AWS region, did you mean `{}`?", region, suggestion)),
+            Err(None) => Err(format!("`{}` is not an AWS region", region)),
+        })
+        .collect()
+}
+
+/// An error for every call of a service (or operation) that is not available in one of the regions.
+/// Regions of partitions we have no data of the service for (like `cn-north-1` for App Runner) are not checked
+pub(crate) fn find_unavailable_calls(calls: &[SdkCall], regions: &[TargetRegion]) -> Vec<UsageFinds> {
+    let mut findings = vec![];
+    for call in calls {
+        for target in regions {
+            let Some(available) = available_regions(&call.sdk, &call.operation, &target.region) else {
+                continue;
+            };
+            if available.contains(&target.region.as_str()) {
+                continue;
+            }
+            findings.push(UsageFinds::of_sdk(call.span, call.sdk.clone(), Usage::Unavailable(UnavailableUsage {
+                method: call.method.clone(),
+                region: target.region.clone(),
+                source: target.source.clone(),
+                available,
+            })));
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    use super::*;
+    use crate::registered::tracked_inputs;
+
+    fn call(sdk: &str, method: &str) -> SdkCall {
+        SdkCall {
+            span: Span::call_site(),
+            sdk: sdk.to_string(),
+            method: method.to_string(),
+            operation: method.to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_configured_regions_rejects_unknown_regions() {
+        let actual = parse_configured_regions("eu-west-1, us-gov-west-1,").unwrap();
+
+        assert_eq!(actual.iter().map(|r| r.region.as_str()).collect::<Vec<_>>(), vec!["eu-west-1", "us-gov-west-1"]);
+        assert_eq!(actual[0].source, "`AWS_SDK_COMPILE_CHECKS_REGION`");
+        assert_eq!(parse_configured_regions("eu-west1").unwrap_err(), "`eu-west1` is not an AWS region, did you mean `eu-west-1`?");
+    }
+
+    #[test]
+    fn target_regions_prefers_the_literals_of_the_function() {
+        let actual = target_regions(vec!["eu-south-1".to_string()]).unwrap();
+
+        assert_eq!(
+            actual,
+            vec![TargetRegion {
+                region: "eu-south-1".to_string(),
+                source: "`.region(\"eu-south-1\")`".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn target_regions_without_literals_make_the_expansion_depend_on_the_variable() {
+        let _ = target_regions(vec![]);
+
+        assert!(tracked_inputs().to_string().contains("option_env ! (\"AWS_SDK_COMPILE_CHECKS_REGION\")"));
+    }
+
+    #[test]
+    fn find_unavailable_calls_in_regions() {
+        let calls = vec![call("apprunner", "create_service"), call("sqs", "send_message")];
+        let regions = target_regions(vec!["eu-south-1".to_string(), "eu-west-1".to_string()]).unwrap();
+
+        let actual: Vec<(String, String)> = find_unavailable_calls(&calls, &regions)
+            .into_iter()
+            .map(|f| match f.usage {
+                Usage::Unavailable(u) => (u.method, u.region),
+                _ => panic!("expected only unavailable findings"),
+            })
+            .collect();
+
+        assert_eq!(actual, vec![("create_service".to_string(), "eu-south-1".to_string())]);
+    }
+
+    #[test]
+    fn find_unavailable_calls_skips_partitions_without_data() {
+        let calls = vec![call("apprunner", "create_service")];
+        let regions = target_regions(vec!["cn-north-1".to_string()]).unwrap();
+
+        assert!(find_unavailable_calls(&calls, &regions).is_empty());
+    }
+}
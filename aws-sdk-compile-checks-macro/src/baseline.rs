@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use serde_json::Value;
+
+use crate::diagnostics::Diagnostic;
+use crate::export::file_of;
+use crate::registered::registered_files;
+
+/// Files of findings exported with `AWS_SDK_COMPILE_CHECKS_EXPORT`, e.g. `baseline.jsonl` with the files of all crates combined, which are accepted for now
+const BASELINE_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_BASELINE";
+
+/// The file, code and message of a finding. Lines and columns are left out, so editing the code above it does not matter
+type Fingerprint = (String, String, String);
+
+/// The findings in the baseline (see [`registered_files`]), `None` when there is none, or a description of why it could not be loaded
+pub(crate) fn baseline() -> Result<Option<&'static HashSet<Fingerprint>>, &'static str> {
+    static BASELINE: OnceLock<Result<Option<HashSet<Fingerprint>>, String>> = OnceLock::new();
+    BASELINE
+        .get_or_init(|| {
+            let files = registered_files(BASELINE_ENV_VAR)?;
+            if files.is_empty() {
+                return Ok(None);
+            }
+            let mut fingerprints = HashSet::new();
+            for file in files {
+                fingerprints.extend(parse_baseline(&file.content, &file.name)?);
+            }
+            Ok(Some(fingerprints))
+        })
+        .as_ref()
+        .map(Option::as_ref)
+        .map_err(String::as_str)
+}
+
+fn parse_baseline(content: &str, file: &str) -> Result<HashSet<Fingerprint>, String> {
+    let mut fingerprints = HashSet::new();
+    for (index, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let finding: Value = serde_json::from_str(line).map_err(|e| format!("{}:{}: {}", file, index + 1, e))?;
+        let field = |key: &str| match finding.get(key) {
+            Some(Value::String(value)) => Ok(value.clone()),
+            _ => Err(format!("{}:{}: expected a finding with a `{}`, like the lines of `AWS_SDK_COMPILE_CHECKS_EXPORT`", file, index + 1, key)),
+        };
+        fingerprints.insert((field("file")?, field("code")?, field("message")?));
+    }
+    Ok(fingerprints)
+}
+
+/// Errors that are in the baseline become warnings, all other diagnostics stay as they are
+pub(crate) fn apply_baseline(diagnostic: Diagnostic, baseline: &HashSet<Fingerprint>) -> Diagnostic {
+    let Some(code) = diagnostic.code else {
+        return diagnostic;
+    };
+    let fingerprint = (file_of(diagnostic.span), code.to_string(), diagnostic.message.clone());
+    match baseline.contains(&fingerprint) {
+        true => diagnostic.downgraded(format!("this error is accepted in the baseline of `{}`", BASELINE_ENV_VAR)),
+        false => diagnostic,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    use super::*;
+    use crate::registered::tracked_inputs;
+
+    #[test]
+    fn parse_baseline_of_exported_findings() {
+        let content = r#"{"level":"error","code":"missing-required-props","message":"method `send_message` (from sqs) is missing required argument(s): `queue_url`","notes":[],"help":[],"file":"src/lib.rs","start_line":3,"start_column":5,"end_line":3,"end_column":17}
+
+"#;
+
+        let actual = parse_baseline(content, "baseline.jsonl").unwrap();
+
+        assert_eq!(
+            actual,
+            HashSet::from([(
+                "src/lib.rs".to_string(),
+                "missing-required-props".to_string(),
+                "method `send_message` (from sqs) is missing required argument(s): `queue_url`".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn parse_baseline_requires_the_fingerprint() {
+        let actual = parse_baseline(r#"{"code":"missing-required-props","message":"something"}"#, "baseline.jsonl").unwrap_err();
+
+        assert_eq!(actual, "baseline.jsonl:1: expected a finding with a `file`, like the lines of `AWS_SDK_COMPILE_CHECKS_EXPORT`");
+    }
+
+    #[test]
+    fn baseline_makes_the_expansion_depend_on_its_variable() {
+        let _ = baseline();
+
+        assert!(tracked_inputs().to_string().contains("option_env ! (\"AWS_SDK_COMPILE_CHECKS_BASELINE\")"));
+    }
+
+    #[test]
+    fn apply_baseline_downgrades_accepted_errors() {
+        let error = || Diagnostic::error(Span::call_site(), "something is wrong").code("missing-required-props");
+        let baseline = HashSet::from([(file_of(Span::call_site()), "missing-required-props".to_string(), "something is wrong".to_string())]);
+
+        let accepted = apply_baseline(error(), &baseline);
+        let other = apply_baseline(error(), &HashSet::new());
+
+        assert_eq!(accepted.notes, vec!["this error is accepted in the baseline of `AWS_SDK_COMPILE_CHECKS_BASELINE`"]);
+        assert!(other.notes.is_empty());
+    }
+}
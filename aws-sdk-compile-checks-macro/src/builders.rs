@@ -0,0 +1,160 @@
+use std::sync::OnceLock;
+
+use aws_sdk_compile_checks_data::required_properties::alternatives;
+use aws_sdk_compile_checks_data::rows::records;
+
+use crate::registered::registered_files;
+
+/// Registers required methods for the fluent builders of your own APIs, as CSV files, e.g. `builders.csv`
+const BUILDERS_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_BUILDERS";
+
+/// A row of `type,method,terminator,prop1 prop2`, e.g. `EventPublisher,publish,dispatch,detail_type source`:
+/// every chain that starts with the method has to call the properties before the terminator, like the AWS SDKs before `send`
+#[derive(Debug, PartialEq)]
+pub(crate) struct BuilderRule {
+    pub(crate) owner: &'static str, // the type with the method, only to tell the user which API it is about
+    pub(crate) method: &'static str,
+    pub(crate) terminator: &'static str,
+    pub(crate) required: Vec<&'static str>, // like the AWS data, these can be 'at least one of' groups, e.g. `source|source_arn`
+    pub(crate) file: &'static str,
+}
+
+impl BuilderRule {
+    /// The required properties that none of the calls in the chain set
+    pub(crate) fn missing<'a>(&'a self, calls_in_chain: &'a [String]) -> impl Iterator<Item = &'static str> + 'a {
+        self.required
+            .iter()
+            .filter(|required| !alternatives(required).any(|alternative| calls_in_chain.iter().any(|c| c == alternative)))
+            .copied()
+    }
+}
+
+/// The rules in the files registered with `AWS_SDK_COMPILE_CHECKS_BUILDERS` (see [`registered_files`]),
+/// or a description of why they could not be loaded
+pub(crate) fn builder_rules() -> Result<&'static [BuilderRule], &'static str> {
+    static RULES: OnceLock<Result<Vec<BuilderRule>, String>> = OnceLock::new();
+    RULES
+        .get_or_init(|| {
+            let mut rules = vec![];
+            for file in registered_files(BUILDERS_ENV_VAR)? {
+                rules.extend(parse_rules(&file.content, &file.name)?);
+            }
+            Ok(rules)
+        })
+        .as_ref()
+        .map(Vec::as_slice)
+        .map_err(String::as_str)
+}
+
+fn parse_rules(csv: &str, file: &str) -> Result<Vec<BuilderRule>, String> {
+    // the rules live as long as the compilation, like the datasets
+    let file: &'static str = file.to_string().leak();
+    let mut rules: Vec<BuilderRule> = vec![];
+
+    for record in records(csv) {
+        let record = record.map_err(|e| format!("{}:{}: {}", file, e.line, e.message))?;
+        let error = |message: String| format!("{}:{}: {}", file, record.line, message);
+        if record.fields.len() != 4 {
+            return Err(error(format!(
+                "expected 4 columns (type, method, terminator and properties), found {}",
+                record.fields.len()
+            )));
+        }
+        let (owner, method, terminator, required) = (record.field(0), record.field(1), record.field(2), record.field(3));
+        if owner.is_empty() {
+            return Err(error("expected the name of a type, found an empty column".to_string()));
+        }
+        let required: Vec<&str> = required.split_whitespace().collect();
+        if required.is_empty() {
+            return Err(error("expected one or more required properties".to_string()));
+        }
+        if let Some(name) = [method, terminator]
+            .into_iter()
+            .chain(required.iter().flat_map(|r| r.split('|')))
+            .find(|n| !is_method_name(n))
+        {
+            return Err(error(format!("expected snake_case method names, found `{}`", name)));
+        }
+        if rules.iter().any(|r| r.method == method) {
+            return Err(error(format!("`{}` was already defined", method)));
+        }
+        rules.push(BuilderRule {
+            owner: owner.to_string().leak(),
+            method: method.to_string().leak(),
+            terminator: terminator.to_string().leak(),
+            required: required.into_iter().map(|r| &*r.to_string().leak()).collect(),
+            file,
+        });
+    }
+    Ok(rules)
+}
+
+fn is_method_name(name: &str) -> bool {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+pub(crate) fn rules_for_tests(csv: &str) -> &'static [BuilderRule] {
+    parse_rules(csv, "builders.csv").unwrap().leak()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rules_of_own_builders() {
+        let actual = parse_rules(
+            "EventPublisher,publish,dispatch,detail_type source\nMailer,compose,deliver,to|bcc\n",
+            "builders.csv",
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual,
+            vec![
+                BuilderRule {
+                    owner: "EventPublisher",
+                    method: "publish",
+                    terminator: "dispatch",
+                    required: vec!["detail_type", "source"],
+                    file: "builders.csv",
+                },
+                BuilderRule {
+                    owner: "Mailer",
+                    method: "compose",
+                    terminator: "deliver",
+                    required: vec!["to|bcc"],
+                    file: "builders.csv",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rules_without_terminator_fails() {
+        let actual = parse_rules("EventPublisher,publish,detail_type source\n", "builders.csv").unwrap_err();
+
+        assert_eq!(
+            actual,
+            "builders.csv:1: expected 4 columns (type, method, terminator and properties), found 3"
+        );
+    }
+
+    #[test]
+    fn parse_rules_with_a_type_as_method_fails() {
+        let actual = parse_rules("EventPublisher,Publish,dispatch,source\n", "builders.csv").unwrap_err();
+
+        assert_eq!(actual, "builders.csv:1: expected snake_case method names, found `Publish`");
+    }
+
+    #[test]
+    fn missing_counts_any_alternative_of_a_group() {
+        let rules = rules_for_tests("Mailer,compose,deliver,to|bcc subject\n");
+        let calls = vec!["bcc".to_string()];
+
+        assert_eq!(rules[0].missing(&calls).collect::<Vec<_>>(), vec!["subject"]);
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use aws_sdk_compile_checks_data::rows::{fluent_builder_of, parse_row, records};
+
+use crate::registered::{read_registered_file, registrations};
+
+/// Registers datasets for in-house clients generated with smithy-rs, as `module prefix=path` pairs, e.g. `my_internal_sdk_*=internal_props.csv`
+const DATASETS_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_DATASETS";
+const PREFIX_WILDCARD: char = '*';
+
+/// (service, method, properties, docs URL) of a row in a dataset, the URL being empty when we do not know it
+type DatasetEntry = (&'static str, &'static str, &'static [&'static str], &'static str);
+
+/// Required properties of clients that follow the same patterns as the AWS SDKs, but live in crates like `my_internal_sdk_orders`
+#[derive(Debug)]
+pub(crate) struct Dataset {
+    pub(crate) prefix: String, // the module prefix without the wildcard, e.g. `my_internal_sdk_`
+    entries: Vec<DatasetEntry>,
+    builders: Vec<(String, &'static str, &'static str)>, // (fluent builder, service, method)
+    sdks: OnceLock<HashSet<&'static str>>, // the services of the entries, collected once, because the datasets stay loaded for the whole crate
+}
+
+impl Dataset {
+    /// The services in this dataset with the given method, with the properties they require for it
+    pub(crate) fn props_for<'a>(&'a self, method: &'a str) -> impl Iterator<Item = (&'static str, &'static [&'static str])> + 'a {
+        self.entries
+            .iter()
+            .filter(move |(_, method_name, _, _)| *method_name == method)
+            .map(|(service, _, required_props, _)| (*service, *required_props))
+    }
+
+    pub(crate) fn docs_url(&self, method: &str, sdk: &str) -> Option<&'static str> {
+        self.entries
+            .iter()
+            .find(|(service, method_name, _, _)| *service == sdk && *method_name == method)
+            .map(|(_, _, _, docs_url)| *docs_url)
+    }
+
+    pub(crate) fn operations_of_builder<'a>(&'a self, builder: &'a str) -> impl Iterator<Item = (&'static str, &'static str)> + 'a {
+        self.builders
+            .iter()
+            .filter(move |(name, _, _)| name == builder)
+            .map(|(_, service, method)| (*service, *method))
+    }
+
+    pub(crate) fn contains_sdk(&self, sdk: &str) -> bool {
+        self.sdks.get_or_init(|| self.entries.iter().map(|(service, _, _, _)| *service).collect()).contains(sdk)
+    }
+}
+
+/// The datasets registered with `AWS_SDK_COMPILE_CHECKS_DATASETS` (see [`registrations`]), or a description of why they could not be loaded
+pub(crate) fn datasets() -> Result<&'static [Dataset], &'static str> {
+    static DATASETS: OnceLock<Result<Vec<Dataset>, String>> = OnceLock::new();
+    DATASETS
+        .get_or_init(|| {
+            parse_registrations(&registrations(DATASETS_ENV_VAR))?
+                .into_iter()
+                .map(|(prefix, path)| parse_dataset(prefix, &read_registered_file(&path)?, &path))
+                .collect()
+        })
+        .as_ref()
+        .map(Vec::as_slice)
+        .map_err(String::as_str)
+}
+
+/// (prefix without the wildcard, path) for every registration, e.g. `my_internal_sdk_*=internal_props.csv`
+fn parse_registrations(registrations: &[String]) -> Result<Vec<(String, String)>, String> {
+    registrations
+        .iter()
+        .map(|registration| {
+            let expected = || format!("expected `{}` to look like `my_internal_sdk_*=internal_props.csv`", registration);
+            let (pattern, path) = registration.split_once('=').ok_or_else(expected)?;
+            let prefix = pattern.trim().strip_suffix(PREFIX_WILDCARD).ok_or_else(expected)?;
+            if prefix.is_empty() || prefix.contains(PREFIX_WILDCARD) || path.trim().is_empty() {
+                return Err(expected());
+            }
+            Ok((prefix.to_string(), path.trim().to_string()))
+        })
+        .collect()
+}
+
+fn parse_dataset(prefix: String, csv: &str, file: &str) -> Result<Dataset, String> {
+    let mut seen = HashSet::new();
+    let mut entries = vec![];
+    let mut builders = vec![];
+
+    for record in records(csv) {
+        let record = record.map_err(|e| format!("{}:{}: {}", file, e.line, e.message))?;
+        let row = parse_row(&record).map_err(|e| format!("{}:{}: {}", file, record.line, e))?;
+        if !seen.insert((row.service.clone(), row.name.clone())) {
+            return Err(format!("{}:{}: `{},{}` was already defined", file, record.line, row.service, row.name));
+        }
+        let builder = if row.builder.is_empty() { fluent_builder_of(&row.name) } else { row.builder };
+        // the datasets live as long as the compilation, like our own data
+        let service: &'static str = row.service.leak();
+        let name: &'static str = row.name.leak();
+        let required_props: Vec<&'static str> = row.required_props.into_iter().map(|p| &*p.leak()).collect();
+        builders.push((builder, service, name));
+        entries.push((service, name, &*required_props.leak(), &*row.docs_url.leak()));
+    }
+    Ok(Dataset {
+        prefix,
+        entries,
+        builders,
+        sdks: OnceLock::new(),
+    })
+}
+
+#[cfg(test)]
+pub(crate) fn dataset_for_tests(prefix: &str, csv: &str) -> Dataset {
+    parse_dataset(prefix.to_string(), csv, "test.csv").unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registered::split_registrations;
+
+    #[test]
+    fn parse_registrations_strips_the_wildcard() {
+        let actual = parse_registrations(&split_registrations("my_internal_sdk_*=internal_props.csv; other_*=data/other.csv;")).unwrap();
+
+        assert_eq!(
+            actual,
+            vec![
+                ("my_internal_sdk_".to_string(), "internal_props.csv".to_string()),
+                ("other_".to_string(), "data/other.csv".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_registrations_without_wildcard_fails() {
+        let actual = parse_registrations(&split_registrations("my_internal_sdk=internal_props.csv")).unwrap_err();
+
+        assert_eq!(actual, "expected `my_internal_sdk=internal_props.csv` to look like `my_internal_sdk_*=internal_props.csv`");
+    }
+
+    #[test]
+    fn parse_dataset_finds_props_by_service() {
+        let dataset = dataset_for_tests("my_internal_sdk_", "orders,create_order,customer_id items\nbilling,create_order,amount,https://docs.example.com\n");
+
+        let mut props: Vec<_> = dataset.props_for("create_order").collect();
+        props.sort_unstable();
+        assert_eq!(props, vec![("billing", &["amount"][..]), ("orders", &["customer_id", "items"][..])]);
+        assert_eq!(dataset.docs_url("create_order", "billing"), Some("https://docs.example.com"));
+        assert_eq!(dataset.operations_of_builder("CreateOrderFluentBuilder").count(), 2);
+        assert!(dataset.contains_sdk("orders"));
+        assert!(!dataset.contains_sdk("sqs"));
+    }
+
+    #[test]
+    fn parse_dataset_reports_the_line_of_a_malformed_row() {
+        let actual = parse_dataset("my_".to_string(), "orders,create_order,customer_id\n\norders,get_order,OrderId\n", "internal.csv").unwrap_err();
+
+        assert_eq!(actual, "internal.csv:3: expected snake_case property names, found `OrderId`");
+    }
+
+    #[test]
+    fn parse_dataset_rejects_duplicate_rows() {
+        let actual = parse_dataset("my_".to_string(), "orders,get_order,id\norders,get_order,id\n", "internal.csv").unwrap_err();
+
+        assert_eq!(actual, "internal.csv:2: `orders,get_order` was already defined");
+    }
+}
@@ -0,0 +1,144 @@
+use std::sync::OnceLock;
+
+use proc_macro2::Span;
+
+use crate::findings::{DeniedSdkUsage, Usage, UsageFinds};
+use crate::registered::registrations;
+use crate::visitor::SdkCall;
+
+/// SDKs that crates may not use, as `crate pattern=sdk,sdk` pairs, e.g. `orders-*=iam,organizations`.
+/// The pattern is the name of a crate, optionally ending with `*`, and `*` on its own matches every crate
+const DENIED_SDKS_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_DENIED_SDKS";
+const CRATE_WILDCARD: char = '*';
+
+/// An SDK the crate that is being compiled may not use, with the registration that denies it
+#[derive(Debug, PartialEq)]
+pub(crate) struct DeniedSdk {
+    pub(crate) sdk: String,
+    pub(crate) registration: String, // e.g. `orders-*=iam,organizations`
+}
+
+/// The SDKs denied to the crate that is being compiled by the registrations of `AWS_SDK_COMPILE_CHECKS_DENIED_SDKS` (see [`registrations`]),
+/// or a description of why the registrations are invalid
+pub(crate) fn denied_sdks() -> Result<&'static [DeniedSdk], &'static str> {
+    static DENIED: OnceLock<Result<Vec<DeniedSdk>, String>> = OnceLock::new();
+    DENIED
+        .get_or_init(|| {
+            let crate_name = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
+            parse_denied_sdks(&registrations(DENIED_SDKS_ENV_VAR), &crate_name)
+        })
+        .as_ref()
+        .map(Vec::as_slice)
+        .map_err(String::as_str)
+}
+
+fn parse_denied_sdks(registrations: &[String], crate_name: &str) -> Result<Vec<DeniedSdk>, String> {
+    let mut denied = vec![];
+    for registration in registrations {
+        let expected = || format!("expected `{}` to look like `my-crate=iam,organizations` or `*=iam`", registration);
+        let (pattern, sdks) = registration.split_once('=').ok_or_else(expected)?;
+        let sdks: Vec<&str> = sdks.split(',').map(str::trim).filter(|s| !s.is_empty()).collect();
+        if pattern.trim().is_empty() || sdks.is_empty() {
+            return Err(expected());
+        }
+        if !matches_crate(pattern.trim(), crate_name) {
+            continue;
+        }
+        denied.extend(sdks.into_iter().map(|sdk| DeniedSdk {
+            sdk: sdk.to_string(),
+            registration: registration.to_string(),
+        }));
+    }
+    Ok(denied)
+}
+
+fn matches_crate(pattern: &str, crate_name: &str) -> bool {
+    match pattern.strip_suffix(CRATE_WILDCARD) {
+        Some(prefix) => crate_name.starts_with(prefix),
+        None => crate_name == pattern,
+    }
+}
+
+/// An error for every call of a denied SDK, and for clients of one that the function does not call (yet).
+/// `clients` are the SDKs of the clients of the function, with a description of where we found them
+pub(crate) fn find_denied_sdks(calls: &[SdkCall], clients: Vec<(String, String)>, denied: &[DeniedSdk], attribute: Span) -> Vec<UsageFinds> {
+    let mut findings = vec![];
+    for deny in denied {
+        let denied_calls: Vec<&SdkCall> = calls.iter().filter(|c| c.sdk == deny.sdk).collect();
+        findings.extend(denied_calls.iter().map(|call| {
+            UsageFinds::of_sdk(call.span, deny.sdk.clone(), Usage::DeniedSdk(DeniedSdkUsage {
+                found: format!("the call of `{}`", call.method),
+                registration: deny.registration.clone(),
+            }))
+        }));
+        if denied_calls.is_empty() {
+            findings.extend(clients.iter().filter(|(sdk, _)| *sdk == deny.sdk).map(|(_, description)| {
+                UsageFinds::of_sdk(attribute, deny.sdk.clone(), Usage::DeniedSdk(DeniedSdkUsage {
+                    found: description.clone(),
+                    registration: deny.registration.clone(),
+                }))
+            }));
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registered::{split_registrations, tracked_inputs};
+
+    #[test]
+    fn denied_sdks_make_the_expansion_depend_on_the_variable() {
+        let _ = denied_sdks();
+
+        assert!(tracked_inputs().to_string().contains("option_env ! (\"AWS_SDK_COMPILE_CHECKS_DENIED_SDKS\")"));
+    }
+
+    #[test]
+    fn parse_denied_sdks_for_the_crate() {
+        let actual = parse_denied_sdks(&split_registrations("orders-*=iam, organizations; *=account; billing=sts"), "orders-api").unwrap();
+
+        let sdks: Vec<&str> = actual.iter().map(|d| d.sdk.as_str()).collect();
+        assert_eq!(sdks, vec!["iam", "organizations", "account"]);
+        assert_eq!(actual[0].registration, "orders-*=iam, organizations");
+    }
+
+    #[test]
+    fn parse_denied_sdks_rejects_registrations_without_sdks() {
+        let actual = parse_denied_sdks(&split_registrations("orders-api="), "orders-api").unwrap_err();
+
+        assert_eq!(actual, "expected `orders-api=` to look like `my-crate=iam,organizations` or `*=iam`");
+    }
+
+    #[test]
+    fn find_denied_sdks_prefers_the_calls_over_the_clients() {
+        let denied = parse_denied_sdks(&split_registrations("*=iam,organizations"), "orders-api").unwrap();
+        let calls = vec![SdkCall {
+            span: Span::call_site(),
+            sdk: "iam".to_string(),
+            method: "create_user".to_string(),
+            operation: "create_user".to_string(),
+        }];
+        let clients = vec![
+            ("iam".to_string(), "parameter `iam: aws_sdk_iam::Client`".to_string()),
+            ("organizations".to_string(), "parameter `org: aws_sdk_organizations::Client`".to_string()),
+        ];
+
+        let actual: Vec<(String, String)> = find_denied_sdks(&calls, clients, &denied, Span::call_site())
+            .into_iter()
+            .map(|f| match f.usage {
+                Usage::DeniedSdk(d) => (f.sdk.unwrap_or_default(), d.found),
+                _ => panic!("expected only denied SDKs"),
+            })
+            .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                ("iam".to_string(), "the call of `create_user`".to_string()),
+                ("organizations".to_string(), "parameter `org: aws_sdk_organizations::Client`".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use proc_macro2::{Span, TokenStream};
+#[cfg(not(feature = "nightly-diagnostics"))]
+use quote::quote_spanned;
+
+use crate::registered::tracked_env_var;
+
+/// When set, every finding is a warning, for crates that are still adopting the checks. Problems with the configuration stay errors
+const MIGRATION_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_MIGRATION";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Level {
+    Error,
+    Warning,
+}
+
+/// A diagnostic with a level, notes and help, emitted with `proc_macro::Diagnostic` when the `nightly-diagnostics` feature is enabled.
+/// On stable, errors become a `compile_error!` and warnings a use of a deprecated constant, with the notes and help added to the message.
+#[derive(Debug)]
+pub(crate) struct Diagnostic {
+    pub(crate) level: Level,
+    pub(crate) span: Span,
+    pub(crate) code: Option<&'static str>,
+    pub(crate) message: String,
+    pub(crate) notes: Vec<String>,
+    pub(crate) help: Vec<String>,
+    pub(crate) suggestion: Option<Suggestion>,
+}
+
+/// An edit that fixes the problem, e.g. the attribute with an SDK added to it.
+/// Proc macros cannot hand these to rustc, so they only end up in the export (and the SARIF report made from it)
+#[derive(Debug)]
+pub(crate) struct Suggestion {
+    pub(crate) span: Span, // the code to replace
+    pub(crate) replacement: String,
+}
+
+impl Diagnostic {
+    pub(crate) fn error(span: Span, message: impl Into<String>) -> Self {
+        Self::new(Level::Error, span, message)
+    }
+
+    pub(crate) fn warning(span: Span, message: impl Into<String>) -> Self {
+        Self::new(Level::Warning, span, message)
+    }
+
+    fn new(level: Level, span: Span, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            span,
+            code: None,
+            message: message.into(),
+            notes: vec![],
+            help: vec![],
+            suggestion: None,
+        }
+    }
+
+    /// Identifies the kind of finding in exports, rustc itself does not show it
+    pub(crate) fn code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    pub(crate) fn note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub(crate) fn help(mut self, help: impl Into<String>) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    pub(crate) fn suggestion(mut self, span: Span, replacement: impl Into<String>) -> Self {
+        self.suggestion = Some(Suggestion {
+            span,
+            replacement: replacement.into(),
+        });
+        self
+    }
+
+    /// The same diagnostic as a warning, with a note saying why it is not an error
+    pub(crate) fn downgraded(self, reason: impl Into<String>) -> Self {
+        match self.level {
+            Level::Error => Self { level: Level::Warning, ..self }.note(reason),
+            Level::Warning => self,
+        }
+    }
+
+    /// The message with the notes and help appended, mimicking the way rustc shows them
+    #[cfg_attr(feature = "nightly-diagnostics", allow(dead_code))]
+    fn rendered_message(&self) -> String {
+        let notes = self.notes.iter().map(|n| format!("\n= note: {}", n));
+        let help = self.help.iter().map(|h| format!("\n= help: {}", h));
+        std::iter::once(self.message.clone()).chain(notes).chain(help).collect()
+    }
+
+    /// Emits the diagnostic. The returned tokens should be placed in statement position (i.e. inside the function body)
+    #[cfg(feature = "nightly-diagnostics")]
+    pub(crate) fn emit(self) -> TokenStream {
+        let level = match self.level {
+            Level::Error => proc_macro::Level::Error,
+            Level::Warning => proc_macro::Level::Warning,
+        };
+        let diagnostic = proc_macro::Diagnostic::spanned(self.span.unwrap(), level, self.message);
+        let diagnostic = self.notes.into_iter().fold(diagnostic, |acc, note| acc.note(note));
+        let diagnostic = self.help.into_iter().fold(diagnostic, |acc, help| acc.help(help));
+        diagnostic.emit();
+        TokenStream::new()
+    }
+
+    /// Emits the diagnostic. The returned tokens should be placed in statement position (i.e. inside the function body)
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    pub(crate) fn emit(self) -> TokenStream {
+        let message = self.rendered_message();
+        match self.level {
+            Level::Error => syn::Error::new(self.span, message).to_compile_error(),
+            Level::Warning => {
+                // stable has no way to emit warnings from a proc macro, but it does warn about the use of deprecated items
+                quote_spanned!(self.span=>
+                    {
+                        #[deprecated(note = #message)]
+                        #[allow(non_upper_case_globals)]
+                        const required_props: () = ();
+                        #[warn(deprecated)]
+                        let _ = required_props;
+                    }
+                )
+            }
+        }
+    }
+}
+
+/// Downgrades errors to warnings when `AWS_SDK_COMPILE_CHECKS_MIGRATION` is set
+pub(crate) fn apply_migration_mode(diagnostic: Diagnostic) -> Diagnostic {
+    match tracked_env_var(MIGRATION_ENV_VAR) {
+        Some(_) => diagnostic.downgraded(format!("this is an error when `{}` is not set", MIGRATION_ENV_VAR)),
+        None => diagnostic,
+    }
+}
+
+/// Keeps the first `max` errors (and all warnings), adding a note about the errors that are left out to the last error that is kept
+pub(crate) fn limit_errors(diagnostics: Vec<Diagnostic>, max: usize) -> Vec<Diagnostic> {
+    let errors = diagnostics.iter().filter(|d| d.level == Level::Error).count();
+    if errors <= max {
+        return diagnostics;
+    }
+    let mut kept = 0;
+    let mut limited: Vec<Diagnostic> = vec![];
+    for diagnostic in diagnostics {
+        if diagnostic.level == Level::Error {
+            kept += 1;
+            if kept > max {
+                continue;
+            }
+        }
+        limited.push(diagnostic);
+    }
+    if let Some(last_error) = limited.iter_mut().rev().find(|d| d.level == Level::Error) {
+        last_error
+            .notes
+            .push(format!("{} more error(s) in this function are not shown, because of `max_errors = {}`", errors - max, max));
+    }
+    limited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registered::tracked_inputs;
+
+    #[test]
+    fn rendered_message_without_notes_or_help_is_the_message() {
+        let diagnostic = Diagnostic::error(Span::call_site(), "something is wrong");
+
+        assert_eq!(diagnostic.rendered_message(), "something is wrong");
+    }
+
+    #[test]
+    fn rendered_message_adds_notes_before_help() {
+        let diagnostic = Diagnostic::warning(Span::call_site(), "something is wrong")
+            .help("fix it")
+            .note("this is why");
+
+        assert_eq!(diagnostic.rendered_message(), "something is wrong\n= note: this is why\n= help: fix it");
+    }
+
+    #[test]
+    fn apply_migration_mode_makes_the_expansion_depend_on_the_variable() {
+        let _ = apply_migration_mode(Diagnostic::error(Span::call_site(), "something is wrong"));
+
+        assert!(tracked_inputs().to_string().contains("option_env ! (\"AWS_SDK_COMPILE_CHECKS_MIGRATION\")"));
+    }
+
+    #[test]
+    fn limit_errors_keeps_the_first_errors_and_all_warnings() {
+        let diagnostics = vec![
+            Diagnostic::error(Span::call_site(), "first"),
+            Diagnostic::warning(Span::call_site(), "warning"),
+            Diagnostic::error(Span::call_site(), "second"),
+            Diagnostic::error(Span::call_site(), "third"),
+            Diagnostic::error(Span::call_site(), "fourth"),
+        ];
+
+        let actual = limit_errors(diagnostics, 2);
+
+        let messages: Vec<&str> = actual.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages, vec!["first", "warning", "second"]);
+        assert_eq!(actual[2].notes, vec!["2 more error(s) in this function are not shown, because of `max_errors = 2`"]);
+    }
+
+    #[test]
+    fn downgraded_error_is_a_warning_with_a_note() {
+        let diagnostic = Diagnostic::error(Span::call_site(), "something is wrong").code("missing-required-props").downgraded("the reason");
+
+        assert_eq!(diagnostic.level, Level::Warning);
+        assert_eq!(diagnostic.code, Some("missing-required-props"));
+        assert_eq!(diagnostic.notes, vec!["the reason"]);
+    }
+
+    #[test]
+    fn limit_errors_below_the_maximum_changes_nothing() {
+        let actual = limit_errors(vec![Diagnostic::error(Span::call_site(), "only")], 1);
+
+        assert!(actual[0].notes.is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::BTreeSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use aws_sdk_compile_checks_data::iam::iam_actions;
+use proc_macro2::Span;
+use serde::Serialize;
+
+use crate::diagnostics::{Diagnostic, Level};
+use crate::registered::tracked_env_var;
+use crate::visitor::SdkCall;
+
+/// When set, the findings of every crate are written to a file of its own in this directory as lines of JSON, see [`crate_file`]
+const EXPORT_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_EXPORT";
+/// When set, every call of an AWS SDK we could tell the SDK of is written to the file of its crate in this directory as a line of JSON,
+/// with the IAM actions it needs, so a policy can be generated from them
+const CALLS_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_CALLS";
+/// When set, every call of an AWS SDK is written to the file of its crate in this directory, e.g. `target/aws-operations/orders.orders.jsonl`,
+/// an inventory of the operations of every crate, whether the calls are fine or not
+const INVENTORY_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_INVENTORY";
+
+/// The files this compiler already replaced, see [`write_crate_file`]
+static REPLACED: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// A finding as a line of the export, which `aws-sdk-compile-checks-report` reads
+#[derive(Serialize)]
+struct ExportedFinding<'a> {
+    level: &'static str,
+    code: Option<&'static str>,
+    message: &'a str,
+    notes: &'a [String],
+    help: &'a [String],
+    file: String,
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+    suggestion: Option<ExportedSuggestion<'a>>,
+}
+
+/// A suggestion of a finding, in the same file as the finding, e.g. the attribute of its function
+#[derive(Serialize)]
+struct ExportedSuggestion<'a> {
+    replacement: &'a str,
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+#[derive(Serialize)]
+struct ExportedCall<'a> {
+    sdk: &'a str,
+    method: &'a str,
+    operation: &'a str,
+    actions: Vec<String>,
+    file: String,
+    line: usize,
+}
+
+#[derive(Serialize)]
+struct InventoryEntry<'a> {
+    #[serde(rename = "crate")]
+    crate_name: &'a str,
+    sdk: &'a str,
+    operation: &'a str,
+    method: &'a str,
+    file: String,
+    line: usize,
+}
+
+/// Writes the diagnostics to the export file of the crate, if the user asked for an export, also when there are none,
+/// so the findings that were fixed disappear from it. Returns a warning when the export failed, because silently skipping it would leave gaps in the report.
+pub(crate) fn export_findings(diagnostics: &[Diagnostic]) -> Option<Diagnostic> {
+    let directory = tracked_env_var(EXPORT_ENV_VAR)?;
+    let path = crate_file(Path::new(&directory));
+    let lines: String = diagnostics.iter().map(|d| format!("{}\n", to_json_line(d))).collect();
+
+    match write_crate_file(&path, &lines) {
+        Ok(_) => None,
+        Err(e) => Some(Diagnostic::warning(Span::call_site(), format!("could not export findings to {}: {}", path.display(), e))),
+    }
+}
+
+/// Writes the calls to the calls file of the crate, if the user asked for one. Like `export_findings`, returns a warning when that failed
+pub(crate) fn export_calls(calls: &[SdkCall]) -> Option<Diagnostic> {
+    let directory = tracked_env_var(CALLS_ENV_VAR)?;
+    let path = crate_file(Path::new(&directory));
+    let lines: String = calls.iter().map(|c| format!("{}\n", call_to_json_line(c))).collect();
+
+    match write_crate_file(&path, &lines) {
+        Ok(_) => None,
+        Err(e) => Some(Diagnostic::warning(Span::call_site(), format!("could not export calls to {}: {}", path.display(), e))),
+    }
+}
+
+/// Writes the calls to the inventory of the crate that is being compiled, if the user asked for one
+pub(crate) fn export_inventory(calls: &[SdkCall]) -> Option<Diagnostic> {
+    let directory = tracked_env_var(INVENTORY_ENV_VAR)?;
+    let crate_name = std::env::var("CARGO_PKG_NAME").unwrap_or_else(|_| "unknown".to_string());
+    let path = crate_file(Path::new(&directory));
+    let lines: String = calls.iter().map(|c| format!("{}\n", inventory_json_line(&crate_name, c))).collect();
+
+    match write_crate_file(&path, &lines) {
+        Ok(_) => None,
+        Err(e) => Some(Diagnostic::warning(
+            Span::call_site(),
+            format!("could not add calls to the inventory at {}: {}", path.display(), e),
+        )),
+    }
+}
+
+/// The file of the crate that is being compiled in an export directory, e.g. `orders-api.worker.jsonl` for the `worker` binary of `orders-api`.
+/// Every crate of a package (the library, its binaries and tests) has a file of its own, because each of them is compiled on its own
+fn crate_file(directory: &Path) -> PathBuf {
+    let package = std::env::var("CARGO_PKG_NAME").unwrap_or_else(|_| "unknown".to_string());
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| package.replace('-', "_"));
+    directory.join(format!("{}.{}.jsonl", package, crate_name))
+}
+
+/// Replaces the file for the first annotated function of the crate, and appends to it for the others.
+/// The macro runs every time the crate is compiled, so the file has the lines of the latest compilation, and none of an earlier one
+fn write_crate_file(path: &Path, lines: &str) -> std::io::Result<()> {
+    let mut replaced = REPLACED.lock().unwrap_or_else(|e| e.into_inner());
+    let first = !replaced.contains(path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().create(true).write(true).truncate(first).append(!first).open(path)?.write_all(lines.as_bytes())?;
+    replaced.insert(path.to_path_buf());
+    Ok(())
+}
+
+fn to_json_line(diagnostic: &Diagnostic) -> String {
+    let start = diagnostic.span.start();
+    let end = diagnostic.span.end();
+    // columns are zero-based in proc_macro2, but one-based in rustc output (and SARIF)
+    let finding = ExportedFinding {
+        level: match diagnostic.level {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        },
+        code: diagnostic.code,
+        message: &diagnostic.message,
+        notes: &diagnostic.notes,
+        help: &diagnostic.help,
+        file: file_of(diagnostic.span),
+        start_line: start.line,
+        start_column: start.column + 1,
+        end_line: end.line,
+        end_column: end.column + 1,
+        suggestion: diagnostic.suggestion.as_ref().map(|suggestion| ExportedSuggestion {
+            replacement: &suggestion.replacement,
+            start_line: suggestion.span.start().line,
+            start_column: suggestion.span.start().column + 1,
+            end_line: suggestion.span.end().line,
+            end_column: suggestion.span.end().column + 1,
+        }),
+    };
+    serde_json::to_string(&finding).expect("a finding only has strings and numbers")
+}
+
+fn call_to_json_line(call: &SdkCall) -> String {
+    let call = ExportedCall {
+        sdk: &call.sdk,
+        method: &call.method,
+        operation: &call.operation,
+        actions: iam_actions(&call.sdk, &call.operation),
+        file: file_of(call.span),
+        line: call.span.start().line,
+    };
+    serde_json::to_string(&call).expect("a call only has strings and numbers")
+}
+
+fn inventory_json_line(crate_name: &str, call: &SdkCall) -> String {
+    let entry = InventoryEntry {
+        crate_name,
+        sdk: &call.sdk,
+        operation: &call.operation,
+        method: &call.method,
+        file: file_of(call.span),
+        line: call.span.start().line,
+    };
+    serde_json::to_string(&entry).expect("an entry of the inventory only has strings and numbers")
+}
+
+pub(crate) fn file_of(span: Span) -> String {
+    span.local_file().map(|f| f.to_string_lossy().to_string()).unwrap_or_else(|| span.file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_json_line_contains_level_code_message_and_notes() {
+        let diagnostic = Diagnostic::error(Span::call_site(), "method `send_message` is missing `queue_url`")
+            .code("missing-required-props")
+            .note("a note");
+
+        let actual = to_json_line(&diagnostic);
+
+        assert!(actual.starts_with(
+            r#"{"level":"error","code":"missing-required-props","message":"method `send_message` is missing `queue_url`","notes":["a note"],"help":[],"file":"#
+        ));
+    }
+
+    #[test]
+    fn to_json_line_ends_with_the_suggestion() {
+        let diagnostic = Diagnostic::error(Span::call_site(), "method `create_app` is used in multiple SDKs")
+            .suggestion(Span::call_site(), "#[required_props(sdk = amplify)]");
+
+        let actual = to_json_line(&diagnostic);
+
+        assert!(actual.ends_with(r##","suggestion":{"replacement":"#[required_props(sdk = amplify)]","start_line":1,"start_column":1,"end_line":1,"end_column":1}}"##));
+        assert!(to_json_line(&Diagnostic::error(Span::call_site(), "a problem")).ends_with(r#","suggestion":null}"#));
+    }
+
+    #[test]
+    fn call_to_json_line_contains_the_operation_and_its_actions() {
+        let call = SdkCall {
+            span: Span::call_site(),
+            sdk: "dynamodb".to_string(),
+            method: "wait_until_table_exists".to_string(),
+            operation: "describe_table".to_string(),
+        };
+
+        let actual = call_to_json_line(&call);
+
+        assert!(actual.starts_with(
+            r#"{"sdk":"dynamodb","method":"wait_until_table_exists","operation":"describe_table","actions":["dynamodb:DescribeTable"],"file":"#
+        ));
+    }
+
+    #[test]
+    fn inventory_json_line_contains_the_crate_and_operation() {
+        let call = SdkCall {
+            span: Span::call_site(),
+            sdk: "sqs".to_string(),
+            method: "send_message".to_string(),
+            operation: "send_message".to_string(),
+        };
+
+        let actual = inventory_json_line("orders", &call);
+
+        assert!(actual.starts_with(r#"{"crate":"orders","sdk":"sqs","operation":"send_message","method":"send_message","file":"#));
+    }
+}
@@ -1,51 +1,1674 @@
-use proc_macro2::{Span, TokenStream};
+use aws_sdk_compile_checks_data::conditions::ConditionallyRequired;
+use aws_sdk_compile_checks_data::deprecations::Deprecation;
+use aws_sdk_compile_checks_data::exceptions::{Exception, Requirement};
+use aws_sdk_compile_checks_data::policies::map_key;
+use aws_sdk_compile_checks_data::recommendations::Recommended;
+use aws_sdk_compile_checks_data::required_properties::{alternatives, data_version, data_version_of};
+use proc_macro2::{LineColumn, Span};
+
+use crate::attributes::{OptInWarning, Policy};
+use crate::diagnostics::Diagnostic;
+use crate::registered::tracked_env_var;
+use crate::versions::version_notes;
 
 const COMMA_WITH_SPACE: &str = ", ";
+const MAX_SDKS_TO_SHOW: usize = 5;
+/// When set, ambiguous SDK errors list every candidate SDK instead of only the first few
+const FULL_SDK_LIST_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_FULL_SDK_LIST";
 
+/// A problem in the function, with what every kind of problem has: where it is, the SDK it is about (when there is one)
+/// and the other places in the function with the exact same problem
 #[derive(Debug)]
-pub(crate) enum UsageFinds {
+pub(crate) struct UsageFinds {
+    pub(crate) span: Span,
+    pub(crate) sdk: Option<String>,
+    pub(crate) usage: Usage,
+    pub(crate) duplicates: Vec<Span>,
+}
+
+#[derive(Debug)]
+pub(crate) enum Usage {
     Improper(ImproperUsage),
     Unknown(UnknownUsage),
+    Conditional(ConditionalUsage),
+    Deprecated(DeprecatedUsage),
+    Unawaited(UnawaitedUsage),
+    Empty(EmptyUsage),
+    Exclusive(ExclusiveUsage),
+    Unwrapped(UnwrappedUsage),
+    Unpaginated(UnpaginatedUsage),
+    Credentials(CredentialsUsage),
+    MalformedArn(MalformedArnUsage),
+    OutOfRange(OutOfRangeUsage),
+    QueueUrl(QueueUrlUsage),
+    BucketName(BucketNameUsage),
+    LeadingSlashKey(LeadingSlashKeyUsage),
+    DuplicateSetter(DuplicateSetterUsage),
+    UnknownRegion(UnknownRegionUsage),
+    Policy(PolicyUsage),
+    AboveMaximum(AboveMaximumUsage),
+    Batch(BatchUsage),
+    Sequential(SequentialUsage),
+    Recommended(RecommendedUsage),
+    Iam(IamUsage),
+    Unavailable(UnavailableUsage),
+    UnknownResource(UnknownResourceUsage),
+    DeniedSdk(DeniedSdkUsage),
+    UnusedSdk(UnusedSdkUsage),
+    UnselectedSdk(UnselectedSdkUsage),
+    Builder(BuilderUsage),
+    HardcodedResource(HardcodedResourceUsage),
+    Untimed(UntimedUsage),
+    Idempotency(IdempotencyUsage),
+    Undeleted(UndeletedUsage),
 }
 
 #[derive(Debug)]
 pub(crate) struct UnknownUsage {
-    pub(crate) span: Span,
     pub(crate) method: String,
+    pub(crate) receiver: Option<String>,
     pub(crate) sdks: Vec<String>,
+    pub(crate) suggested_sdk: Option<String>, // the only one of the SDKs that the clients or other calls of the function belong to
 }
 
 #[derive(Debug)]
 pub(crate) struct ImproperUsage {
-    pub(crate) span: Span,
     pub(crate) method: String,
     pub(crate) missing: Vec<String>,
-    pub(crate) sdk: String,
+    pub(crate) inferred_from: Inference,
+    pub(crate) terminator: &'static str, // the call that fails at runtime when properties are missing, e.g. `send`
+    pub(crate) consequence: Consequence,
+    pub(crate) docs_url: Option<&'static str>,
+    pub(crate) conditional: Vec<ConditionallyRequired>, // missing properties that are only required in some situations
+    pub(crate) exceptions: Vec<Exception>, // corrections of the documentation that changed what this call has to set
 }
 
-impl UsageFinds {
-    pub fn into_compile_error(self) -> TokenStream {
+/// A call that does not set properties that are not required, but recommended, e.g. the `content_type` of S3 `put_object`
+#[derive(Debug)]
+pub(crate) struct RecommendedUsage {
+    pub(crate) method: String,
+    pub(crate) missing: Vec<Recommended>,
+    pub(crate) terminator: &'static str,
+}
+
+/// A call that has all the properties that are always required, but misses some that are required in some situations
+#[derive(Debug)]
+pub(crate) struct ConditionalUsage {
+    pub(crate) method: String,
+    pub(crate) missing: Vec<ConditionallyRequired>,
+}
+
+/// A call of a deprecated operation, or a deprecated property of an operation
+#[derive(Debug)]
+pub(crate) struct DeprecatedUsage {
+    pub(crate) method: String,
+    pub(crate) deprecation: Deprecation,
+}
+
+/// A call whose future is never awaited, so the request is never sent
+#[derive(Debug)]
+pub(crate) struct UnawaitedUsage {
+    pub(crate) method: String,
+}
+
+/// A call whose result is unwrapped, which panics when AWS returns an error
+#[derive(Debug)]
+pub(crate) struct UnwrappedUsage {
+    pub(crate) method: String,
+    pub(crate) unwrap: String,
+}
+
+/// A call that is awaited without a timeout, while no config in the function sets one
+#[derive(Debug)]
+pub(crate) struct UntimedUsage {
+    pub(crate) method: String,
+}
+
+/// A call that is awaited right after another one, although it does not use the result of that call
+#[derive(Debug)]
+pub(crate) struct SequentialUsage {
+    pub(crate) method: String,
+    pub(crate) previous: String, // the operation that is awaited first
+}
+
+/// A call of a paginated operation with `send()`, in a function that never looks at the token for the next page
+#[derive(Debug)]
+pub(crate) struct UnpaginatedUsage {
+    pub(crate) method: String,
+}
+
+/// A call of an operation that accepts an idempotency token, without setting one
+#[derive(Debug)]
+pub(crate) struct IdempotencyUsage {
+    pub(crate) method: String,
+    pub(crate) token: &'static str, // the setter of the token, e.g. `client_token`
+}
+
+/// An SQS `receive_message` in a function that never deletes a message, nor passes on its receipt handle
+#[derive(Debug)]
+pub(crate) struct UndeletedUsage;
+
+/// A call inside a loop of an operation that has a batch variant, e.g. `send_message` instead of `send_message_batch`
+#[derive(Debug)]
+pub(crate) struct BatchUsage {
+    pub(crate) method: String,
+    pub(crate) batch: &'static str,
+}
+
+/// Credentials written in the code, which end up in version control and in the binary
+#[derive(Debug)]
+pub(crate) struct CredentialsUsage {
+    pub(crate) credential: Credential,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Credential {
+    AccessKeyId,
+    SecretAccessKey,
+}
+
+/// A string literal passed to a setter that takes an ARN, which is obviously not an ARN
+#[derive(Debug)]
+pub(crate) struct MalformedArnUsage {
+    pub(crate) method: String,
+    pub(crate) property: String,
+    pub(crate) problem: String,
+}
+
+/// An integer literal passed to a setter of a property that only accepts values in a range, e.g. `.delay_seconds(1000)`
+#[derive(Debug)]
+pub(crate) struct OutOfRangeUsage {
+    pub(crate) method: String,
+    pub(crate) property: &'static str,
+    pub(crate) value: i64,
+    pub(crate) min: i64,
+    pub(crate) max: i64,
+}
+
+/// A string literal passed to `queue_url` that does not look like the URL of a queue, e.g. `.queue_url("my-queue")`
+#[derive(Debug)]
+pub(crate) struct QueueUrlUsage {
+    pub(crate) method: String,
+    pub(crate) problem: String,
+}
+
+/// A string literal passed to `bucket` of an S3 operation, which breaks the naming rules of S3, e.g. `.bucket("My_Bucket")`
+#[derive(Debug)]
+pub(crate) struct BucketNameUsage {
+    pub(crate) method: String,
+    pub(crate) problem: String,
+}
+
+/// A string literal passed to `key` of an S3 object operation that starts with a `/`, e.g. `.key("/folder/file.txt")`
+#[derive(Debug)]
+pub(crate) struct LeadingSlashKeyUsage {
+    pub(crate) method: String,
+}
+
+/// A setter that is called more than once in the same chain, e.g. `.queue_url(a).queue_url(b)`, of which only the last value is kept
+#[derive(Debug)]
+pub(crate) struct DuplicateSetterUsage {
+    pub(crate) method: String,
+    pub(crate) property: String,
+}
+
+/// A call that does not set the properties a policy of the attribute requires, e.g. S3 `put_object` without encryption for `policy = encryption`
+#[derive(Debug)]
+pub(crate) struct PolicyUsage {
+    pub(crate) method: String,
+    pub(crate) policy: Policy,
+    pub(crate) missing: Vec<&'static str>, // like required properties, these can be 'at least one of' groups
+    pub(crate) terminator: &'static str,
+}
+
+/// A string literal passed to a setter of a resource, e.g. `.table_name("orders")`, see `OptInWarning::HardcodedResource`
+#[derive(Debug)]
+pub(crate) struct HardcodedResourceUsage {
+    pub(crate) setter: String,
+    pub(crate) value: String,
+}
+
+/// A chain of one of your own builders that is missing methods required by `AWS_SDK_COMPILE_CHECKS_BUILDERS`
+#[derive(Debug)]
+pub(crate) struct BuilderUsage {
+    pub(crate) owner: &'static str,
+    pub(crate) method: &'static str,
+    pub(crate) missing: Vec<&'static str>, // like required properties, these can be 'at least one of' groups
+    pub(crate) terminator: &'static str,
+    pub(crate) file: &'static str,
+}
+
+/// A call that needs an IAM action the policies in `AWS_SDK_COMPILE_CHECKS_IAM_POLICIES` do not allow, e.g. `sqs:SendMessage` for `send_message`
+#[derive(Debug)]
+pub(crate) struct IamUsage {
+    pub(crate) method: String,
+    pub(crate) action: String, // like required properties, this can be an 'at least one of' group
+    pub(crate) policies: &'static [String], // the files of the policies
+}
+
+/// A call of a service (or operation) that is not available in the region the code runs in, e.g. App Runner in `eu-south-1`
+#[derive(Debug)]
+pub(crate) struct UnavailableUsage {
+    pub(crate) method: String,
+    pub(crate) region: String,
+    pub(crate) source: String, // where the region comes from, e.g. ``.region("eu-south-1")``
+    pub(crate) available: Vec<&'static str>,
+}
+
+/// A string literal passed as a queue URL, table name or bucket that is not in the outputs of `AWS_SDK_COMPILE_CHECKS_RESOURCES`
+#[derive(Debug)]
+pub(crate) struct UnknownResourceUsage {
+    pub(crate) setter: String,
+    pub(crate) value: String,
+    pub(crate) files: &'static [String], // the output files of the infrastructure as code
+}
+
+/// A call (or client) of an SDK that `AWS_SDK_COMPILE_CHECKS_DENIED_SDKS` denies to the crate, e.g. `iam` in an application crate
+#[derive(Debug)]
+pub(crate) struct DeniedSdkUsage {
+    pub(crate) found: String, // what uses the SDK, e.g. the call of `create_user`
+    pub(crate) registration: String,
+}
+
+/// An SDK in the `sdk` attribute that no call or client of the function matches, e.g. after copy-pasting the attribute
+#[derive(Debug)]
+pub(crate) struct UnusedSdkUsage {
+    pub(crate) function: String,
+}
+
+/// A call on a client of an SDK that the `sdk` attribute does not list, e.g. `put_item` on a DynamoDB client with `sdk = sqs, sns`
+#[derive(Debug)]
+pub(crate) struct UnselectedSdkUsage {
+    pub(crate) method: String,
+    pub(crate) client: String, // how we know the SDK, e.g. "parameter `dynamodb: aws_sdk_dynamodb::Client`"
+    pub(crate) selected: Vec<String>, // the SDKs of the attribute
+}
+
+/// An integer literal above the maximum the attribute sets for its setter, e.g. `.read_capacity_units(5000)` with `max(read_capacity_units = 100)`
+#[derive(Debug)]
+pub(crate) struct AboveMaximumUsage {
+    pub(crate) setter: String,
+    pub(crate) value: i64,
+    pub(crate) max: i64,
+}
+
+/// A string literal passed to a setter that takes a region, which is not a region AWS has, e.g. `.region("eu-west1")`
+#[derive(Debug)]
+pub(crate) struct UnknownRegionUsage {
+    pub(crate) setter: String,
+    pub(crate) region: String,
+    pub(crate) suggestion: Option<&'static str>,
+}
+
+/// A required property that is set, but only to an empty (or whitespace-only) string literal, e.g. `.queue_url("")`
+#[derive(Debug)]
+pub(crate) struct EmptyUsage {
+    pub(crate) method: String,
+    pub(crate) property: String,
+}
+
+/// A call that sets several properties of a group of which the service accepts at most one
+#[derive(Debug)]
+pub(crate) struct ExclusiveUsage {
+    pub(crate) method: String,
+    pub(crate) properties: Vec<&'static str>, // those of the group that are set
+    pub(crate) reason: &'static str,
+}
+
+/// What happens at runtime when the properties the SDK requires are missing, as far as we know
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Consequence {
+    Rejected,         // required by the model of the service: AWS rejects the request, or the SDK cannot build it when the property is part of its URI
+    BuildError,       // required members of a type, which its `build()` checks
+    ClientPanics,     // a config without a behavior version, which the client panics on
+    PresigningError,  // a `PresigningConfig` without an expiry, which its `build()` checks
+    Unknown,          // e.g. the properties of a dataset of your own, which might be required for reasons of your own
+}
+
+/// How we determined the SDK of a call
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Inference {
+    OnlySdkWithMethod,
+    SameRequiredProps,
+    Attribute,
+    AttributeAndReceiver(String), // name of the receiver that was used as a tie-breaker
+    Client(String),               // description of the client
+    Path(String),                 // path of a type that includes the SDK crate, e.g. `aws_sdk_dynamodb::types::Replica`
+    BuilderType(String),          // fluent builder type of a local or parameter, e.g. `SendMessageFluentBuilder`
+    Presigned(String),            // operation that is presigned in the same function, e.g. `get_object`
+    Assumed,                      // nothing else told us, so we used the SDK of `assume_sdk`
+}
+
+impl Inference {
+    fn describe(&self, sdk: &str, method: &str) -> String {
         match self {
-            UsageFinds::Improper(improper) => {
-                let missing = improper.missing.into_iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(COMMA_WITH_SPACE);
-                let message = format!("method `{}` (from {}) is missing required argument(s): {}", improper.method, improper.sdk, missing);
-                syn::Error::new(improper.span, message)
-                    .to_compile_error()
+            Inference::OnlySdkWithMethod => format!("`{}` is the only SDK with a method `{}`", sdk, method),
+            Inference::SameRequiredProps => format!("all SDKs with a method `{}` ({}) require the same properties", method, sdk),
+            Inference::Attribute => format!("SDK `{}` was selected with the `sdk` attribute", sdk),
+            Inference::AttributeAndReceiver(receiver) => {
+                format!("SDK `{}` was selected with the `sdk` attribute, using the name of receiver `{}`", sdk, receiver)
+            }
+            Inference::Client(client) => format!("SDK `{}` inferred from {}", sdk, client),
+            Inference::Path(path) => format!("SDK `{}` inferred from the path `{}`", sdk, path),
+            Inference::BuilderType(builder) => format!("SDK `{}` inferred from the builder type `{}`", sdk, builder),
+            Inference::Presigned(operation) => format!("SDK `{}` inferred from the presigned call of `{}`", sdk, operation),
+            Inference::Assumed => format!("SDK `{}` was assumed with the `assume_sdk` attribute, because nothing else tells us the SDK of `{}`", sdk, method),
+        }
+    }
+}
+
+impl UsageFinds {
+    pub(crate) fn new(span: Span, usage: Usage) -> Self {
+        Self {
+            span,
+            sdk: None,
+            usage,
+            duplicates: vec![],
+        }
+    }
+
+    /// A finding about a call (or client) of `sdk`, or of one of the SDKs of a list like `sqs,sns` when they require the same properties
+    pub(crate) fn of_sdk(span: Span, sdk: impl Into<String>, usage: Usage) -> Self {
+        Self {
+            sdk: Some(sdk.into()),
+            ..Self::new(span, usage)
+        }
+    }
+
+    /// The SDK to add to the attribute to fix this finding, when we are fairly sure which one it is
+    pub(crate) fn suggested_sdk(&self) -> Option<&str> {
+        match &self.usage {
+            Usage::Unknown(unknown) => unknown.suggested_sdk.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Recommendations are only reported with `recommend = true`, instead of with a kind of warning
+    pub(crate) fn is_recommendation(&self) -> bool {
+        matches!(self.usage, Usage::Recommended(_))
+    }
+
+    /// The warning the user has to ask for before we report this finding, if any
+    pub(crate) fn opt_in_warning(&self) -> Option<OptInWarning> {
+        match self.usage {
+            Usage::Improper(_)
+            | Usage::Unknown(_)
+            | Usage::Exclusive(_)
+            | Usage::Credentials(_)
+            | Usage::MalformedArn(_)
+            | Usage::OutOfRange(_)
+            | Usage::BucketName(_)
+            | Usage::UnknownRegion(_)
+            | Usage::Policy(_)
+            | Usage::AboveMaximum(_)
+            | Usage::Recommended(_)
+            | Usage::Iam(_)
+            | Usage::Unavailable(_)
+            | Usage::UnknownResource(_)
+            | Usage::DeniedSdk(_)
+            | Usage::UnselectedSdk(_)
+            | Usage::Builder(_) => None,
+            Usage::Conditional(_) => Some(OptInWarning::Conditional),
+            Usage::Deprecated(_) => Some(OptInWarning::Deprecated),
+            Usage::Unawaited(_) => Some(OptInWarning::Unawaited),
+            Usage::Empty(_) => Some(OptInWarning::Empty),
+            Usage::Unwrapped(_) => Some(OptInWarning::Unwrap),
+            Usage::Unpaginated(_) => Some(OptInWarning::Pagination),
+            Usage::Batch(_) => Some(OptInWarning::Batch),
+            Usage::Sequential(_) => Some(OptInWarning::Concurrent),
+            Usage::QueueUrl(_) => Some(OptInWarning::QueueUrl),
+            Usage::LeadingSlashKey(_) => Some(OptInWarning::S3Key),
+            Usage::DuplicateSetter(_) => Some(OptInWarning::Duplicate),
+            Usage::UnusedSdk(_) => Some(OptInWarning::UnusedSdk),
+            Usage::HardcodedResource(_) => Some(OptInWarning::HardcodedResource),
+            Usage::Untimed(_) => Some(OptInWarning::Timeout),
+            Usage::Idempotency(_) => Some(OptInWarning::Idempotency),
+            Usage::Undeleted(_) => Some(OptInWarning::SqsDelete),
+        }
+    }
+
+    fn is_same_problem(&self, other: &UsageFinds) -> bool {
+        self.sdk == other.sdk && self.usage.is_same_problem(&other.usage)
+    }
+
+    pub fn into_diagnostic(self) -> Diagnostic {
+        let UsageFinds { span, sdk, usage, duplicates } = self;
+        let checked_against = match (usage.is_checked_against_data(), &sdk) {
+            (true, Some(sdk)) => Some(data_version_of(sdk)),
+            (true, None) => Some(data_version()),
+            (false, _) => None,
+        };
+        let sdk = sdk.unwrap_or_default();
+        let diagnostic = match usage {
+            Usage::Improper(mut improper) => {
+                let version_notes = version_notes(&sdk, &improper.method, &improper.missing);
+                // properties our data requires, but the pinned version of the SDK does not, are left out, unless nothing else is missing
+                let optional_in_pinned_version: Vec<&str> = version_notes.iter().filter(|n| !n.required).map(|n| n.property).collect();
+                let only_optional_in_pinned_version = improper.missing.iter().all(|m| optional_in_pinned_version.contains(&m.as_str()));
+                if !only_optional_in_pinned_version {
+                    improper.missing.retain(|m| !optional_in_pinned_version.contains(&m.as_str()));
+                }
+                let fix = fix_snippet(&improper.missing, improper.terminator);
+                let inferred_from = improper.inferred_from.describe(&sdk, &improper.method);
+                let missing = improper.missing.iter().map(|m| describe_required(m, property)).collect::<Vec<_>>().join(COMMA_WITH_SPACE);
+                let message = format!("method `{}` (from {}) is missing required argument(s): {}", improper.method, sdk, missing);
+                let diagnostic = Diagnostic::error(span, message)
+                    .code("missing-required-props")
+                    .note(inferred_from);
+                let diagnostic = match improper.docs_url {
+                    Some(docs_url) => diagnostic.note(format!("see {}", docs_url)),
+                    None => diagnostic,
+                };
+                let diagnostic = add_duplicates_note(diagnostic, &duplicates);
+                let diagnostic = match only_optional_in_pinned_version {
+                    true => diagnostic.downgraded("the version of the SDK you are using does not require them"),
+                    false => diagnostic,
+                };
+                version_notes
+                    .into_iter()
+                    .map(|n| n.message)
+                    .chain(improper.conditional.iter().map(|c| format!("`{}` is also required when {}", c.property, c.condition)))
+                    .chain(improper.exceptions.iter().map(describe_exception))
+                    .chain(runtime_failure(&improper))
+                    .fold(diagnostic, |acc, note| acc.note(note))
+                    .help(fix)
             }
-            UsageFinds::Unknown(mut unknown) => {
+            Usage::Unknown(mut unknown) => {
                 unknown.sdks.sort(); // to have a deterministic output
-                let sdks_to_show = if unknown.sdks.len() <= 5 {
-                    unknown.sdks.join(COMMA_WITH_SPACE)
+                let full_sdk_list = tracked_env_var(FULL_SDK_LIST_ENV_VAR).is_some();
+                let sdks_to_show = sdk_list(&unknown.sdks, full_sdk_list);
+                let first_sdk_option = unknown.suggested_sdk.as_deref().or(unknown.sdks.first().map(|s| s.as_ref())).unwrap_or("sqs");
+                let message = format!("method `{}` is used in multiple SDKs: {}", unknown.method, sdks_to_show);
+                let diagnostic = Diagnostic::error(span, message)
+                    .code("ambiguous-sdk")
+                    .note(format!("the required properties of `{}` differ between these SDKs, so we need to know which one you are using", unknown.method));
+                let diagnostic = if !full_sdk_list && unknown.sdks.len() > MAX_SDKS_TO_SHOW {
+                    diagnostic.note(format!("set `{}=1` to see all {} SDKs", FULL_SDK_LIST_ENV_VAR, unknown.sdks.len()))
                 } else {
-                    format!("{}... (abbreviated list)", unknown.sdks[0..5].join(COMMA_WITH_SPACE))
+                    diagnostic
+                };
+                let diagnostic = add_duplicates_note(diagnostic, &duplicates);
+                let diagnostic = match &unknown.suggested_sdk {
+                    Some(sdk) => diagnostic.help(format!(
+                        "`{}` is the only one of these SDKs that the clients or other calls of this function belong to, add `sdk = {}` to the attribute",
+                        sdk, sdk
+                    )),
+                    None => diagnostic.help(format!("add the right one(s) to the attribute, e.g. `#[required_props(sdk = {})]`", first_sdk_option)),
+                };
+                match unknown.receiver {
+                    Some(receiver) => diagnostic.help(format!(
+                        "or map the receiver to its SDK, e.g. `#[required_props(clients({} = {}))]`",
+                        receiver, first_sdk_option
+                    )),
+                    None => diagnostic,
+                }
+            }
+            Usage::Conditional(conditional) => {
+                let properties: Vec<String> = conditional.missing.iter().map(|c| c.property.to_string()).collect();
+                let fix = fix_snippet(&properties, "send");
+                let missing = properties.iter().map(|p| format!("`{}`", p)).collect::<Vec<_>>().join(COMMA_WITH_SPACE);
+                let message = format!("method `{}` (from {}) might be missing argument(s): {}", conditional.method, sdk, missing);
+                let diagnostic = Diagnostic::warning(span, message).code("conditionally-required-props");
+                let diagnostic = conditional
+                    .missing
+                    .iter()
+                    .fold(diagnostic, |acc, c| acc.note(format!("`{}` is required when {}", c.property, c.condition)));
+                add_duplicates_note(diagnostic, &duplicates).help(format!("if that is the case, {}", fix))
+            }
+            Usage::Recommended(recommended) => {
+                let properties: Vec<String> = recommended.missing.iter().map(|r| r.property.to_string()).collect();
+                let fix = fix_snippet(&properties, recommended.terminator);
+                let missing = properties.iter().map(|p| format!("`{}`", p)).collect::<Vec<_>>().join(COMMA_WITH_SPACE);
+                let message = format!("method `{}` (from {}) does not set recommended argument(s): {}", recommended.method, sdk, missing);
+                let diagnostic = Diagnostic::warning(span, message).code("recommended-props");
+                let diagnostic = recommended
+                    .missing
+                    .iter()
+                    .fold(diagnostic, |acc, r| acc.note(format!("`{}` is recommended: {}", r.property, r.reason)));
+                add_duplicates_note(diagnostic, &duplicates)
+                    .note("these are not required, the call succeeds without them")
+                    .help(format!("unless you have a reason not to, {}", fix))
+            }
+            Usage::Deprecated(deprecated) => {
+                let message = match deprecated.deprecation.property {
+                    Some(property) => format!("property `{}` of method `{}` (from {}) is deprecated", property, deprecated.method, sdk),
+                    None => format!("method `{}` (from {}) is deprecated", deprecated.method, sdk),
+                };
+                let diagnostic = add_duplicates_note(Diagnostic::warning(span, message).code("deprecated"), &duplicates);
+                match deprecated.deprecation.note {
+                    Some(note) => diagnostic.help(note),
+                    None => diagnostic,
+                }
+            }
+            Usage::Unawaited(unawaited) => {
+                let message = format!("the future of `{}` is never awaited, so the request is never sent", unawaited.method);
+                add_duplicates_note(Diagnostic::warning(span, message).code("unawaited-send"), &duplicates)
+                    .note("futures do nothing unless they are awaited (or passed on to something that does)")
+                    .help("add `.await` after `.send()`")
+            }
+            Usage::Empty(empty) => {
+                let message = format!("required argument `{}` of method `{}` (from {}) is an empty string", empty.property, empty.method, sdk);
+                add_duplicates_note(Diagnostic::warning(span, message).code("empty-required-prop"), &duplicates)
+                    .note("the check only sees that the property is set, the service will still reject the call because the value is empty")
+                    .help(format!("pass a non-empty value to `.{}(...)`", empty.property))
+            }
+            Usage::Unwrapped(unwrapped) => {
+                let message = format!("the result of `{}` is unwrapped with `{}`, which panics when the call fails", unwrapped.method, unwrapped.unwrap);
+                let call = if unwrapped.unwrap == "unwrap" { "unwrap()" } else { "expect(...)" };
+                add_duplicates_note(Diagnostic::warning(span, message).code("unwrapped-send"), &duplicates)
+                    .note("calls to AWS can fail for reasons outside of your control, like throttling or missing permissions")
+                    .help(format!("return the error with `?` instead of `.{}`, or handle it explicitly", call))
+            }
+            Usage::Unpaginated(unpaginated) => {
+                let message = format!(
+                    "method `{}` (from {}) only returns the first page of results, and the token for the next page is never used",
+                    unpaginated.method, sdk
+                );
+                add_duplicates_note(Diagnostic::warning(span, message).code("unpaginated-call"), &duplicates)
+                    .note("the operation is paginated, so results beyond the first page are silently missing")
+                    .help("use `.into_paginator()` instead of `.send()` to go through all the pages")
+            }
+            Usage::Credentials(credentials) => {
+                // never repeat the value, the diagnostic can end up in logs and exported reports
+                let credential = match credentials.credential {
+                    Credential::AccessKeyId => "an AWS access key id",
+                    Credential::SecretAccessKey => "an AWS secret access key",
                 };
-                let first_sdk_option = unknown.sdks.first()
-                    .map(|s| s.as_ref())
-                    .unwrap_or_else(|| "sqs");
-                let message = format!("method `{}` is used in multiple SDKs: {}. Please add the right one(s) to the attribute, e.g. `#[required_props(sdk = {})]`", unknown.method, sdks_to_show, first_sdk_option);
-                syn::Error::new(unknown.span, message)
-                    .to_compile_error()
+                add_duplicates_note(Diagnostic::error(span, format!("this literal looks like {}", credential)).code("hardcoded-credentials"), &duplicates)
+                    .note("credentials in the code end up in version control and in the binary, where anyone with access can use them")
+                    .help("let the SDK find the credentials, e.g. with `aws_config::load_defaults`, which reads the environment, profiles and roles")
+            }
+            Usage::MalformedArn(malformed) => {
+                let message = format!("argument `{}` of method `{}` is not a valid ARN", malformed.property, malformed.method);
+                add_duplicates_note(Diagnostic::error(span, message).code("malformed-arn"), &duplicates)
+                    .note(malformed.problem)
+                    .help("ARNs look like `arn:aws:sns:eu-west-1:123456789012:my-topic`")
+            }
+            Usage::OutOfRange(out_of_range) => {
+                let message = format!(
+                    "argument `{}` of method `{}` (from {}) is {}, which is not between {} and {}",
+                    out_of_range.property, out_of_range.method, sdk, out_of_range.value, out_of_range.min, out_of_range.max
+                );
+                let closest = out_of_range.value.clamp(out_of_range.min, out_of_range.max);
+                add_duplicates_note(Diagnostic::error(span, message).code("out-of-range"), &duplicates)
+                    .note("the service rejects values outside this range at runtime")
+                    .help(format!("use a value from {} to {}, e.g. `.{}({})`", out_of_range.min, out_of_range.max, out_of_range.property, closest))
+            }
+            Usage::QueueUrl(queue_url) => {
+                let message = format!("argument `queue_url` of method `{}` does not look like the URL of a queue", queue_url.method);
+                add_duplicates_note(Diagnostic::warning(span, message).code("malformed-queue-url"), &duplicates)
+                    .note(queue_url.problem)
+                    .help("queue URLs look like `https://sqs.eu-west-1.amazonaws.com/123456789012/my-queue`, `get_queue_url` returns the URL for a name")
+            }
+            Usage::BucketName(bucket) => {
+                let message = format!("argument `bucket` of method `{}` is not a valid bucket name", bucket.method);
+                add_duplicates_note(Diagnostic::error(span, message).code("invalid-bucket-name"), &duplicates)
+                    .note(bucket.problem)
+                    .help("see https://docs.aws.amazon.com/AmazonS3/latest/userguide/bucketnamingrules.html for the naming rules")
+            }
+            Usage::LeadingSlashKey(key) => {
+                let message = format!("argument `key` of method `{}` starts with a `/`", key.method);
+                add_duplicates_note(Diagnostic::warning(span, message).code("leading-slash-key"), &duplicates)
+                    .note("S3 keeps the `/` as part of the key, so the object ends up in a folder without a name")
+                    .help("remove the leading `/` from the key")
+            }
+            Usage::DuplicateSetter(duplicate) => {
+                let message = format!("method `{}` (from {}) sets `{}` more than once", duplicate.method, sdk, duplicate.property);
+                add_duplicates_note(Diagnostic::warning(span, message).code("duplicate-setter"), &duplicates)
+                    .note("only the last value is sent, the earlier ones are silently overwritten")
+                    .help(format!("remove all but one of the `.{}(...)` calls", duplicate.property))
+            }
+            Usage::UnknownRegion(unknown) => {
+                let message = format!("`{}` passed to `{}` is not an AWS region", unknown.region, unknown.setter);
+                let diagnostic = add_duplicates_note(Diagnostic::error(span, message).code("unknown-region"), &duplicates);
+                match unknown.suggestion {
+                    Some(suggestion) => diagnostic.help(format!("did you mean `{}`?", suggestion)),
+                    None => diagnostic.help("regions look like `eu-west-1`, see https://docs.aws.amazon.com/general/latest/gr/rande.html for all of them"),
+                }
+            }
+            Usage::Policy(policy) => {
+                let missing: Vec<String> = policy.missing.iter().map(|m| m.to_string()).collect();
+                let fix = fix_snippet(&missing, policy.terminator);
+                let described = missing.iter().map(|m| describe_required(m, property)).collect::<Vec<_>>().join(COMMA_WITH_SPACE);
+                let message = format!(
+                    "method `{}` (from {}) is missing argument(s) required by the `{}` policy: {}",
+                    policy.method,
+                    sdk,
+                    policy.policy.name(),
+                    described
+                );
+                add_duplicates_note(Diagnostic::error(span, message).code("policy-violation"), &duplicates)
+                    .note(format!("the `{}` policy was enabled in the attribute, e.g. `#[required_props(policy = {})]`", policy.policy.name(), policy.policy.name()))
+                    .help(fix)
+            }
+            Usage::Sequential(sequential) => {
+                let message = format!("`{}` is awaited after `{}`, although it does not use its result", sequential.method, sequential.previous);
+                add_duplicates_note(Diagnostic::warning(span, message).code("sequential-sends"), &duplicates)
+                    .note("the second request only starts when the first one is done, so the function waits for both one after the other")
+                    .help("if the order does not matter, await both at the same time with `tokio::join!` or `futures::try_join!`")
+            }
+            Usage::Batch(batch) => {
+                let message = format!("method `{}` (from {}) is called in a loop, while `{}` handles many items in one call", batch.method, sdk, batch.batch);
+                add_duplicates_note(Diagnostic::warning(span, message).code("batch-variant"), &duplicates)
+                    .note("every iteration is a separate request, which is slower and, for most services, more expensive")
+                    .help(format!("collect the items and send them with `{}` instead", batch.batch))
+            }
+            Usage::Iam(iam) => {
+                let action = describe_required(&iam.action, str::to_string);
+                let message = format!("method `{}` (from {}) needs {}, which the IAM policies do not allow", iam.method, sdk, action);
+                add_duplicates_note(Diagnostic::warning(span, message).code("iam-not-allowed"), &duplicates)
+                    .note(format!("checked against {}", iam.policies.join(COMMA_WITH_SPACE)))
+                    .note("resources and conditions are not known at compile time, so only the actions of the statements are checked")
+                    .help(format!("allow {} in one of the policies, or the call fails with an `AccessDenied` error", action))
+            }
+            Usage::Unavailable(unavailable) => {
+                let message = format!("method `{}` (from {}) is not available in `{}`", unavailable.method, sdk, unavailable.region);
+                let regions: Vec<String> = unavailable.available.iter().map(|r| format!("`{}`", r)).collect();
+                add_duplicates_note(Diagnostic::error(span, message).code("unavailable-in-region"), &duplicates)
+                    .note(format!("the region comes from {}", unavailable.source))
+                    .help(format!("the call only works in {}", join_last_with(&regions, "and")))
+            }
+            Usage::DeniedSdk(denied) => {
+                let message = format!("this crate may not use the {} SDK, but {} does", sdk, denied.found);
+                add_duplicates_note(Diagnostic::error(span, message).code("denied-sdk"), &duplicates)
+                    .note(format!("denied by `{}` in `AWS_SDK_COMPILE_CHECKS_DENIED_SDKS`", denied.registration))
+                    .help("move this call to a crate that may use the SDK, or change the registration")
+            }
+            Usage::UnusedSdk(unused) => {
+                let message = format!("SDK `{}` is in the attribute, but no call or client of `{}` matches it", sdk, unused.function);
+                add_duplicates_note(Diagnostic::warning(span, message).code("unused-sdk"), &duplicates)
+                    .note("the attribute might be stale, or copied from another function")
+                    .help(format!("remove `{}` from `sdk = ...`, or check that the calls you expect are there", sdk))
+            }
+            Usage::UnselectedSdk(unselected) => {
+                let message = format!("`{}` belongs to {}, which is not in your `sdk` list", unselected.method, sdk);
+                let selected = unselected.selected.join(COMMA_WITH_SPACE);
+                add_duplicates_note(Diagnostic::warning(span, message).code("unselected-sdk"), &duplicates)
+                    .note(format!("the SDK is known from {}, so the call is checked against {} instead of `sdk = {}`", unselected.client, sdk, selected))
+                    .help(format!("add it to the attribute, e.g. `sdk = {}, {}`", selected, sdk))
+            }
+            Usage::Untimed(untimed) => {
+                let message = format!("the call of `{}` is awaited without a timeout", untimed.method);
+                add_duplicates_note(Diagnostic::warning(span, message).code("untimed-send"), &duplicates)
+                    .note("by default, the SDK has no timeout for the whole operation, so a call that hangs keeps the function waiting")
+                    .help("wrap the call in `tokio::time::timeout(...)`, or set an `operation_timeout` in the `TimeoutConfig` of the client")
+            }
+            Usage::Idempotency(idempotency) => {
+                let message = format!("method `{}` (from {}) is called without `{}`", idempotency.method, sdk, idempotency.token);
+                add_duplicates_note(Diagnostic::warning(span, message).code("missing-idempotency-token"), &duplicates)
+                    .note("without a token of your own, every call gets a new one, so when your code retries the call (e.g. after a timeout), the operation can run twice")
+                    .help(format!("set `.{}(...)` to a value that stays the same when the call is retried, e.g. derived from the id of the request", idempotency.token))
+            }
+            Usage::Undeleted(UndeletedUsage) => {
+                let message = "messages received with `receive_message` (from sqs) are never deleted in this function";
+                add_duplicates_note(Diagnostic::warning(span, message).code("undeleted-message"), &duplicates)
+                    .note("SQS does not remove a message when you receive it, it only hides it for the visibility timeout, after which it is received again")
+                    .help("call `delete_message` (or `delete_message_batch`) with the receipt handle once a message is processed, or pass the receipt handle on to the code that does")
+            }
+            Usage::HardcodedResource(hardcoded) => {
+                let message = format!("`{}` is hardcoded as `{}`", hardcoded.setter, hardcoded.value);
+                add_duplicates_note(Diagnostic::warning(span, message).code("hardcoded-resource"), &duplicates)
+                    .note("the names of resources usually differ per environment, like a test and a production account")
+                    .help(format!("pass `{}` a value from a variable or the config, e.g. read from an environment variable", hardcoded.setter))
+            }
+            Usage::Builder(builder) => {
+                let missing: Vec<String> = builder.missing.iter().map(|m| m.to_string()).collect();
+                let described = missing.iter().map(|m| describe_required(m, str::to_string)).collect::<Vec<_>>().join(COMMA_WITH_SPACE);
+                let message = format!("method `{}` (from {}) is missing required method(s): {}", builder.method, builder.owner, described);
+                add_duplicates_note(Diagnostic::error(span, message).code("missing-builder-props"), &duplicates)
+                    .note(format!("required before `.{}()` by {} in `AWS_SDK_COMPILE_CHECKS_BUILDERS`", builder.terminator, builder.file))
+                    .help(fix_snippet(&missing, builder.terminator))
+            }
+            Usage::UnknownResource(unknown) => {
+                let message = format!("`{}` passed to `{}` is not one of the resources of your infrastructure", unknown.value, unknown.setter);
+                add_duplicates_note(Diagnostic::warning(span, message).code("unknown-resource"), &duplicates)
+                    .note(format!("checked against {}", unknown.files.join(COMMA_WITH_SPACE)))
+                    .help("check the identifier for typos, or deploy (and export the outputs of) the resource")
+            }
+            Usage::AboveMaximum(above) => {
+                let message = format!("`{}` passed to `{}` is above the maximum of {}", above.value, above.setter, above.max);
+                add_duplicates_note(Diagnostic::error(span, message).code("above-maximum"), &duplicates)
+                    .note(format!("the maximum was set in the attribute, with `max({} = {})`", above.setter, above.max))
+                    .help(format!("use a value of at most {}, or raise the maximum if you need the capacity", above.max))
+            }
+            Usage::Exclusive(exclusive) => {
+                let properties: Vec<String> = exclusive.properties.iter().map(|p| format!("`{}`", p)).collect();
+                let setters: Vec<String> = exclusive.properties.iter().map(|p| format!("`.{}(...)`", p)).collect();
+                let message = format!(
+                    "method `{}` (from {}) sets {}, which cannot be set together",
+                    exclusive.method,
+                    sdk,
+                    join_last_with(&properties, "and")
+                );
+                add_duplicates_note(Diagnostic::error(span, message).code("exclusive-props"), &duplicates)
+                    .note(format!("the service rejects the call, because {}", exclusive.reason))
+                    .help(format!("keep only one of {}", join_last_with(&setters, "or")))
+            }
+        };
+        // the last note, after those of the finding itself
+        match checked_against {
+            Some(version) => diagnostic.note(format!("checked against {}", version)),
+            None => diagnostic,
+        }
+    }
+}
+
+impl Usage {
+    /// Whether we found the problem with our data about the SDKs, whose version the diagnostic mentions
+    fn is_checked_against_data(&self) -> bool {
+        matches!(
+            self,
+            Usage::Improper(_)
+                | Usage::Unknown(_)
+                | Usage::Conditional(_)
+                | Usage::Deprecated(_)
+                | Usage::Empty(_)
+                | Usage::OutOfRange(_)
+                | Usage::Unavailable(_)
+                | Usage::Exclusive(_)
+        )
+    }
+
+    /// Whether two findings are about the same problem, apart from the SDK, which every finding has in the same place
+    fn is_same_problem(&self, other: &Usage) -> bool {
+        match (self, other) {
+            (Usage::Improper(first), Usage::Improper(second)) => first.method == second.method && first.missing == second.missing,
+            (Usage::Unknown(first), Usage::Unknown(second)) => first.method == second.method && first.sdks == second.sdks,
+            (Usage::Conditional(first), Usage::Conditional(second)) => first.method == second.method && first.missing == second.missing,
+            (Usage::Deprecated(first), Usage::Deprecated(second)) => first.method == second.method && first.deprecation == second.deprecation,
+            (Usage::Unawaited(first), Usage::Unawaited(second)) => first.method == second.method,
+            (Usage::Empty(first), Usage::Empty(second)) => first.method == second.method && first.property == second.property,
+            (Usage::Unwrapped(first), Usage::Unwrapped(second)) => first.method == second.method && first.unwrap == second.unwrap,
+            (Usage::Unpaginated(first), Usage::Unpaginated(second)) => first.method == second.method,
+            (Usage::Batch(first), Usage::Batch(second)) => first.method == second.method,
+            (Usage::Recommended(first), Usage::Recommended(second)) => first.method == second.method && first.missing == second.missing,
+            (Usage::Sequential(first), Usage::Sequential(second)) => first.method == second.method && first.previous == second.previous,
+            (Usage::Credentials(first), Usage::Credentials(second)) => first.credential == second.credential,
+            (Usage::MalformedArn(first), Usage::MalformedArn(second)) => {
+                first.method == second.method && first.property == second.property && first.problem == second.problem
+            }
+            (Usage::OutOfRange(first), Usage::OutOfRange(second)) => {
+                first.method == second.method && first.property == second.property && first.value == second.value
+            }
+            (Usage::QueueUrl(first), Usage::QueueUrl(second)) => first.method == second.method && first.problem == second.problem,
+            (Usage::BucketName(first), Usage::BucketName(second)) => first.method == second.method && first.problem == second.problem,
+            (Usage::LeadingSlashKey(first), Usage::LeadingSlashKey(second)) => first.method == second.method,
+            (Usage::DuplicateSetter(first), Usage::DuplicateSetter(second)) => first.method == second.method && first.property == second.property,
+            (Usage::UnknownRegion(first), Usage::UnknownRegion(second)) => first.setter == second.setter && first.region == second.region,
+            (Usage::DeniedSdk(first), Usage::DeniedSdk(second)) => first.found == second.found,
+            (Usage::UnusedSdk(_), Usage::UnusedSdk(_)) => true,
+            (Usage::UnselectedSdk(first), Usage::UnselectedSdk(second)) => first.method == second.method,
+            (Usage::Untimed(first), Usage::Untimed(second)) => first.method == second.method,
+            (Usage::Idempotency(first), Usage::Idempotency(second)) => first.method == second.method,
+            (Usage::Undeleted(_), Usage::Undeleted(_)) => true,
+            (Usage::HardcodedResource(first), Usage::HardcodedResource(second)) => first.setter == second.setter && first.value == second.value,
+            (Usage::Builder(first), Usage::Builder(second)) => first.owner == second.owner && first.method == second.method && first.missing == second.missing,
+            (Usage::UnknownResource(first), Usage::UnknownResource(second)) => first.setter == second.setter && first.value == second.value,
+            (Usage::Unavailable(first), Usage::Unavailable(second)) => first.method == second.method && first.region == second.region,
+            (Usage::Iam(first), Usage::Iam(second)) => first.method == second.method && first.action == second.action,
+            (Usage::AboveMaximum(first), Usage::AboveMaximum(second)) => first.setter == second.setter && first.value == second.value,
+            (Usage::Policy(first), Usage::Policy(second)) => first.method == second.method && first.policy == second.policy && first.missing == second.missing,
+            (Usage::Exclusive(first), Usage::Exclusive(second)) => first.method == second.method && first.properties == second.properties,
+            _ => false,
+        }
+    }
+}
+
+/// Lists the (sorted) SDKs, abbreviated to the first few unless the user asked for the full list
+fn sdk_list(sdks: &[String], full: bool) -> String {
+    if full || sdks.len() <= MAX_SDKS_TO_SHOW {
+        return sdks.join(COMMA_WITH_SPACE);
+    }
+    format!("{} and {} more", sdks[0..MAX_SDKS_TO_SHOW].join(COMMA_WITH_SPACE), sdks.len() - MAX_SDKS_TO_SHOW)
+}
+
+/// What happens at runtime when the properties are missing, because that is what the error prevents.
+/// Only about the properties the SDK or the service check themselves, so not about 'at least one of' groups, entries of maps or
+/// the corrections of `exceptions` (which have a note of their own). A service might accept a call without any of those
+fn runtime_failure(improper: &ImproperUsage) -> Option<String> {
+    let checked: Vec<String> = improper
+        .missing
+        .iter()
+        .filter(|m| alternatives(m).count() == 1 && map_key(m).is_none())
+        .filter(|m| !improper.exceptions.iter().any(|e| e.property == m.as_str()))
+        .map(|m| format!("`{}`", m))
+        .collect();
+    if checked.is_empty() {
+        return None;
+    }
+    let in_uri = if checked.len() == 1 { "it is part of the URI" } else { "one of them is part of the URI" };
+    let failure = match (improper.consequence, improper.terminator) {
+        (Consequence::Rejected, "presigned") => format!("AWS rejects the presigned request, and `presigned()` already fails with an `SdkError::ConstructionFailure` when {}", in_uri),
+        (Consequence::Rejected, "wait") => format!("AWS rejects the requests of the waiter, and it already fails with a `WaiterError::ConstructionFailure` when {}", in_uri),
+        (Consequence::Rejected, _) => format!("AWS rejects the request, and the SDK already fails with an `SdkError::ConstructionFailure` before sending it when {}", in_uri),
+        (Consequence::BuildError, _) => "`build()` returns a `BuildError`".to_string(),
+        (Consequence::ClientPanics, _) => "creating a client from this config panics".to_string(),
+        (Consequence::PresigningError, _) => "`build()` returns a `PresigningConfigError`".to_string(),
+        (Consequence::Unknown, _) => return None,
+    };
+    Some(format!(
+        "without {}, {} (the setters of the SDK are optional, so the compiler does not catch this)",
+        join_last_with(&checked, "and"),
+        failure
+    ))
+}
+
+/// Tells the user what setters to add, in the order of the data (which follows the AWS documentation)
+fn fix_snippet(missing: &[String], terminator: &str) -> String {
+    let setters: Vec<String> = missing.iter().map(|m| describe_required(m, setter_call)).collect();
+    format!("add {} before `.{}()`", join_last_with(&setters, "and"), terminator)
+}
+
+/// Formats a required property, or every alternative when it is an 'at least one of' group
+fn describe_required(required_prop: &str, format: fn(&str) -> String) -> String {
+    let formatted: Vec<String> = alternatives(required_prop).map(|a| format!("`{}`", format(a))).collect();
+    match formatted.len() {
+        1 => formatted.join(""),
+        _ => format!("at least one of {}", join_last_with(&formatted, "or")),
+    }
+}
+
+/// The property as the user sets it, e.g. `attributes(KmsMasterKeyId, ..)` for an entry of a map
+fn property(required: &str) -> String {
+    match map_key(required) {
+        Some((setter, key)) => format!("{}({}, ..)", setter, key),
+        None => required.to_string(),
+    }
+}
+
+fn setter_call(required: &str) -> String {
+    match map_key(required) {
+        Some((setter, key)) => format!(".{}({}, todo!())", setter, key),
+        None => format!(".{}(todo!())", required),
+    }
+}
+
+/// Explains why we check the call differently than its documentation suggests
+fn describe_exception(exception: &Exception) -> String {
+    match exception.requirement {
+        Requirement::Optional => format!("`{}` is not checked, although the documentation marks it as required: {}", exception.property, exception.rationale),
+        Requirement::Required => format!("`{}` is required, although the documentation marks it as optional: {}", exception.property, exception.rationale),
+    }
+}
+
+fn join_last_with(items: &[String], last_separator: &str) -> String {
+    match items.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} {} {}", rest.join(COMMA_WITH_SPACE), last_separator, last),
+        _ => items.join(COMMA_WITH_SPACE),
+    }
+}
+
+fn add_duplicates_note(diagnostic: Diagnostic, duplicates: &[Span]) -> Diagnostic {
+    if duplicates.is_empty() {
+        return diagnostic;
+    }
+    let mut lines: Vec<usize> = duplicates.iter().map(|d| d.start().line).collect();
+    lines.sort_unstable();
+    let lines = lines.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(COMMA_WITH_SPACE);
+    diagnostic.note(format!("the same problem occurs on line(s) {}", lines))
+}
+
+/// Collapses findings to avoid a flood of errors about the same problem.
+/// Every kind of finding stays apart, only an exact duplicate at the same location is dropped, and an unknown SDK
+/// gives way to an improper usage of the same call (knowing what is missing beats not knowing the SDK).
+/// Identical findings in code that is only compiled for some configurations (see `visitor::cfg_gated_code`) are merged into the first one,
+/// because they are the same code behind different `cfg` attributes, not different calls.
+pub(crate) fn deduplicate(findings: Vec<UsageFinds>, cfg_gated: &[(LineColumn, LineColumn)]) -> Vec<UsageFinds> {
+    let mut results: Vec<UsageFinds> = vec![];
+    let is_gated = |f: &UsageFinds| cfg_gated.iter().any(|(start, end)| *start <= f.span.start() && f.span.end() <= *end);
+
+    for finding in findings {
+        if let Some(existing) = results.iter_mut().find(|r| is_same_location(r.span, finding.span) && is_same_call(r, &finding)) {
+            if let (Usage::Unknown(_), Usage::Improper(_)) = (&existing.usage, &finding.usage) {
+                *existing = finding;
+            }
+        } else if let Some(existing) = results.iter_mut().find(|r| is_gated(r) && is_gated(&finding) && r.is_same_problem(&finding)) {
+            let mut finding = finding;
+            // we want to point to the first occurrence in the code, and the visitor goes through the calls in reverse order
+            if finding.span.start() < existing.span.start() {
+                std::mem::swap(existing, &mut finding);
+                existing.duplicates.append(&mut finding.duplicates);
             }
+            existing.duplicates.push(finding.span);
+        } else {
+            results.push(finding);
+        }
+    }
+
+    results
+}
+
+/// Whether two findings at the same location are about the same problem, or one is an unknown SDK of the call the other knows is improper
+fn is_same_call(first: &UsageFinds, second: &UsageFinds) -> bool {
+    first.is_same_problem(second) || matches!((&first.usage, &second.usage), (Usage::Unknown(_), Usage::Improper(_)) | (Usage::Improper(_), Usage::Unknown(_)))
+}
+
+pub(crate) fn is_same_location(first: Span, second: Span) -> bool {
+    first.start() == second.start() && first.end() == second.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::{TokenStream, TokenTree};
+
+    use super::*;
+
+    // spans of tokens parsed from a string have a location, unlike `Span::call_site()`
+    fn spans(source: &str) -> Vec<Span> {
+        source.parse::<TokenStream>().unwrap().into_iter().map(|t: TokenTree| t.span()).collect()
+    }
+
+    fn improper(span: Span, missing: &str) -> UsageFinds {
+        UsageFinds::of_sdk(span, "sqs", Usage::Improper(ImproperUsage {
+            method: "send_message".to_string(),
+            missing: vec![missing.to_string()],
+            inferred_from: Inference::Attribute,
+            terminator: "send",
+            consequence: Consequence::Rejected,
+            docs_url: None,
+            conditional: vec![],
+            exceptions: vec![],
+        }))
+    }
+
+    fn unpaginated(span: Span) -> UsageFinds {
+        UsageFinds::of_sdk(span, "sqs", Usage::Unpaginated(UnpaginatedUsage {
+            method: "send_message".to_string(),
+        }))
+    }
+
+    fn all_lines_gated() -> Vec<(LineColumn, LineColumn)> {
+        vec![(LineColumn { line: 1, column: 0 }, LineColumn { line: usize::MAX, column: 0 })]
+    }
+
+    fn unknown(span: Span) -> UsageFinds {
+        UsageFinds::new(span, Usage::Unknown(UnknownUsage {
+            method: "send_message".to_string(),
+            receiver: Some("client".to_string()),
+            sdks: vec!["connectparticipant".to_string(), "sqs".to_string()],
+            suggested_sdk: None,
+        }))
+    }
+
+    #[test]
+    fn fix_snippet_for_one_missing_property() {
+        assert_eq!(fix_snippet(&["queue_url".to_string()], "send"), "add `.queue_url(todo!())` before `.send()`");
+    }
+
+    #[test]
+    fn fix_snippet_for_type_builder_adds_before_build() {
+        assert_eq!(fix_snippet(&["region_name".to_string()], "build"), "add `.region_name(todo!())` before `.build()`");
+    }
+
+    #[test]
+    fn fix_snippet_for_multiple_missing_properties_keeps_their_order() {
+        let missing = ["queue_url".to_string(), "message_body".to_string(), "delay_seconds".to_string()];
+
+        assert_eq!(
+            fix_snippet(&missing, "send"),
+            "add `.queue_url(todo!())`, `.message_body(todo!())` and `.delay_seconds(todo!())` before `.send()`"
+        );
+    }
+
+    #[test]
+    fn fix_snippet_for_at_least_one_of_group() {
+        let missing = ["message".to_string(), "topic_arn|target_arn|phone_number".to_string()];
+
+        assert_eq!(
+            fix_snippet(&missing, "send"),
+            "add `.message(todo!())` and at least one of `.topic_arn(todo!())`, `.target_arn(todo!())` or `.phone_number(todo!())` before `.send()`"
+        );
+    }
+
+    #[test]
+    fn improper_usage_message_describes_at_least_one_of_group() {
+        let diagnostic = improper(Span::call_site(), "topic_arn|target_arn").into_diagnostic();
+
+        assert_eq!(
+            diagnostic.message,
+            "method `send_message` (from sqs) is missing required argument(s): at least one of `topic_arn` or `target_arn`"
+        );
+    }
+
+    #[test]
+    fn improper_usage_explains_the_runtime_failure() {
+        let diagnostic = improper(Span::call_site(), "queue_url").into_diagnostic();
+
+        assert!(diagnostic.notes.contains(
+            &"without `queue_url`, AWS rejects the request, and the SDK already fails with an `SdkError::ConstructionFailure` before sending it when it is part of the URI (the setters of the SDK are optional, so the compiler does not catch this)"
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn runtime_failure_of_type_builders() {
+        let mut finding = improper(Span::call_site(), "key_type");
+        if let Usage::Improper(improper) = &mut finding.usage {
+            improper.missing.push("attribute_name".to_string());
+            improper.terminator = "build";
+            improper.consequence = Consequence::BuildError;
+        }
+
+        let diagnostic = finding.into_diagnostic();
+
+        assert!(diagnostic.notes.iter().any(|n| n.starts_with("without `key_type` and `attribute_name`, `build()` returns a `BuildError`")));
+    }
+
+    #[test]
+    fn runtime_failure_is_only_about_the_properties_the_sdk_or_service_check() {
+        let mut finding = improper(Span::call_site(), "update_expression|attribute_updates");
+        if let Usage::Improper(improper) = &mut finding.usage {
+            improper.missing.push("attributes:KmsMasterKeyId".to_string());
+        }
+
+        let diagnostic = finding.into_diagnostic();
+
+        assert!(!diagnostic.notes.iter().any(|n| n.starts_with("without")));
+    }
+
+    #[test]
+    fn runtime_failure_is_unknown_for_datasets() {
+        let mut finding = improper(Span::call_site(), "order_id");
+        if let Usage::Improper(improper) = &mut finding.usage {
+            improper.consequence = Consequence::Unknown;
         }
+
+        let diagnostic = finding.into_diagnostic();
+
+        assert!(!diagnostic.notes.iter().any(|n| n.starts_with("without")));
+    }
+
+    #[test]
+    fn improper_usage_explains_the_exceptions() {
+        let mut finding = improper(Span::call_site(), "vault_name");
+        if let Usage::Improper(improper) = &mut finding.usage {
+            improper.exceptions = vec![
+                Exception {
+                    property: "account_id",
+                    requirement: Requirement::Optional,
+                    rationale: "the SDK sets it to `-` when it is missing",
+                },
+                Exception {
+                    property: "vault_name",
+                    requirement: Requirement::Required,
+                    rationale: "the service rejects calls without it",
+                },
+            ];
+        }
+
+        let diagnostic = finding.into_diagnostic();
+
+        assert!(diagnostic
+            .notes
+            .contains(&"`account_id` is not checked, although the documentation marks it as required: the SDK sets it to `-` when it is missing".to_string()));
+        assert!(diagnostic
+            .notes
+            .contains(&"`vault_name` is required, although the documentation marks it as optional: the service rejects calls without it".to_string()));
+    }
+
+    #[test]
+    fn conditional_usage_is_a_warning_with_the_condition() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "sqs", Usage::Conditional(ConditionalUsage {
+            method: "send_message".to_string(),
+            missing: vec![ConditionallyRequired {
+                property: "message_group_id",
+                condition: "the queue is a FIFO queue",
+            }],
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "method `send_message` (from sqs) might be missing argument(s): `message_group_id`");
+        assert_eq!(diagnostic.notes[0], "`message_group_id` is required when the queue is a FIFO queue");
+        assert_eq!(diagnostic.help, vec!["if that is the case, add `.message_group_id(todo!())` before `.send()`"]);
+    }
+
+    #[test]
+    fn unawaited_usage_is_a_warning_with_the_fix() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::Unawaited(UnawaitedUsage {
+            method: "receive_message".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "the future of `receive_message` is never awaited, so the request is never sent");
+        assert_eq!(diagnostic.help, vec!["add `.await` after `.send()`"]);
+    }
+
+    #[test]
+    fn empty_usage_is_a_warning_about_the_property() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "sqs", Usage::Empty(EmptyUsage {
+            method: "receive_message".to_string(),
+            property: "queue_url".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "required argument `queue_url` of method `receive_message` (from sqs) is an empty string");
+        assert_eq!(diagnostic.help, vec!["pass a non-empty value to `.queue_url(...)`"]);
+    }
+
+    #[test]
+    fn unwrapped_usage_is_a_warning_with_the_alternative() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::Unwrapped(UnwrappedUsage {
+            method: "receive_message".to_string(),
+            unwrap: "expect".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "the result of `receive_message` is unwrapped with `expect`, which panics when the call fails");
+        assert_eq!(diagnostic.help, vec!["return the error with `?` instead of `.expect(...)`, or handle it explicitly"]);
+    }
+
+    #[test]
+    fn unpaginated_usage_is_a_warning_suggesting_the_paginator() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "dynamodb", Usage::Unpaginated(UnpaginatedUsage {
+            method: "query".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(
+            diagnostic.message,
+            "method `query` (from dynamodb) only returns the first page of results, and the token for the next page is never used"
+        );
+        assert_eq!(diagnostic.help, vec!["use `.into_paginator()` instead of `.send()` to go through all the pages"]);
+    }
+
+    #[test]
+    fn undeleted_usage_is_an_opt_in_warning_about_the_visibility_timeout() {
+        let finding = UsageFinds::new(Span::call_site(), Usage::Undeleted(UndeletedUsage));
+
+        assert_eq!(finding.opt_in_warning(), Some(OptInWarning::SqsDelete));
+        let diagnostic = finding.into_diagnostic();
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "messages received with `receive_message` (from sqs) are never deleted in this function");
+        assert!(diagnostic.notes[0].contains("visibility timeout"));
+    }
+
+    #[test]
+    fn credentials_usage_is_an_error_without_the_value() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::Credentials(CredentialsUsage {
+            credential: Credential::SecretAccessKey,
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.message, "this literal looks like an AWS secret access key");
+    }
+
+    #[test]
+    fn malformed_arn_usage_is_an_error_with_the_problem() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::MalformedArn(MalformedArnUsage {
+            method: "publish".to_string(),
+            property: "topic_arn".to_string(),
+            problem: "an ARN starts with `arn:`".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.message, "argument `topic_arn` of method `publish` is not a valid ARN");
+        assert_eq!(diagnostic.notes[0], "an ARN starts with `arn:`");
+    }
+
+    #[test]
+    fn out_of_range_usage_is_an_error_suggesting_the_closest_bound() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "sqs", Usage::OutOfRange(OutOfRangeUsage {
+            method: "send_message".to_string(),
+            property: "delay_seconds",
+            value: 1000,
+            min: 0,
+            max: 900,
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.message, "argument `delay_seconds` of method `send_message` (from sqs) is 1000, which is not between 0 and 900");
+        assert_eq!(diagnostic.help[0], "use a value from 0 to 900, e.g. `.delay_seconds(900)`");
+    }
+
+    #[test]
+    fn queue_url_usage_is_a_warning_with_the_problem() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::QueueUrl(QueueUrlUsage {
+            method: "send_message".to_string(),
+            problem: "this looks like the name of the queue, while the call needs its URL".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "argument `queue_url` of method `send_message` does not look like the URL of a queue");
+        assert_eq!(diagnostic.notes[0], "this looks like the name of the queue, while the call needs its URL");
+    }
+
+    #[test]
+    fn bucket_name_usage_is_an_error_with_the_problem() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::BucketName(BucketNameUsage {
+            method: "put_object".to_string(),
+            problem: "bucket names begin and end with a letter or number".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.message, "argument `bucket` of method `put_object` is not a valid bucket name");
+        assert_eq!(diagnostic.notes[0], "bucket names begin and end with a letter or number");
+    }
+
+    #[test]
+    fn leading_slash_key_usage_is_a_warning() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::LeadingSlashKey(LeadingSlashKeyUsage {
+            method: "get_object".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "argument `key` of method `get_object` starts with a `/`");
+    }
+
+    #[test]
+    fn duplicate_setter_usage_is_a_warning() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "sqs", Usage::DuplicateSetter(DuplicateSetterUsage {
+            method: "send_message".to_string(),
+            property: "queue_url".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "method `send_message` (from sqs) sets `queue_url` more than once");
+    }
+
+    #[test]
+    fn policy_usage_is_an_error_with_the_entries_of_maps() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "sqs", Usage::Policy(PolicyUsage {
+            method: "create_queue".to_string(),
+            policy: Policy::Encryption,
+            missing: vec!["attributes:KmsMasterKeyId|attributes:SqsManagedSseEnabled"],
+            terminator: "send",
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(
+            diagnostic.message,
+            "method `create_queue` (from sqs) is missing argument(s) required by the `encryption` policy: at least one of `attributes(KmsMasterKeyId, ..)` or `attributes(SqsManagedSseEnabled, ..)`"
+        );
+        assert_eq!(
+            diagnostic.help,
+            vec!["add at least one of `.attributes(KmsMasterKeyId, todo!())` or `.attributes(SqsManagedSseEnabled, todo!())` before `.send()`"]
+        );
+    }
+
+    #[test]
+    fn recommended_usage_is_a_warning_that_is_not_about_required_props() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "s3", Usage::Recommended(RecommendedUsage {
+            method: "put_object".to_string(),
+            missing: vec![Recommended {
+                property: "content_type",
+                reason: "without it, the type is unknown",
+            }],
+            terminator: "send",
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "method `put_object` (from s3) does not set recommended argument(s): `content_type`");
+        assert_eq!(diagnostic.notes[0], "`content_type` is recommended: without it, the type is unknown");
+    }
+
+    #[test]
+    fn sequential_usage_is_a_warning() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::Sequential(SequentialUsage {
+            method: "list_tables".to_string(),
+            previous: "list_queues".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "`list_tables` is awaited after `list_queues`, although it does not use its result");
+    }
+
+    #[test]
+    fn batch_usage_is_a_warning_with_the_batch_variant() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "dynamodb", Usage::Batch(BatchUsage {
+            method: "put_item".to_string(),
+            batch: "batch_write_item",
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "method `put_item` (from dynamodb) is called in a loop, while `batch_write_item` handles many items in one call");
+    }
+
+    #[test]
+    fn iam_usage_is_a_warning_with_the_action() {
+        static POLICIES: [String; 0] = [];
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "dynamodb", Usage::Iam(IamUsage {
+            method: "execute_statement".to_string(),
+            action: "dynamodb:PartiQLSelect|dynamodb:PartiQLInsert".to_string(),
+            policies: &POLICIES,
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(
+            diagnostic.message,
+            "method `execute_statement` (from dynamodb) needs at least one of `dynamodb:PartiQLSelect` or `dynamodb:PartiQLInsert`, which the IAM policies do not allow"
+        );
+    }
+
+    #[test]
+    fn above_maximum_usage_is_an_error() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::AboveMaximum(AboveMaximumUsage {
+            setter: "read_capacity_units".to_string(),
+            value: 5000,
+            max: 100,
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.message, "`5000` passed to `read_capacity_units` is above the maximum of 100");
+    }
+
+    #[test]
+    fn unknown_region_usage_is_an_error_with_a_suggestion() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::UnknownRegion(UnknownRegionUsage {
+            setter: "region".to_string(),
+            region: "eu-west1".to_string(),
+            suggestion: Some("eu-west-1"),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.message, "`eu-west1` passed to `region` is not an AWS region");
+        assert_eq!(diagnostic.help[0], "did you mean `eu-west-1`?");
+    }
+
+    #[test]
+    fn unavailable_usage_is_an_error_with_the_available_regions() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "apprunner", Usage::Unavailable(UnavailableUsage {
+            method: "create_service".to_string(),
+            region: "eu-south-1".to_string(),
+            source: "`.region(\"eu-south-1\")`".to_string(),
+            available: vec!["eu-west-1", "us-east-1"],
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.code, Some("unavailable-in-region"));
+        assert_eq!(diagnostic.message, "method `create_service` (from apprunner) is not available in `eu-south-1`");
+        assert_eq!(diagnostic.notes[0], "the region comes from `.region(\"eu-south-1\")`");
+        assert_eq!(diagnostic.help[0], "the call only works in `eu-west-1` and `us-east-1`");
+    }
+
+    #[test]
+    fn unknown_resource_usage_is_a_warning_with_the_files() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::UnknownResource(UnknownResourceUsage {
+            setter: "table_name".to_string(),
+            value: "order".to_string(),
+            files: Box::leak(Box::new(["cdk-outputs.json".to_string()])),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.code, Some("unknown-resource"));
+        assert_eq!(diagnostic.message, "`order` passed to `table_name` is not one of the resources of your infrastructure");
+        assert_eq!(diagnostic.notes[0], "checked against cdk-outputs.json");
+    }
+
+    #[test]
+    fn denied_sdk_usage_is_an_error_with_the_registration() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "iam", Usage::DeniedSdk(DeniedSdkUsage {
+            found: "the call of `create_user`".to_string(),
+            registration: "*=iam".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.code, Some("denied-sdk"));
+        assert_eq!(diagnostic.message, "this crate may not use the iam SDK, but the call of `create_user` does");
+        assert_eq!(diagnostic.notes[0], "denied by `*=iam` in `AWS_SDK_COMPILE_CHECKS_DENIED_SDKS`");
+    }
+
+    #[test]
+    fn unused_sdk_usage_is_a_warning_about_the_attribute() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "sns", Usage::UnusedSdk(UnusedSdkUsage {
+            function: "call_with_sqs_client".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.code, Some("unused-sdk"));
+        assert_eq!(diagnostic.message, "SDK `sns` is in the attribute, but no call or client of `call_with_sqs_client` matches it");
+    }
+
+    #[test]
+    fn unselected_sdk_usage_is_a_warning_naming_the_sdk_of_the_call() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "dynamodb", Usage::UnselectedSdk(UnselectedSdkUsage {
+            method: "put_item".to_string(),
+            client: "parameter `dynamodb: aws_sdk_dynamodb::Client`".to_string(),
+            selected: vec!["sqs".to_string(), "sns".to_string()],
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.code, Some("unselected-sdk"));
+        assert_eq!(diagnostic.message, "`put_item` belongs to dynamodb, which is not in your `sdk` list");
+        assert_eq!(diagnostic.help, vec!["add it to the attribute, e.g. `sdk = sqs, sns, dynamodb`".to_string()]);
+    }
+
+    #[test]
+    fn untimed_usage_is_a_warning_suggesting_a_timeout() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::Untimed(UntimedUsage {
+            method: "receive_message".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.code, Some("untimed-send"));
+        assert_eq!(diagnostic.message, "the call of `receive_message` is awaited without a timeout");
+    }
+
+    #[test]
+    fn idempotency_usage_is_a_warning_with_the_setter_of_the_token() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "dynamodb", Usage::Idempotency(IdempotencyUsage {
+            method: "import_table".to_string(),
+            token: "client_token",
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.code, Some("missing-idempotency-token"));
+        assert_eq!(diagnostic.message, "method `import_table` (from dynamodb) is called without `client_token`");
+    }
+
+    #[test]
+    fn hardcoded_resource_usage_is_a_warning_with_the_literal() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::HardcodedResource(HardcodedResourceUsage {
+            setter: "table_name".to_string(),
+            value: "orders".to_string(),
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.code, Some("hardcoded-resource"));
+        assert_eq!(diagnostic.message, "`table_name` is hardcoded as `orders`");
+    }
+
+    #[test]
+    fn builder_usage_is_an_error_with_the_registration() {
+        let diagnostic = UsageFinds::new(Span::call_site(), Usage::Builder(BuilderUsage {
+            owner: "EventPublisher",
+            method: "publish",
+            missing: vec!["detail_type", "source|source_arn"],
+            terminator: "dispatch",
+            file: "builders.csv",
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.code, Some("missing-builder-props"));
+        assert_eq!(
+            diagnostic.message,
+            "method `publish` (from EventPublisher) is missing required method(s): `detail_type`, at least one of `source` or `source_arn`"
+        );
+        assert_eq!(diagnostic.notes[0], "required before `.dispatch()` by builders.csv in `AWS_SDK_COMPILE_CHECKS_BUILDERS`");
+    }
+
+    #[test]
+    fn exclusive_usage_is_an_error_with_the_reason() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "dynamodb", Usage::Exclusive(ExclusiveUsage {
+            method: "scan".to_string(),
+            properties: vec!["scan_filter", "filter_expression"],
+            reason: "the legacy parameter cannot be combined with its expression",
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Error);
+        assert_eq!(diagnostic.message, "method `scan` (from dynamodb) sets `scan_filter` and `filter_expression`, which cannot be set together");
+        assert_eq!(diagnostic.notes[0], "the service rejects the call, because the legacy parameter cannot be combined with its expression");
+        assert_eq!(diagnostic.help, vec!["keep only one of `.scan_filter(...)` or `.filter_expression(...)`"]);
+    }
+
+    #[test]
+    fn sdk_list_abbreviates_long_lists() {
+        let sdks: Vec<String> = ["a", "b", "c", "d", "e", "f", "g"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(sdk_list(&sdks, false), "a, b, c, d, e and 2 more");
+        assert_eq!(sdk_list(&sdks, true), "a, b, c, d, e, f, g");
+        assert_eq!(sdk_list(&sdks[0..5], false), "a, b, c, d, e");
+    }
+
+    #[test]
+    fn unknown_usage_suggests_sdk_attribute_and_client_mapping() {
+        let diagnostic = unknown(Span::call_site()).into_diagnostic();
+
+        assert_eq!(
+            diagnostic.help,
+            vec![
+                "add the right one(s) to the attribute, e.g. `#[required_props(sdk = connectparticipant)]`",
+                "or map the receiver to its SDK, e.g. `#[required_props(clients(client = connectparticipant))]`",
+            ]
+        );
+    }
+
+    #[test]
+    fn deduplicate_prefers_improper_over_unknown_for_the_same_call() {
+        let spans = spans("send_message");
+
+        let actual = deduplicate(vec![unknown(spans[0]), improper(spans[0], "queue_url")], &[]);
+
+        assert_eq!(actual.len(), 1);
+        assert!(matches!(actual[0].usage, Usage::Improper(_)));
+    }
+
+    #[test]
+    fn deduplicate_merges_identical_findings_in_cfg_gated_code() {
+        let spans = spans("send_message\nsend_message\nsend_message");
+
+        let actual = deduplicate(
+            vec![
+                improper(spans[0], "queue_url"),
+                improper(spans[1], "queue_url"),
+                improper(spans[2], "message_body"),
+            ],
+            &all_lines_gated(),
+        );
+
+        assert_eq!(actual.len(), 2);
+        assert!(matches!(actual[0].usage, Usage::Improper(_)));
+        assert_eq!(actual[0].duplicates.len(), 1);
+    }
+
+    #[test]
+    fn deduplicate_keeps_the_first_occurrence_in_the_code() {
+        let spans = spans("send_message\nsend_message");
+
+        let actual = deduplicate(vec![improper(spans[1], "queue_url"), improper(spans[0], "queue_url")], &all_lines_gated());
+
+        assert_eq!(actual.len(), 1);
+        assert!(matches!(actual[0].usage, Usage::Improper(_)));
+        assert_eq!(actual[0].span.start().line, 1);
+        assert_eq!(actual[0].duplicates[0].start().line, 2);
+    }
+
+    #[test]
+    fn deprecated_usage_is_a_warning_with_the_note_as_help() {
+        let diagnostic = UsageFinds::of_sdk(Span::call_site(), "s3", Usage::Deprecated(DeprecatedUsage {
+            method: "list_objects".to_string(),
+            deprecation: Deprecation {
+                property: Some("marker"),
+                note: Some("use `list_objects_v2` with `start_after` instead"),
+            },
+        }))
+        .into_diagnostic();
+
+        assert_eq!(diagnostic.level, crate::diagnostics::Level::Warning);
+        assert_eq!(diagnostic.message, "property `marker` of method `list_objects` (from s3) is deprecated");
+        assert_eq!(diagnostic.help, vec!["use `list_objects_v2` with `start_after` instead"]);
+    }
+
+    #[test]
+    fn deduplicate_keeps_deprecation_of_the_same_call() {
+        let spans = spans("send_message");
+        let deprecated = UsageFinds::of_sdk(spans[0], "sqs", Usage::Deprecated(DeprecatedUsage {
+            method: "send_message".to_string(),
+            deprecation: Deprecation { property: None, note: None },
+        }));
+
+        let actual = deduplicate(vec![improper(spans[0], "queue_url"), deprecated], &[]);
+
+        assert_eq!(actual.len(), 2);
+    }
+
+    #[test]
+    fn deduplicate_keeps_unselected_sdk_of_the_same_call() {
+        let spans = spans("send_message");
+        let unselected = UsageFinds::of_sdk(spans[0], "sqs", Usage::UnselectedSdk(UnselectedSdkUsage {
+            method: "send_message".to_string(),
+            client: "parameter `queue: aws_sdk_sqs::Client`".to_string(),
+            selected: vec!["sns".to_string()],
+        }));
+
+        let actual = deduplicate(vec![unselected, improper(spans[0], "queue_url")], &[]);
+
+        assert_eq!(actual.len(), 2);
+    }
+
+    #[test]
+    fn deduplicate_keeps_different_findings() {
+        let spans = spans("send_message send_message");
+
+        let actual = deduplicate(vec![unknown(spans[0]), improper(spans[1], "queue_url")], &[]);
+
+        assert_eq!(actual.len(), 2);
+    }
+
+    #[test]
+    fn deduplicate_keeps_identical_findings_of_calls_that_are_not_cfg_gated() {
+        let spans = spans("send_message\nsend_message");
+
+        let actual = deduplicate(vec![improper(spans[0], "queue_url"), improper(spans[1], "queue_url")], &[]);
+
+        assert_eq!(actual.len(), 2);
+    }
+
+    #[test]
+    fn deduplicate_keeps_an_improper_usage_after_another_finding_of_the_same_call() {
+        let spans = spans("send_message");
+
+        let actual = deduplicate(vec![unpaginated(spans[0]), improper(spans[0], "queue_url")], &all_lines_gated());
+
+        assert_eq!(actual.len(), 2);
+        assert!(matches!(actual[1].usage, Usage::Improper(_)));
+    }
+
+    #[test]
+    fn deduplicate_drops_exact_duplicates_and_unknowns_of_an_improper_call() {
+        let spans = spans("send_message");
+
+        let actual = deduplicate(vec![improper(spans[0], "queue_url"), improper(spans[0], "queue_url"), unknown(spans[0])], &[]);
+
+        assert_eq!(actual.len(), 1);
+        assert!(matches!(actual[0].usage, Usage::Improper(_)));
     }
 }
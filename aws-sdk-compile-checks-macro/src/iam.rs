@@ -0,0 +1,253 @@
+use std::sync::OnceLock;
+
+use aws_sdk_compile_checks_data::iam::iam_actions;
+use aws_sdk_compile_checks_data::required_properties::alternatives;
+use serde_json::Value;
+
+use crate::findings::{IamUsage, Usage, UsageFinds};
+use crate::registered::registered_files;
+use crate::visitor::SdkCall;
+
+/// IAM policy documents (JSON) to check the calls against, e.g. `iam/lambda_role.json`
+const IAM_POLICIES_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_IAM_POLICIES";
+const WILDCARD: char = '*';
+const SINGLE_CHARACTER_WILDCARD: char = '?';
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Effect {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, PartialEq)]
+struct Statement {
+    effect: Effect,
+    actions: Vec<String>,
+    not_actions: Vec<String>,
+    everywhere: bool, // for all resources and without conditions, the only kind of deny we can be sure of at compile time
+}
+
+impl Statement {
+    fn matches(&self, action: &str) -> bool {
+        if self.not_actions.is_empty() {
+            self.actions.iter().any(|pattern| matches_pattern(pattern, action))
+        } else {
+            !self.not_actions.iter().any(|pattern| matches_pattern(pattern, action))
+        }
+    }
+}
+
+/// The statements of all the policies in `AWS_SDK_COMPILE_CHECKS_IAM_POLICIES`
+#[derive(Debug)]
+pub(crate) struct IamPolicies {
+    files: Vec<String>,
+    statements: Vec<Statement>,
+}
+
+impl IamPolicies {
+    /// Allowed by at least one statement and not denied by any statement we are sure of
+    fn allows(&self, action: &str) -> bool {
+        let allowed = self.statements.iter().any(|s| s.effect == Effect::Allow && s.matches(action));
+        let denied = self.statements.iter().any(|s| s.effect == Effect::Deny && s.everywhere && s.matches(action));
+        allowed && !denied
+    }
+}
+
+/// The policies registered with `AWS_SDK_COMPILE_CHECKS_IAM_POLICIES` (see [`registered_files`]), `None` when there are none,
+/// or a description of why they could not be loaded
+pub(crate) fn iam_policies() -> Result<Option<&'static IamPolicies>, &'static str> {
+    static POLICIES: OnceLock<Result<Option<IamPolicies>, String>> = OnceLock::new();
+    POLICIES
+        .get_or_init(|| {
+            let policies = registered_files(IAM_POLICIES_ENV_VAR)?;
+            if policies.is_empty() {
+                return Ok(None);
+            }
+            let mut statements = vec![];
+            for policy in &policies {
+                statements.extend(parse_policy(&policy.content).map_err(|e| format!("{}: {}", policy.name, e))?);
+            }
+            let files = policies.into_iter().map(|policy| policy.name).collect();
+            Ok(Some(IamPolicies { files, statements }))
+        })
+        .as_ref()
+        .map(Option::as_ref)
+        .map_err(String::as_str)
+}
+
+/// A finding for every call with an action (or group of alternative actions) the policies do not allow
+pub(crate) fn find_calls_not_allowed(calls: &[SdkCall], policies: &'static IamPolicies) -> Vec<UsageFinds> {
+    calls
+        .iter()
+        .flat_map(|call| {
+            iam_actions(&call.sdk, &call.operation)
+                .into_iter()
+                .filter(|action| !alternatives(action).any(|a| policies.allows(a)))
+                .map(|action| {
+                    UsageFinds::of_sdk(call.span, call.sdk.clone(), Usage::Iam(IamUsage {
+                        method: call.method.clone(),
+                        action,
+                        policies: &policies.files,
+                    }))
+                })
+        })
+        .collect()
+}
+
+/// Case-insensitive, with `*` for any number of characters and `?` for one, like IAM itself
+fn matches_pattern(pattern: &str, action: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_ascii_lowercase().chars().collect();
+    let action: Vec<char> = action.to_ascii_lowercase().chars().collect();
+    let (mut p, mut a) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None; // position after the last `*`, and the part of the action it covers
+    while a < action.len() {
+        if p < pattern.len() && (pattern[p] == SINGLE_CHARACTER_WILDCARD || pattern[p] == action[a]) {
+            p += 1;
+            a += 1;
+        } else if p < pattern.len() && pattern[p] == WILDCARD {
+            backtrack = Some((p + 1, a));
+            p += 1;
+        } else if let Some((after_wildcard, covered)) = backtrack {
+            p = after_wildcard;
+            a = covered + 1;
+            backtrack = Some((after_wildcard, a));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == WILDCARD)
+}
+
+fn parse_policy(json: &str) -> Result<Vec<Statement>, String> {
+    let document: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let statements = match document.get("Statement") {
+        Some(Value::Array(statements)) => statements.iter().collect(),
+        Some(statement @ Value::Object(_)) => vec![statement],
+        _ => return Err("expected a policy document with a `Statement`".to_string()),
+    };
+    statements.into_iter().map(parse_statement).collect()
+}
+
+fn parse_statement(statement: &Value) -> Result<Statement, String> {
+    let effect = match statement.get("Effect") {
+        Some(Value::String(effect)) if effect == "Allow" => Effect::Allow,
+        Some(Value::String(effect)) if effect == "Deny" => Effect::Deny,
+        _ => return Err("expected every statement to have an `Effect` of `Allow` or `Deny`".to_string()),
+    };
+    let actions = strings(statement.get("Action"))?;
+    let not_actions = strings(statement.get("NotAction"))?;
+    if actions.is_empty() && not_actions.is_empty() {
+        return Err("expected every statement to have an `Action` or `NotAction`".to_string());
+    }
+    let everywhere =
+        strings(statement.get("Resource"))?.iter().any(|r| r == "*") && statement.get("NotResource").is_none() && statement.get("Condition").is_none();
+    Ok(Statement {
+        effect,
+        actions,
+        not_actions,
+        everywhere,
+    })
+}
+
+/// A string or an array of strings, which IAM allows for e.g. `Action` and `Resource`
+fn strings(value: Option<&Value>) -> Result<Vec<String>, String> {
+    match value {
+        None => Ok(vec![]),
+        Some(Value::String(value)) => Ok(vec![value.clone()]),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|v| match v {
+                Value::String(value) => Ok(value.clone()),
+                _ => Err("expected actions and resources to be strings".to_string()),
+            })
+            .collect(),
+        Some(_) => Err("expected actions and resources to be a string or an array of strings".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    use super::*;
+
+    const POLICY: &str = r#"{
+        "Version": "2012-10-17",
+        "Statement": [
+            { "Effect": "Allow", "Action": ["sqs:SendMessage", "s3:Get*"], "Resource": "arn:aws:sqs:eu-west-1:123456789012:orders" },
+            { "Effect": "Allow", "Action": "dynamodb:PartiQLSelect", "Resource": "*" },
+            { "Effect": "Deny", "Action": "s3:GetObjectAcl", "Resource": "*" },
+            { "Effect": "Deny", "Action": "sqs:SendMessage", "Resource": "*", "Condition": { "Bool": { "aws:SecureTransport": "false" } } }
+        ]
+    }"#;
+
+    fn policies(json: &str) -> &'static IamPolicies {
+        Box::leak(Box::new(IamPolicies {
+            files: vec!["policy.json".to_string()],
+            statements: parse_policy(json).unwrap(),
+        }))
+    }
+
+    fn call(sdk: &str, method: &str) -> SdkCall {
+        SdkCall {
+            span: Span::call_site(),
+            sdk: sdk.to_string(),
+            method: method.to_string(),
+            operation: method.to_string(),
+        }
+    }
+
+    #[test]
+    fn matches_pattern_with_wildcards_ignoring_case() {
+        assert!(matches_pattern("s3:Get*", "s3:GetObject"));
+        assert!(matches_pattern("*", "sqs:SendMessage"));
+        assert!(matches_pattern("S3:*Object?cl", "s3:GetObjectAcl"));
+        assert!(matches_pattern("sqs:*Message*", "sqs:SendMessageBatch"));
+        assert!(!matches_pattern("s3:Get*", "s3:PutObject"));
+        assert!(!matches_pattern("sqs:SendMessage", "sqs:SendMessageBatch"));
+    }
+
+    #[test]
+    fn parse_policy_with_a_single_statement_and_not_action() {
+        let actual = parse_policy(r#"{"Statement": {"Effect": "Allow", "NotAction": "iam:*", "Resource": ["*"]}}"#).unwrap();
+
+        assert_eq!(
+            actual,
+            vec![Statement {
+                effect: Effect::Allow,
+                actions: vec![],
+                not_actions: vec!["iam:*".to_string()],
+                everywhere: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_policy_reports_invalid_documents() {
+        assert_eq!(parse_policy(r#"{"Version": "2012-10-17"}"#).unwrap_err(), "expected a policy document with a `Statement`");
+        assert_eq!(parse_policy(r#"{"Statement": [{"Effect": "Allow"}]}"#).unwrap_err(), "expected every statement to have an `Action` or `NotAction`");
+        assert_eq!(parse_policy(r#"{"Statement": [}"#).unwrap_err(), "expected value at line 1 column 16");
+    }
+
+    #[test]
+    fn find_calls_not_allowed_by_the_policies() {
+        let calls = vec![
+            call("sqs", "send_message"),
+            call("s3", "get_object"),
+            call("s3", "get_object_acl"),
+            call("sqs", "delete_message"),
+            call("dynamodb", "execute_statement"),
+        ];
+
+        let actual: Vec<String> = find_calls_not_allowed(&calls, policies(POLICY))
+            .into_iter()
+            .map(|f| match f.usage {
+                Usage::Iam(iam) => iam.action,
+                _ => panic!("expected only IAM findings"),
+            })
+            .collect();
+
+        // the deny with a condition might not apply, and one of the PartiQL actions is enough for `execute_statement`
+        assert_eq!(actual, vec!["s3:GetObjectAcl", "sqs:DeleteMessage"]);
+    }
+}
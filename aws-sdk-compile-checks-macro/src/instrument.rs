@@ -0,0 +1,82 @@
+use syn::spanned::Spanned;
+use syn::visit_mut::{self, VisitMut};
+use syn::{Block, Expr};
+
+use crate::findings::is_same_location;
+use crate::visitor::{operation_of_send, SdkCall};
+
+const SPAN_NAME: &str = "aws_sdk_call";
+
+/// Wraps every `send().await` of a call we know the SDK of in a `tracing` span with the SDK and the operation, e.g.
+/// `::tracing::Instrument::instrument(client.receive_message().send(), ::tracing::info_span!("aws_sdk_call", sdk = "sqs", operation = "receive_message")).await`.
+/// The crate that is being compiled has to depend on `tracing` itself
+pub(crate) fn instrument_sends(block: &mut Block, calls: &[SdkCall]) {
+    SendInstrumenter { calls }.visit_block_mut(block);
+}
+
+struct SendInstrumenter<'a> {
+    calls: &'a [SdkCall],
+}
+
+impl VisitMut for SendInstrumenter<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        visit_mut::visit_expr_mut(self, expr);
+        let Expr::Await(awaited) = expr else {
+            return;
+        };
+        let Some(call) = operation_of_send(&awaited.base).and_then(|operation| self.calls.iter().find(|c| is_same_location(c.span, operation.span())))
+        else {
+            return;
+        };
+        let base = &awaited.base;
+        let (sdk, operation) = (&call.sdk, &call.operation);
+        *expr = syn::parse_quote_spanned!(awaited.span()=>
+            ::tracing::Instrument::instrument(#base, ::tracing::info_span!(#SPAN_NAME, sdk = #sdk, operation = #operation)).await
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use quote::ToTokens;
+    use syn::ItemFn;
+
+    use super::*;
+    use crate::required_properties::create_required_props_map;
+    use crate::visitor::MethodVisitor;
+
+    fn instrumented(function: &str) -> String {
+        let mut item: ItemFn = syn::parse_str(function).unwrap();
+        let calls = MethodVisitor::new(&item, create_required_props_map(), &HashMap::new()).check(vec![], &[]).calls;
+        instrument_sends(&mut item.block, &calls);
+        item.block.to_token_stream().to_string()
+    }
+
+    #[test]
+    fn instrument_sends_wraps_the_sends_of_known_calls() {
+        let block = instrumented(
+            "async fn f(client: aws_sdk_sqs::Client) {
+                let received = client.receive_message().queue_url(url).send().await;
+            }",
+        );
+
+        assert_eq!(
+            block,
+            "{ let received = :: tracing :: Instrument :: instrument (client . receive_message () . queue_url (url) . send () , :: tracing :: info_span ! (\"aws_sdk_call\" , sdk = \"sqs\" , operation = \"receive_message\")) . await ; }"
+        );
+    }
+
+    #[test]
+    fn instrument_sends_keeps_other_awaits() {
+        let block = instrumented(
+            "async fn f(client: Wrapper) {
+                let queues = client.fetch().send().await;
+                tokio::time::sleep(duration).await;
+            }",
+        );
+
+        assert!(!block.contains("tracing"));
+    }
+}
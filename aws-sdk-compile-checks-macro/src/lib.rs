@@ -1,18 +1,43 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(feature = "nightly-diagnostics", feature(proc_macro_diagnostic))]
 use proc_macro::TokenStream;
 
+use aws_sdk_compile_checks_data::required_properties::data_version as describe_data_version;
 use quote::quote;
-use syn::{parse_macro_input, ItemFn};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Item, ItemFn, TraitItemFn};
 
 use crate::attributes::Attributes;
-use crate::findings::UsageFinds;
+use crate::diagnostics::Diagnostic;
 use crate::required_properties::{create_required_props_map, valid_sdks};
 
+mod assertions;
 mod attributes;
+mod availability;
+mod baseline;
+mod builders;
+mod datasets;
+mod denied;
+mod diagnostics;
+mod export;
+mod iam;
+mod instrument;
+mod literals;
+mod manifests;
+mod pipeline;
+mod profiles;
+mod registered;
+mod renames;
 mod required_properties;
+mod resources;
+mod runtime_checks;
+mod versions;
 mod visitor;
 mod findings;
 
+/// Set in the environment of the proc macros that rust-analyzer expands, which it does after every keystroke
+const IDE_ENV_VARS: [&str; 3] = ["RUST_ANALYZER_INTERNALS_DO_NOT_USE", "RUST_ANALYZER", "IS_RA"];
+
 /// Adding this attribute to a function or method will make it check for AWS SDK calls that are missing required properties
 /// (properties that, if missing, would cause a panic at runtime)
 /// Example:
@@ -28,33 +53,191 @@ mod findings;
 #[proc_macro_attribute]
 pub fn required_props(attrs: TokenStream, input: TokenStream) -> TokenStream {
     let attributes: Attributes = parse_macro_input!(attrs);
-    let item: ItemFn = parse_macro_input!(input);
-    let required_props = create_required_props_map();
+    let attributes = match attributes.profile.clone() {
+        Some(profile) => {
+            let profile_attributes = profiles::profile_arguments(&profile.value()).and_then(|arguments| syn::parse_str(arguments).map_err(|e| e.to_string()));
+            match profile_attributes {
+                Ok(profile_attributes) => attributes.with_profile(profile_attributes),
+                Err(e) => return Diagnostic::error(profile.span(), e).emit().into(),
+            }
+        }
+        None => attributes,
+    };
+    if let Some(min_data) = &attributes.min_data {
+        if let Some(stale) = versions::stale_data(&min_data.value()) {
+            return Diagnostic::error(min_data.span(), stale)
+                .help("update `aws-sdk-compile-checks-macro` (or the `aws-sdk-compile-checks-data` it uses) to a version with newer data")
+                .emit()
+                .into();
+        }
+    }
+    let mut item: ItemFn = match syn::parse(input.clone()) {
+        Ok(item) => item,
+        Err(_) => return unsupported_item(input.into()).into(),
+    };
+    // the attribute and item are still parsed above, so the IDE shows mistakes in them, the checks are left to `cargo build`
+    if skip_checks() {
+        return quote!(#item).into();
+    }
+    let datasets = match datasets::datasets() {
+        Ok(datasets) => datasets,
+        Err(e) => return Diagnostic::error(attributes.span, format!("could not load the registered datasets: {}", e)).emit().into(),
+    };
+    let iam_policies = match iam::iam_policies() {
+        Ok(policies) => policies,
+        Err(e) => return Diagnostic::error(attributes.span, format!("could not load the IAM policies: {}", e)).emit().into(),
+    };
+    let known_resources = match resources::known_resources() {
+        Ok(resources) => resources,
+        Err(e) => return Diagnostic::error(attributes.span, format!("could not load the outputs of your infrastructure: {}", e)).emit().into(),
+    };
+    let baseline = match baseline::baseline() {
+        Ok(baseline) => baseline,
+        Err(e) => return Diagnostic::error(attributes.span, format!("could not load the baseline: {}", e)).emit().into(),
+    };
+    let builder_rules = match builders::builder_rules() {
+        Ok(rules) => rules,
+        Err(e) => return Diagnostic::error(attributes.span, format!("could not load the required methods of your builders: {}", e)).emit().into(),
+    };
+    let denied_sdks = match denied::denied_sdks() {
+        Ok(denied) => denied,
+        Err(e) => return Diagnostic::error(attributes.span, format!("could not load the denied SDKs: {}", e)).emit().into(),
+    };
+    let required_props = create_required_props_map().with_datasets(datasets);
 
-    let Attributes { sdks, span } = attributes;
-    match valid_sdks(&required_props, &sdks) {
-        Ok(_) => {}
+    let sdks_without_data = match valid_sdks(&required_props, &attributes.all_sdks()) {
+        Ok(sdks) => sdks,
         Err(e) => {
-            return syn::Error::new(
-                span,
+            return Diagnostic::error(
+                attributes.span,
                 format!("some of the SDKs you specified do not exist in our list of supported SDKs: {}", e),
             )
-            .to_compile_error()
+            .note(format!("checked against {}", describe_data_version()))
+            .emit()
             .into();
         }
-    }
+    };
 
-    let visitor = visitor::MethodVisitor::new(&item, required_props);
-    let improper = visitor.find_improper_usages(sdks);
+    let sources = pipeline::Sources {
+        required_props,
+        builder_rules,
+        denied_sdks,
+        known_resources,
+        iam_policies,
+    };
+    let checked = match pipeline::check_function(&mut item, &attributes, sources) {
+        Ok(checked) => checked,
+        Err(e) => return Diagnostic::error(attributes.span, format!("could not load the configured regions: {}", e)).emit().into(),
+    };
 
-    let errors: Vec<proc_macro2::TokenStream> = improper
+    let mut diagnostics: Vec<Diagnostic> = sdks_without_data
         .into_iter()
-        .map(UsageFinds::into_compile_error)
+        .map(|sdk| {
+            Diagnostic::warning(attributes.span, format!("no required-props data for {}; calls will not be checked", sdk))
+                .code("sdk-without-data")
+                .note(format!("checked against {}", describe_data_version()))
+        })
+        .chain(
+            checked
+                .findings
+                .into_iter()
+                .map(|finding| {
+                    // the attribute with the SDK added, e.g. for an editor that reads the exported findings to apply
+                    let attribute_with_sdk = finding.suggested_sdk().zip(attributes.span.source_text()).and_then(|(sdk, attribute)| attributes::with_sdk(&attribute, sdk));
+                    match attribute_with_sdk {
+                        Some(attribute) => finding.into_diagnostic().suggestion(attributes.span, attribute),
+                        None => finding.into_diagnostic(),
+                    }
+                })
+                .map(|diagnostic| match checked.generated_spans {
+                    true => diagnostic.note(format!(
+                        "in `{}`, a function generated by a macro, so this points to where the macro got the tokens of the call",
+                        item.sig.ident
+                    )),
+                    false => diagnostic,
+                })
+                .map(|diagnostic| match baseline {
+                    Some(baseline) => baseline::apply_baseline(diagnostic, baseline),
+                    None => diagnostic,
+                })
+                .map(diagnostics::apply_migration_mode),
+        )
         .collect();
+    if let Some(export_failure) = export::export_findings(&diagnostics) {
+        diagnostics.push(export_failure);
+    }
+    if let Some(export_failure) = export::export_calls(&checked.calls) {
+        diagnostics.push(export_failure);
+    }
+    if let Some(export_failure) = export::export_inventory(&checked.calls) {
+        diagnostics.push(export_failure);
+    }
+    // the exports above have every finding, only the output of the compiler is limited
+    if let Some(max_errors) = attributes.max_errors {
+        diagnostics = diagnostics::limit_errors(diagnostics, max_errors);
+    }
+    let diagnostics: Vec<proc_macro2::TokenStream> = diagnostics.into_iter().map(Diagnostic::emit).collect();
+    let assertions = checked.assertions;
+    let tracked_inputs = registered::tracked_inputs();
+
+    if diagnostics.is_empty() && assertions.is_empty() && tracked_inputs.is_empty() {
+        return quote!(#item).into();
+    }
 
+    // diagnostics go inside the body, because warnings on stable need statement position, and so do the tracked inputs, to work for methods as well
+    let ItemFn { attrs, vis, sig, block } = item;
     quote!(
-        #(#errors)*
-        #item
+        #(#attrs)*
+        #vis #sig {
+            #tracked_inputs
+            #(#diagnostics)*
+            #assertions
+            #block
+        }
     )
     .into()
 }
+
+/// Whether the macro is expanded by an IDE (or the `skip-checks` feature is enabled), where it has to be fast more than thorough
+fn skip_checks() -> bool {
+    cfg!(feature = "skip-checks") || IDE_ENV_VARS.iter().any(|var| std::env::var_os(var).is_some())
+}
+
+/// An error for items we cannot check, followed by the item itself, so the rest of the crate still compiles
+fn unsupported_item(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let (span, kind) = match syn::parse2::<Item>(input.clone()) {
+        Ok(Item::Impl(item)) => (item.impl_token.span, "an `impl` block"),
+        Ok(Item::Trait(item)) => (item.trait_token.span, "a trait"),
+        Ok(Item::Mod(item)) => (item.mod_token.span, "a module"),
+        Ok(Item::Struct(item)) => (item.struct_token.span, "a struct"),
+        Ok(Item::Enum(item)) => (item.enum_token.span, "an enum"),
+        Ok(item) => (item.span(), "this item"),
+        Err(_) => match syn::parse2::<TraitItemFn>(input.clone()) {
+            Ok(item) => (item.sig.ident.span(), "a trait method without a body"),
+            Err(_) => (input.span(), "this item"),
+        },
+    };
+    let error = Diagnostic::error(span, format!("`required_props` can only be added to functions and methods, not to {}", kind))
+        .code("unsupported-item")
+        .help("add it to the functions or methods that make the AWS SDK calls")
+        .emit();
+    quote!(
+        #error
+        #input
+    )
+}
+
+/// Expands to a string literal describing the snapshot of AWS SDK data that `required_props` checks against,
+/// e.g. "aws-sdk data 2024-03, SDK 1.18.0".
+/// Useful to judge whether a surprising finding might be caused by stale data.
+/// ```rust
+/// use aws_sdk_compile_checks_macro::data_version;
+///
+/// assert!(data_version!().starts_with("aws-sdk data"));
+/// ```
+#[proc_macro]
+pub fn data_version(input: TokenStream) -> TokenStream {
+    let _: syn::parse::Nothing = parse_macro_input!(input);
+    let version = describe_data_version();
+    quote!(#version).into()
+}
@@ -0,0 +1,278 @@
+/// Suffix of the setters that take an ARN, e.g. `topic_arn`
+pub(crate) const ARN_SUFFIX: &str = "_arn";
+
+/// The setter of the URL of an SQS queue, which is not its name or ARN
+pub(crate) const QUEUE_URL_SETTER: &str = "queue_url";
+
+/// Setters of the resources a call works on, which usually differ per environment, so their value should come from the config
+pub(crate) const HARDCODED_RESOURCE_SETTERS: [&str; 4] = [QUEUE_URL_SETTER, "table_name", BUCKET_SETTER, "function_name"];
+
+/// The setter of the bucket on S3 operations
+pub(crate) const BUCKET_SETTER: &str = "bucket";
+/// The setter of the key of an object on S3 operations
+pub(crate) const KEY_SETTER: &str = "key";
+/// S3 operations on a single object, where a key with a leading `/` ends up as an object in a folder without a name
+pub(crate) const OBJECT_OPERATIONS: [&str; 4] = ["delete_object", "get_object", "head_object", "put_object"];
+
+/// Setters that take a region, `region` on the config loaders and `region_name` on types like the DynamoDB `Replica`
+pub(crate) const REGION_SETTERS: [&str; 2] = [REGION_SETTER, "region_name"];
+/// The setter of the config loaders and configs, the region the calls of the function go to
+pub(crate) const REGION_SETTER: &str = "region";
+
+/// The regions of all partitions, plus `aws-global`, which some global services (like IAM) accept
+const REGIONS: [&str; 40] = [
+    "af-south-1",
+    "ap-east-1",
+    "ap-northeast-1",
+    "ap-northeast-2",
+    "ap-northeast-3",
+    "ap-south-1",
+    "ap-south-2",
+    "ap-southeast-1",
+    "ap-southeast-2",
+    "ap-southeast-3",
+    "ap-southeast-4",
+    "ap-southeast-5",
+    "ap-southeast-7",
+    "aws-global",
+    "ca-central-1",
+    "ca-west-1",
+    "cn-north-1",
+    "cn-northwest-1",
+    "eu-central-1",
+    "eu-central-2",
+    "eu-north-1",
+    "eu-south-1",
+    "eu-south-2",
+    "eu-west-1",
+    "eu-west-2",
+    "eu-west-3",
+    "il-central-1",
+    "me-central-1",
+    "me-south-1",
+    "mx-central-1",
+    "sa-east-1",
+    "us-east-1",
+    "us-east-2",
+    "us-gov-east-1",
+    "us-gov-west-1",
+    "us-iso-east-1",
+    "us-iso-west-1",
+    "us-isob-east-1",
+    "us-west-1",
+    "us-west-2",
+];
+
+/// Suggestions further away than this are more likely to be confusing than helpful
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// The partitions AWS has, the second part of every ARN
+const PARTITIONS: [&str; 7] = ["aws", "aws-cn", "aws-us-gov", "aws-iso", "aws-iso-b", "aws-iso-e", "aws-iso-f"];
+
+/// Checks the basic shape of an ARN, `arn:partition:service:region:account-id:resource`, returning what is wrong with it.
+/// The region and account are empty for some services (e.g. S3) and the resource can contain `:`, so only obviously malformed values fail
+pub(crate) fn validate_arn(value: &str) -> Result<(), String> {
+    let parts: Vec<&str> = value.splitn(6, ':').collect();
+    let [prefix, partition, service, _region, account, resource] = parts.as_slice() else {
+        return Err(format!(
+            "an ARN has six parts separated by `:` (`arn:partition:service:region:account-id:resource`), found {}",
+            parts.len()
+        ));
+    };
+    if *prefix != "arn" {
+        return Err("an ARN starts with `arn:`".to_string());
+    }
+    if !PARTITIONS.contains(partition) {
+        return Err(format!("`{}` is not an AWS partition, e.g. `aws` or `aws-cn`", partition));
+    }
+    if service.is_empty() {
+        return Err("the service of the ARN is missing".to_string());
+    }
+    // managed resources, like the policies of AWS, have `aws` as their account
+    if !account.is_empty() && *account != "aws" && !(account.len() == 12 && account.chars().all(|c| c.is_ascii_digit())) {
+        return Err(format!("`{}` is not an account id, which has 12 digits", account));
+    }
+    if resource.is_empty() {
+        return Err("the resource of the ARN is missing".to_string());
+    }
+    Ok(())
+}
+
+
+/// Checks whether a region exists, returning the closest known region (if it is close enough) when it does not.
+/// FIPS endpoints (like `fips-us-east-1` or `us-east-1-fips`) are accepted when the region itself exists
+pub(crate) fn validate_region(value: &str) -> Result<(), Option<&'static str>> {
+    let region = value.strip_prefix("fips-").or_else(|| value.strip_suffix("-fips")).unwrap_or(value);
+    if REGIONS.contains(&region) {
+        return Ok(());
+    }
+    let lowercase = value.to_lowercase();
+    let suggestion = REGIONS
+        .iter()
+        .map(|known| (known, edit_distance(&lowercase, known)))
+        .filter(|(_, distance)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(known, _)| *known);
+    Err(suggestion)
+}
+
+/// Levenshtein distance, the number of single character insertions, deletions and substitutions to go from one value to the other
+pub(crate) fn edit_distance(first: &str, second: &str) -> usize {
+    let second: Vec<char> = second.chars().collect();
+    let mut previous: Vec<usize> = (0..=second.len()).collect();
+    for (i, first_char) in first.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, second_char) in second.iter().enumerate() {
+            let substitution = previous[j] + usize::from(first_char != *second_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[second.len()]
+}
+
+/// Checks whether a queue URL looks like `https://sqs.<region>.amazonaws.com/<account-id>/<queue-name>`, returning what is wrong with it
+pub(crate) fn validate_queue_url(value: &str) -> Result<(), String> {
+    if value.starts_with("arn:") {
+        return Err("this is the ARN of the queue, while the call needs its URL".to_string());
+    }
+    let Some((scheme, rest)) = value.split_once("://") else {
+        return Err("this looks like the name of the queue, while the call needs its URL".to_string());
+    };
+    if scheme != "https" {
+        return Err(format!("queue URLs use `https`, not `{}`", scheme));
+    }
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let region = host
+        .strip_prefix("sqs.")
+        .and_then(|h| h.strip_suffix(".amazonaws.com").or_else(|| h.strip_suffix(".amazonaws.com.cn")));
+    if region.is_none_or(|r| validate_region(r).is_err()) {
+        return Err(format!("`{}` is not an SQS endpoint, like `sqs.eu-west-1.amazonaws.com`", host));
+    }
+    match path.split('/').collect::<Vec<_>>().as_slice() {
+        [account, name] if account.len() == 12 && account.chars().all(|c| c.is_ascii_digit()) && !name.is_empty() => Ok(()),
+        _ => Err(format!("the path of a queue URL is `/<account-id>/<queue-name>`, found `/{}`", path)),
+    }
+}
+
+/// Checks a bucket name against the naming rules of S3, returning the first rule it breaks.
+/// Access point ARNs are accepted in place of a bucket, so those are not checked
+pub(crate) fn validate_bucket_name(value: &str) -> Result<(), String> {
+    if value.starts_with("arn:") {
+        return Ok(());
+    }
+    if !(3..=63).contains(&value.len()) {
+        return Err(format!("bucket names are between 3 and 63 characters long, this one has {}", value.len()));
+    }
+    if let Some(invalid) = value.chars().find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '.' || *c == '-')) {
+        return Err(format!("bucket names only contain lowercase letters, numbers, dots and hyphens, not `{}`", invalid));
+    }
+    if !value.starts_with(|c: char| c.is_ascii_alphanumeric()) || !value.ends_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err("bucket names begin and end with a letter or number".to_string());
+    }
+    if value.contains("..") {
+        return Err("bucket names do not contain two adjacent dots".to_string());
+    }
+    if value.parse::<std::net::Ipv4Addr>().is_ok() {
+        return Err("bucket names are not formatted as an IP address".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_arn_accepts_arns_with_and_without_region_and_account() {
+        assert!(validate_arn("arn:aws:sns:eu-west-1:123456789012:topic").is_ok());
+        assert!(validate_arn("arn:aws:s3:::bucket/key:with:colons").is_ok());
+        assert!(validate_arn("arn:aws:iam::aws:policy/ReadOnlyAccess").is_ok());
+        assert!(validate_arn("arn:aws-cn:lambda:cn-north-1:123456789012:function:name").is_ok());
+    }
+
+    #[test]
+    fn validate_arn_explains_what_is_wrong() {
+        assert_eq!(
+            validate_arn("sns:eu-west-1:123456789012:topic").unwrap_err(),
+            "an ARN has six parts separated by `:` (`arn:partition:service:region:account-id:resource`), found 4"
+        );
+        assert_eq!(validate_arn("amz:aws:sns:eu-west-1:123456789012:topic").unwrap_err(), "an ARN starts with `arn:`");
+        assert_eq!(validate_arn("arn:amazon:sns:eu-west-1:123456789012:topic").unwrap_err(), "`amazon` is not an AWS partition, e.g. `aws` or `aws-cn`");
+        assert_eq!(validate_arn("arn:aws:sns:eu-west-1:1234:topic").unwrap_err(), "`1234` is not an account id, which has 12 digits");
+        assert_eq!(validate_arn("arn:aws:sns:eu-west-1:123456789012:").unwrap_err(), "the resource of the ARN is missing");
+    }
+
+    #[test]
+    fn validate_region_accepts_known_regions_and_their_fips_variants() {
+        assert!(validate_region("eu-west-1").is_ok());
+        assert!(validate_region("us-gov-west-1").is_ok());
+        assert!(validate_region("aws-global").is_ok());
+        assert!(validate_region("fips-us-east-1").is_ok());
+        assert!(validate_region("us-east-1-fips").is_ok());
+    }
+
+    #[test]
+    fn validate_region_suggests_the_closest_region() {
+        assert_eq!(validate_region("eu-west1").unwrap_err(), Some("eu-west-1"));
+        assert_eq!(validate_region("EU-WEST-1").unwrap_err(), Some("eu-west-1"));
+        assert_eq!(validate_region("us-east").unwrap_err(), Some("us-east-1"));
+        assert_eq!(validate_region("ireland").unwrap_err(), None);
+    }
+
+    #[test]
+    fn edit_distance_counts_insertions_deletions_and_substitutions() {
+        assert_eq!(edit_distance("eu-west-1", "eu-west-1"), 0);
+        assert_eq!(edit_distance("eu-west1", "eu-west-1"), 1);
+        assert_eq!(edit_distance("eu-wset-1", "eu-west-1"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn validate_queue_url_accepts_queue_urls_of_all_partitions() {
+        assert!(validate_queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/queue").is_ok());
+        assert!(validate_queue_url("https://sqs.us-east-1.amazonaws.com/123456789012/queue.fifo").is_ok());
+        assert!(validate_queue_url("https://sqs.cn-north-1.amazonaws.com.cn/123456789012/queue").is_ok());
+    }
+
+    #[test]
+    fn validate_queue_url_explains_what_is_wrong() {
+        assert_eq!(validate_queue_url("queue").unwrap_err(), "this looks like the name of the queue, while the call needs its URL");
+        assert_eq!(
+            validate_queue_url("arn:aws:sqs:eu-west-1:123456789012:queue").unwrap_err(),
+            "this is the ARN of the queue, while the call needs its URL"
+        );
+        assert_eq!(validate_queue_url("http://sqs.eu-west-1.amazonaws.com/123456789012/queue").unwrap_err(), "queue URLs use `https`, not `http`");
+        assert_eq!(
+            validate_queue_url("https://sqs.eu-west1.amazonaws.com/123456789012/queue").unwrap_err(),
+            "`sqs.eu-west1.amazonaws.com` is not an SQS endpoint, like `sqs.eu-west-1.amazonaws.com`"
+        );
+        assert_eq!(
+            validate_queue_url("https://sqs.eu-west-1.amazonaws.com/queue").unwrap_err(),
+            "the path of a queue URL is `/<account-id>/<queue-name>`, found `/queue`"
+        );
+    }
+
+    #[test]
+    fn validate_bucket_name_accepts_valid_names_and_access_points() {
+        assert!(validate_bucket_name("my-bucket").is_ok());
+        assert!(validate_bucket_name("logs.example.com").is_ok());
+        assert!(validate_bucket_name("arn:aws:s3:eu-west-1:123456789012:accesspoint/my-access-point").is_ok());
+    }
+
+    #[test]
+    fn validate_bucket_name_explains_what_is_wrong() {
+        assert_eq!(validate_bucket_name("ab").unwrap_err(), "bucket names are between 3 and 63 characters long, this one has 2");
+        assert_eq!(
+            validate_bucket_name("My-Bucket").unwrap_err(),
+            "bucket names only contain lowercase letters, numbers, dots and hyphens, not `M`"
+        );
+        assert_eq!(
+            validate_bucket_name("my_bucket").unwrap_err(),
+            "bucket names only contain lowercase letters, numbers, dots and hyphens, not `_`"
+        );
+        assert_eq!(validate_bucket_name("-bucket").unwrap_err(), "bucket names begin and end with a letter or number");
+        assert_eq!(validate_bucket_name("my..bucket").unwrap_err(), "bucket names do not contain two adjacent dots");
+        assert_eq!(validate_bucket_name("192.168.5.4").unwrap_err(), "bucket names are not formatted as an IP address");
+    }
+}
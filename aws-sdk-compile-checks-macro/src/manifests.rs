@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+
+use toml::Table;
+
+use crate::registered::track_file;
+
+const MANIFEST: &str = "Cargo.toml";
+const WORKSPACE_KEY: &str = "workspace";
+
+/// A `Cargo.toml`, parsed
+pub(crate) struct Manifest {
+    pub(crate) path: PathBuf,
+    pub(crate) table: Table,
+}
+
+/// The manifest of the crate that is being compiled, and that of its workspace when that is another one
+#[derive(Default)]
+pub(crate) struct Manifests {
+    pub(crate) package: Option<Manifest>,
+    workspace: Option<Manifest>,
+}
+
+impl Manifests {
+    /// The manifest with the `[workspace]` of the crate, which is that of the crate itself when it is the root of its workspace (or not in one)
+    pub(crate) fn workspace(&self) -> Option<&Manifest> {
+        self.workspace.as_ref().or(self.package.as_ref())
+    }
+}
+
+/// The manifests of the crate that is being compiled and of its workspace, which, like cargo, is the first directory above the crate
+/// with a `[workspace]` in its manifest. The manifests we read are tracked (see [`track_file`]).
+/// Manifests we cannot read or parse are left out, as are all of them outside of a build, because cargo would have rejected them
+pub(crate) fn manifests() -> Manifests {
+    let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") else {
+        return Manifests::default();
+    };
+    let mut paths = Path::new(&manifest_dir).ancestors().map(|dir| dir.join(MANIFEST));
+    let package = paths.next().and_then(read_manifest);
+    let workspace = match &package {
+        Some(package) if package.table.contains_key(WORKSPACE_KEY) => None,
+        _ => paths.filter_map(read_manifest).find(|manifest| manifest.table.contains_key(WORKSPACE_KEY)),
+    };
+    Manifests { package, workspace }
+}
+
+fn read_manifest(path: PathBuf) -> Option<Manifest> {
+    let content = std::fs::read_to_string(&path).ok()?;
+    track_file(path.clone());
+    let table = toml::from_str(&content).ok()?;
+    Some(Manifest { path, table })
+}
+
+/// The table at a path of keys separated by `.`, e.g. `package.metadata`, or `None` when there is no table there
+pub(crate) fn table_at<'a>(table: &'a Table, path: &str) -> Option<&'a Table> {
+    path.split('.').try_fold(table, |table, key| table.get(key)?.as_table())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifests_of_a_member_stop_at_the_workspace() {
+        let actual = manifests();
+
+        let member = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(actual.package.map(|m| m.path), Some(member.join(MANIFEST)));
+        assert_eq!(actual.workspace.map(|m| m.path), member.parent().map(|dir| dir.join(MANIFEST)));
+    }
+
+    #[test]
+    fn table_at_follows_dotted_keys() {
+        let manifest: Table = toml::from_str("[package]\nname = \"orders\"\nmetadata.checks = { profiles = {} }\n").unwrap();
+
+        assert!(table_at(&manifest, "package.metadata.checks.profiles").is_some_and(Table::is_empty));
+        assert!(table_at(&manifest, "package.name").is_none());
+        assert!(table_at(&manifest, "workspace.metadata").is_none());
+    }
+}
@@ -0,0 +1,82 @@
+use proc_macro2::TokenStream;
+use syn::ItemFn;
+
+use crate::attributes::Attributes;
+use crate::builders::BuilderRule;
+use crate::denied::DeniedSdk;
+use crate::findings::UsageFinds;
+use crate::iam::IamPolicies;
+use crate::required_properties::RequiredPropertiesMap;
+use crate::resources::KnownResources;
+use crate::visitor::{MethodVisitor, SdkCall};
+use crate::{assertions, availability, denied, findings, iam, instrument, resources, runtime_checks, visitor};
+
+/// What the calls of a function are checked against besides its attributes.
+/// The macro loads these from the files and environment variables of the crate, the fuzz targets leave them empty
+pub(crate) struct Sources {
+    pub(crate) required_props: RequiredPropertiesMap,
+    pub(crate) builder_rules: &'static [BuilderRule],
+    pub(crate) denied_sdks: &'static [DeniedSdk],
+    pub(crate) known_resources: Option<&'static KnownResources>,
+    pub(crate) iam_policies: Option<&'static IamPolicies>,
+}
+
+/// A function after its checks
+pub(crate) struct CheckedFunction {
+    pub(crate) findings: Vec<UsageFinds>, // without the warnings the attributes did not ask for, and without duplicates
+    pub(crate) calls: Vec<SdkCall>,
+    pub(crate) assertions: TokenStream, // empty unless the attributes ask for `assert_setters`
+    pub(crate) generated_spans: bool,   // whether the function was generated by a macro, see `MethodVisitor::has_generated_spans`
+}
+
+/// Everything `required_props` does with a function before it emits the diagnostics: it checks the calls and, when the attributes ask for it,
+/// instruments the sends and adds runtime checks of the builders passed to the function.
+/// Fails when the configured regions do not exist
+pub(crate) fn check_function(item: &mut ItemFn, attributes: &Attributes, sources: Sources) -> Result<CheckedFunction, String> {
+    let visitor = MethodVisitor::new(item, sources.required_props, &attributes.clients)
+        .with_receivers(&attributes.only_clients, &attributes.ignore_clients)
+        .with_builder_rules(sources.builder_rules)
+        .with_assumed_sdk(attributes.assume_sdk.clone());
+    let cfg_gated = visitor::cfg_gated_code(item);
+    let mut checked = visitor.check(attributes.sdks.clone(), &attributes.policies);
+    checked.findings.extend(visitor.find_values_above_maximum(&attributes.max_values));
+    checked.findings.extend(visitor.find_missing_builder_props());
+    checked.findings.extend(visitor.find_hardcoded_resources());
+    checked.findings.extend(visitor.find_unused_sdks(&attributes.sdks, &checked.sdks, &item.sig.ident, attributes.span));
+    let regions = availability::target_regions(visitor.region_literals())?;
+    checked.findings.extend(availability::find_unavailable_calls(&checked.calls, &regions));
+    checked.findings.extend(denied::find_denied_sdks(&checked.calls, visitor.client_sdks(), sources.denied_sdks, attributes.span));
+    if let Some(known_resources) = sources.known_resources {
+        checked.findings.extend(resources::find_unknown_resources(visitor.string_literals_of(&resources::RESOURCE_SETTERS), known_resources));
+    }
+    if let Some(policies) = sources.iam_policies {
+        checked.findings.extend(iam::find_calls_not_allowed(&checked.calls, policies));
+    }
+    let assertions = if attributes.assert_setters.unwrap_or_default() {
+        assertions::setter_assertions(&checked.chains)
+    } else {
+        TokenStream::new()
+    };
+    let findings = checked
+        .findings
+        .into_iter()
+        .filter(|f| f.opt_in_warning().is_none_or(|w| attributes.warnings.contains(&w)))
+        .filter(|f| attributes.recommend.unwrap_or_default() || !f.is_recommendation())
+        .collect();
+    // when the function is generated, different calls can share a location, so we cannot tell duplicates apart
+    let generated_spans = visitor.has_generated_spans(&item.sig);
+    let findings = if generated_spans { findings } else { findings::deduplicate(findings, &cfg_gated) };
+
+    if attributes.instrument.unwrap_or_default() {
+        instrument::instrument_sends(&mut item.block, &checked.calls);
+    }
+    if attributes.runtime_checks.unwrap_or_default() {
+        runtime_checks::add_runtime_checks(item, &visitor.passed_builders());
+    }
+    Ok(CheckedFunction {
+        findings,
+        calls: checked.calls,
+        assertions,
+        generated_spans,
+    })
+}
@@ -0,0 +1,137 @@
+use std::sync::OnceLock;
+
+use toml::Table;
+
+use crate::attributes::Attributes;
+use crate::manifests::{manifests, table_at};
+
+/// The tables with named sets of attribute arguments, selected with `profile = "..."`, in the manifest of the crate or of its workspace.
+/// A profile of the crate wins over one of the workspace with the same name
+const PACKAGE_PROFILES_TABLE: &str = "package.metadata.aws-sdk-compile-checks.profiles";
+const WORKSPACE_PROFILES_TABLE: &str = "workspace.metadata.aws-sdk-compile-checks.profiles";
+const EXPECTED_PROFILE: &str = "expected `name = \"arguments\"`, e.g. `strict-prod = \"warn(unawaited), policy = encryption\"`";
+
+/// The arguments of the profile with this name, e.g. `warn(unawaited, unwrap), policy = encryption` for `strict-prod`
+pub(crate) fn profile_arguments(name: &str) -> Result<&'static str, String> {
+    let profiles = profiles().map_err(|e| format!("could not load the profiles: {}", e))?;
+    profiles.iter().find(|(profile, _)| profile == name).map(|(_, arguments)| arguments.as_str()).ok_or_else(|| {
+        let names: Vec<String> = profiles.iter().map(|(profile, _)| format!("`{}`", profile)).collect();
+        match names.is_empty() {
+            true => format!("there is no profile `{}`, define it in `[{}]` of your Cargo.toml", name, PACKAGE_PROFILES_TABLE),
+            false => format!("there is no profile `{}`, the profiles are {}", name, names.join(", ")),
+        }
+    })
+}
+
+/// The profiles in the manifests of the crate and its workspace, as (name, arguments).
+/// The manifests do not change during a build, so they are only read once
+fn profiles() -> Result<&'static [(String, String)], &'static str> {
+    static PROFILES: OnceLock<Result<Vec<(String, String)>, String>> = OnceLock::new();
+    PROFILES
+        .get_or_init(|| {
+            let manifests = manifests();
+            let mut profiles: Vec<(String, String)> = vec![];
+            for (manifest, table) in [(manifests.package.as_ref(), PACKAGE_PROFILES_TABLE), (manifests.workspace(), WORKSPACE_PROFILES_TABLE)] {
+                let Some(manifest) = manifest else {
+                    continue;
+                };
+                for (name, arguments) in parse_profiles(&manifest.table, &manifest.path.display().to_string(), table)? {
+                    if !profiles.iter().any(|(profile, _)| *profile == name) {
+                        profiles.push((name, arguments));
+                    }
+                }
+            }
+            Ok(profiles)
+        })
+        .as_ref()
+        .map(Vec::as_slice)
+        .map_err(String::as_str)
+}
+
+/// The `name = "arguments"` entries of the table, where the arguments are those of the attribute, checked when the manifest is read
+fn parse_profiles(manifest: &Table, file: &str, table: &str) -> Result<Vec<(String, String)>, String> {
+    let Some(profiles) = table_at(manifest, table) else {
+        return Ok(vec![]);
+    };
+    profiles
+        .iter()
+        .map(|(name, arguments)| {
+            let location = format!("{}: profile `{}`", file, name);
+            let arguments = arguments.as_str().ok_or_else(|| format!("{}: {}", location, EXPECTED_PROFILE))?;
+            let attributes: Attributes = syn::parse_str(arguments).map_err(|e| format!("{}: {}", location, e))?;
+            if attributes.profile.is_some() {
+                return Err(format!("{}: a profile cannot select another profile", location));
+            }
+            Ok((name.to_string(), arguments.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[package]
+name = "orders"
+
+# for production
+[package.metadata.aws-sdk-compile-checks.profiles]
+strict-prod = "warn(unawaited, unwrap), policy = encryption"
+
+"migration" = 'ignore_clients(legacy), min_data = "2024-06"'
+
+[workspace.metadata.aws-sdk-compile-checks]
+profiles.strict-prod = """
+recommend = true,
+instrument = true"""
+"#;
+
+    fn manifest(content: &str) -> Table {
+        toml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn parse_profiles_of_a_table() {
+        assert_eq!(
+            parse_profiles(&manifest(MANIFEST), "Cargo.toml", PACKAGE_PROFILES_TABLE).unwrap(),
+            vec![
+                ("migration".to_string(), "ignore_clients(legacy), min_data = \"2024-06\"".to_string()),
+                ("strict-prod".to_string(), "warn(unawaited, unwrap), policy = encryption".to_string())
+            ]
+        );
+        assert_eq!(
+            parse_profiles(&manifest(MANIFEST), "Cargo.toml", WORKSPACE_PROFILES_TABLE).unwrap(),
+            vec![("strict-prod".to_string(), "recommend = true,\ninstrument = true".to_string())]
+        );
+    }
+
+    #[test]
+    fn parse_profiles_reports_the_profile_with_invalid_arguments() {
+        let manifest = manifest("[package.metadata.aws-sdk-compile-checks.profiles]\nstrict-prod = \"warn(unawaited)\"\nloose = \"warn(everything)\"\n");
+
+        let actual = parse_profiles(&manifest, "Cargo.toml", PACKAGE_PROFILES_TABLE).unwrap_err();
+
+        assert!(actual.starts_with("Cargo.toml: profile `loose`: the allowed warnings are"));
+    }
+
+    #[test]
+    fn parse_profiles_rejects_arguments_that_are_not_a_string() {
+        let manifest = manifest("[package.metadata.aws-sdk-compile-checks.profiles]\nstrict-prod = [\"warn(unawaited)\"]\n");
+
+        assert_eq!(
+            parse_profiles(&manifest, "Cargo.toml", PACKAGE_PROFILES_TABLE).unwrap_err(),
+            format!("Cargo.toml: profile `strict-prod`: {}", EXPECTED_PROFILE)
+        );
+    }
+
+    #[test]
+    fn parse_profiles_rejects_nested_profiles() {
+        let manifest = manifest("[package.metadata.aws-sdk-compile-checks.profiles]\nnested = 'profile = \"other\"'\n");
+
+        assert_eq!(
+            parse_profiles(&manifest, "Cargo.toml", PACKAGE_PROFILES_TABLE).unwrap_err(),
+            "Cargo.toml: profile `nested`: a profile cannot select another profile"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Separates the files (or other registrations) in the value of an environment variable
+const SEPARATOR: char = ';';
+
+/// The environment variables and files the macro read while compiling the crate, see [`tracked_inputs`]
+static TRACKED: Mutex<TrackedInputs> = Mutex::new(TrackedInputs {
+    env_vars: BTreeSet::new(),
+    files: BTreeSet::new(),
+});
+
+struct TrackedInputs {
+    env_vars: BTreeSet<&'static str>,
+    files: BTreeSet<PathBuf>, // absolute, `include_bytes!` would look for relative paths next to the source file
+}
+
+/// A file registered with one of our environment variables
+pub(crate) struct RegisteredFile {
+    pub(crate) name: String, // as it was registered, e.g. `iam/lambda_role.json`
+    pub(crate) content: String,
+}
+
+/// The registrations in an environment variable, separated by `;`, e.g. `iam/lambda_role.json;iam/worker_role.json`.
+/// The variable and the files do not change during a build, so the callers keep what they make of them in a `OnceLock`
+pub(crate) fn registrations(env_var: &'static str) -> Vec<String> {
+    tracked_env_var(env_var).map(|value| split_registrations(&value.to_string_lossy())).unwrap_or_default()
+}
+
+/// The registrations in the value of an environment variable, without the empty ones
+pub(crate) fn split_registrations(value: &str) -> Vec<String> {
+    value.split(SEPARATOR).map(str::trim).filter(|r| !r.is_empty()).map(str::to_string).collect()
+}
+
+/// The files registered with an environment variable, see [`registrations`], read relative to the crate that is being compiled
+pub(crate) fn registered_files(env_var: &'static str) -> Result<Vec<RegisteredFile>, String> {
+    registrations(env_var)
+        .into_iter()
+        .map(|name| read_registered_file(&name).map(|content| RegisteredFile { name, content }))
+        .collect()
+}
+
+/// The content of a registered file, whose path is relative to the crate that is being compiled
+pub(crate) fn read_registered_file(file: &str) -> Result<String, String> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = Path::new(&manifest_dir).join(file);
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    track_file(path);
+    Ok(content)
+}
+
+/// The value of one of our environment variables, which the expansion then depends on, see [`tracked_inputs`]
+pub(crate) fn tracked_env_var(env_var: &'static str) -> Option<OsString> {
+    TRACKED.lock().unwrap_or_else(|e| e.into_inner()).env_vars.insert(env_var);
+    std::env::var_os(env_var)
+}
+
+/// Makes the expansion depend on a file the macro read
+pub(crate) fn track_file(path: PathBuf) {
+    TRACKED.lock().unwrap_or_else(|e| e.into_inner()).files.insert(path);
+}
+
+/// Cargo only knows about the inputs of a crate that rustc reports, and the macro reads its configuration itself.
+/// These statements make rustc report the environment variables and files the macro read, so cargo recompiles the crate when one of them changes.
+/// The loaders read them once (for the first annotated function), so every expansion mentions all of them
+pub(crate) fn tracked_inputs() -> TokenStream {
+    let tracked = TRACKED.lock().unwrap_or_else(|e| e.into_inner());
+    let env_vars = tracked.env_vars.iter();
+    let files = tracked.files.iter().map(|path| path.to_string_lossy());
+    quote!(
+        #(const _: ::std::option::Option<&str> = ::std::option_env!(#env_vars);)*
+        #(const _: &[u8] = ::std::include_bytes!(#files);)*
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracked_inputs_mention_the_env_vars_and_files_that_were_read() {
+        let _ = tracked_env_var("AWS_SDK_COMPILE_CHECKS_TRACKED_FOR_TESTS");
+        track_file(PathBuf::from("/orders/datasets/internal_props.csv"));
+
+        let actual = tracked_inputs().to_string();
+
+        assert!(actual.contains("option_env ! (\"AWS_SDK_COMPILE_CHECKS_TRACKED_FOR_TESTS\")"));
+        assert!(actual.contains("include_bytes ! (\"/orders/datasets/internal_props.csv\")"));
+    }
+}
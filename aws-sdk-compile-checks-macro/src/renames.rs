@@ -0,0 +1,126 @@
+use std::sync::OnceLock;
+
+use toml::{Table, Value};
+
+use crate::manifests::{manifests, table_at};
+
+/// The package name of the AWS SDK crates, e.g. `aws-sdk-sqs`
+const AWS_SDK_PACKAGE_PREFIX: &str = "aws-sdk-";
+const PACKAGE_KEY: &str = "package";
+const TARGET_KEY: &str = "target";
+/// The dependencies tables of a package, which can also be those of a target, like `[target.'cfg(unix)'.dependencies]`
+const DEPENDENCIES_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+const WORKSPACE_DEPENDENCIES_TABLE: &str = "workspace.dependencies";
+
+/// An AWS SDK dependency that is renamed in the manifest, e.g. `sqs = { package = "aws-sdk-sqs", ... }`
+#[derive(Debug, PartialEq)]
+pub(crate) struct RenamedSdk {
+    pub(crate) module: String, // the name in the code, e.g. `sqs` for `sqs::Client`
+    pub(crate) sdk: String,
+}
+
+/// The SDK of a module that is a renamed AWS SDK, e.g. `sqs` for `sqs::Client`
+pub(crate) fn sdk_of_renamed_module(module: &str) -> Option<String> {
+    renamed_sdks().iter().find(|r| r.module == module).map(|r| r.sdk.clone())
+}
+
+/// The module of an SDK when it is renamed, e.g. `sqs` for `sqs`, to use in the code we generate instead of `aws_sdk_sqs`
+pub(crate) fn renamed_module_of_sdk(sdk: &str) -> Option<&'static str> {
+    renamed_sdks().iter().find(|r| r.sdk == sdk).map(|r| r.module.as_str())
+}
+
+/// The renamed SDKs in the manifest of the crate that is being compiled, and in that of its workspace, for `workspace = true` dependencies.
+/// The manifests do not change during a build, so they are only read once. A manifest we cannot read has no renames
+fn renamed_sdks() -> &'static [RenamedSdk] {
+    static RENAMED: OnceLock<Vec<RenamedSdk>> = OnceLock::new();
+    RENAMED.get_or_init(|| {
+        let manifests = manifests();
+        parse_renamed_sdks(manifests.package.as_ref().map(|m| &m.table), manifests.workspace().map(|m| &m.table))
+    })
+}
+
+/// The dependencies with a `package` that is an AWS SDK, in the dependencies tables of the package and of the workspace,
+/// however the dependency is written, e.g. `sqs = { package = "aws-sdk-sqs" }`, `sqs.package = "aws-sdk-sqs"` or `[dependencies.sqs]`
+fn parse_renamed_sdks(package: Option<&Table>, workspace: Option<&Table>) -> Vec<RenamedSdk> {
+    let targets = package
+        .and_then(|p| p.get(TARGET_KEY))
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values().filter_map(Value::as_table));
+    let package_dependencies = package
+        .into_iter()
+        .chain(targets)
+        .flat_map(|table| DEPENDENCIES_TABLES.iter().filter_map(|name| table.get(*name)?.as_table()));
+    let workspace_dependencies = workspace.and_then(|w| table_at(w, WORKSPACE_DEPENDENCIES_TABLE));
+
+    package_dependencies
+        .chain(workspace_dependencies)
+        .flat_map(Table::iter)
+        .filter_map(|(name, dependency)| {
+            let sdk = dependency.get(PACKAGE_KEY)?.as_str()?.strip_prefix(AWS_SDK_PACKAGE_PREFIX)?;
+            let module = name.replace('-', "_");
+            (module != format!("aws_sdk_{}", sdk.replace('-', "_"))).then(|| RenamedSdk {
+                module,
+                sdk: sdk.replace('-', ""),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renamed(manifest: &str) -> Vec<RenamedSdk> {
+        let manifest: Table = toml::from_str(manifest).unwrap();
+        parse_renamed_sdks(Some(&manifest), Some(&manifest))
+    }
+
+    fn renamed_sdk(module: &str, sdk: &str) -> RenamedSdk {
+        RenamedSdk {
+            module: module.to_string(),
+            sdk: sdk.to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_renamed_sdks_of_inline_and_dotted_tables() {
+        let manifest = r#"
+[package]
+name = "orders"
+
+[dependencies]
+sqs = { package = "aws-sdk-sqs", version = "1.9.0" } # renamed to keep the paths short
+aws-sdk-s3 = "1.20.0"
+serde = { version = "1", features = ["derive"] }
+
+[dev-dependencies.dynamo]
+version = "1.28.0"
+package = "aws-sdk-dynamodb"
+
+[target.'cfg(unix)'.build-dependencies]
+events.package = "aws-sdk-eventbridge"
+
+[workspace.dependencies]
+notifications = {
+    package = 'aws-sdk-sns',
+    version = "1.30.0",
+}
+"#;
+
+        assert_eq!(
+            renamed(manifest),
+            vec![
+                renamed_sdk("sqs", "sqs"),
+                renamed_sdk("dynamo", "dynamodb"),
+                renamed_sdk("events", "eventbridge"),
+                renamed_sdk("notifications", "sns"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_renamed_sdks_ignores_packages_outside_dependencies() {
+        assert!(renamed("[package]\nname = \"x\"\n\n[features]\npackage = \"aws-sdk-sqs\"\n\n[workspace.metadata.sqs]\npackage = \"aws-sdk-sqs\"\n").is_empty());
+    }
+}
@@ -1,74 +1,232 @@
+#[cfg(test)]
 use std::collections::HashMap;
 
-const METHODS_WITH_REQUIRED_PROPS: &str = include_str!("../required_properties_info/required_props_info.csv");
+use aws_sdk_compile_checks_data::operations::waiter_operations;
+use aws_sdk_compile_checks_data::required_properties as data;
+pub(crate) use aws_sdk_compile_checks_data::required_properties::PropsBySdk;
 
-pub(crate) type RequiredPropertiesMap = HashMap<&'static str, HashMap<&'static str, Vec<&'static str>>>;
+use crate::datasets::Dataset;
 
-// if we only need a specific sdk, maybe we could filter out the others?
-// ideally this would be done at compile time, perhaps with konst crate
-pub(crate) fn create_required_props_map() -> RequiredPropertiesMap {
-    create_required_props_for(METHODS_WITH_REQUIRED_PROPS)
+const CLIENT_TYPE: &str = "Client";
+
+/// The required properties of every method, by method name.
+/// Backed by the static data of `aws-sdk-compile-checks-data`, so creating it costs nothing, merged with the datasets of in-house clients.
+#[derive(Debug)]
+pub(crate) struct RequiredPropertiesMap {
+    source: Source,
+    datasets: &'static [Dataset], // registered for in-house clients, these take precedence for services with the same name
+}
+
+#[derive(Debug)]
+enum Source {
+    /// The data of `aws-sdk-compile-checks-data`
+    Generated,
+    #[cfg(test)]
+    Owned(HashMap<&'static str, PropsBySdk>),
 }
 
-fn create_required_props_for(props: &'static str) -> RequiredPropertiesMap {
-    let required_props_as_vec: Vec<(&str, &str, Vec<&str>)> = props
-        .split('\n')
-        .filter(|m| !m.is_empty())
-        .map(|m| {
-            let mut method_and_props: Vec<_> = m.split(',').collect();
-            let required_props = method_and_props
-                .pop()
-                .expect("required props to be the third element")
-                .split_whitespace()
-                .collect();
-            let method_name = method_and_props.pop().expect("method to be the second element");
-            let service_name = method_and_props.pop().expect("service to be the first element");
-            (service_name, method_name, required_props)
-        })
+impl RequiredPropertiesMap {
+    /// Adds the datasets of in-house clients, see `datasets::datasets`
+    pub(crate) fn with_datasets(self, datasets: &'static [Dataset]) -> Self {
+        RequiredPropertiesMap { datasets, ..self }
+    }
+
+    /// The module prefixes of the registered datasets, e.g. `my_internal_sdk_`
+    pub(crate) fn dataset_prefixes(&self) -> impl Iterator<Item = &'static str> {
+        self.datasets.iter().map(|d| d.prefix.as_str())
+    }
+
+    /// The module prefix of the dataset that has the SDK, `None` for the AWS SDKs
+    pub(crate) fn dataset_prefix_of(&self, sdk: &str) -> Option<&'static str> {
+        self.datasets.iter().find(|d| d.contains_sdk(sdk)).map(|d| d.prefix.as_str())
+    }
+
+    /// The (SDK, method) of the operations with a fluent builder of this name, e.g. (`sqs`, `send_message`) for `SendMessageFluentBuilder`
+    pub(crate) fn operations_of_builder(&self, builder: &str) -> Vec<(&'static str, &'static str)> {
+        let mut operations = data::operations_of_builder(builder).to_vec();
+        operations.extend(self.datasets.iter().flat_map(|d| d.operations_of_builder(builder)));
+        operations
+    }
+
+    pub(crate) fn contains_key(&self, method: &str) -> bool {
+        self.datasets.iter().any(|d| d.props_for(method).next().is_some()) || self.contains_static_key(method)
+    }
+
+    pub(crate) fn get(&self, method: &str) -> Option<PropsBySdk> {
+        let mut props_by_sdk = self.get_static(method).unwrap_or_default();
+        props_by_sdk.extend(self.datasets.iter().flat_map(|d| d.props_for(method)));
+        (!props_by_sdk.is_empty()).then_some(props_by_sdk)
+    }
+
+    fn contains_static_key(&self, method: &str) -> bool {
+        match &self.source {
+            Source::Generated => data::contains_method(method) || !waiter_operations(method).is_empty(),
+            #[cfg(test)]
+            Source::Owned(map) => map.contains_key(method),
+        }
+    }
+
+    fn get_static(&self, method: &str) -> Option<PropsBySdk> {
+        match &self.source {
+            Source::Generated => data::required_props(method).or_else(|| waiter_props(method)),
+            #[cfg(test)]
+            Source::Owned(map) => map.get(method).cloned(),
+        }
+    }
+
+    /// Link to the documentation of the builder of the method in the given SDK, if the data has one
+    pub(crate) fn docs_url(&self, method: &str, sdk: &str) -> Option<&'static str> {
+        if let Some(docs_url) = self.datasets.iter().find_map(|d| d.docs_url(method, sdk)) {
+            return Some(docs_url).filter(|url| !url.is_empty());
+        }
+        match &self.source {
+            Source::Generated => data::docs_url(method, sdk),
+            #[cfg(test)]
+            Source::Owned(_) => None,
+        }
+    }
+
+    /// Whether the SDK has at least one method in the map.
+    /// Called for every client and SDK of every annotated function, so neither source goes through all its methods:
+    /// the SDKs of the generated data are sorted at build time, those of a dataset are collected once
+    pub(crate) fn contains_sdk(&self, sdk: &str) -> bool {
+        if self.datasets.iter().any(|d| d.contains_sdk(sdk)) {
+            return true;
+        }
+        match &self.source {
+            Source::Generated => data::contains_sdk(sdk),
+            #[cfg(test)]
+            Source::Owned(map) => map.values().any(|services| services.contains_key(sdk)),
+        }
+    }
+
+    /// The SDK of a client type we do not know, by its name without `Client`, e.g. `sqs` for `SqsClient`.
+    /// A shortened name is accepted when one SDK is the start of all others, e.g. `dynamodb` (and not `dynamodbstreams`) for `DynamoClient`
+    pub(crate) fn sdk_of_client_type(&self, type_name: &str) -> Option<String> {
+        let name = type_name.strip_suffix(CLIENT_TYPE)?.to_lowercase();
+        if name.is_empty() {
+            return None;
+        }
+        if self.contains_sdk(&name) || data::is_known_sdk(&name) {
+            return Some(name);
+        }
+        let candidates: Vec<&str> = data::known_sdks_starting_with(&name).collect();
+        let shortest = candidates.iter().min_by_key(|sdk| sdk.len())?;
+        candidates.iter().all(|sdk| sdk.starts_with(shortest)).then(|| shortest.to_string())
+    }
+}
+
+#[cfg(test)]
+impl Default for RequiredPropertiesMap {
+    fn default() -> Self {
+        HashMap::new().into()
+    }
+}
+
+#[cfg(test)]
+impl From<HashMap<&'static str, HashMap<&'static str, Vec<&'static str>>>> for RequiredPropertiesMap {
+    fn from(map: HashMap<&'static str, HashMap<&'static str, Vec<&'static str>>>) -> Self {
+        // leaking is fine for the small maps of our tests, and gives us the same static slices as the generated data
+        let map = map
+            .into_iter()
+            .map(|(method, services)| (method, services.into_iter().map(|(service, props)| (service, &*props.leak())).collect()))
+            .collect();
+        RequiredPropertiesMap {
+            source: Source::Owned(map),
+            datasets: &[],
+        }
+    }
+}
+
+/// A waiter (e.g. `wait_until_table_exists`) requires the same properties as the operation it polls (e.g. `describe_table`)
+fn waiter_props(waiter: &str) -> Option<PropsBySdk> {
+    let props_by_sdk: PropsBySdk = waiter_operations(waiter)
+        .into_iter()
+        .filter_map(|(sdk, operation)| data::required_props(operation)?.get(sdk).map(|props| (sdk, *props)))
         .collect();
-    required_props_as_vec.into_iter().fold(
-        HashMap::new(),
-        |mut acc: HashMap<&'static str, HashMap<&'static str, Vec<&'static str>>>, (service_name, method_name, required_props)| {
-            let map_for_method = acc.entry(method_name).or_default();
-            map_for_method.entry(service_name).or_default().extend(required_props);
-            acc
-        },
-    )
+    (!props_by_sdk.is_empty()).then_some(props_by_sdk)
 }
 
-pub fn valid_sdks(required_props: &RequiredPropertiesMap, selected_sdks: &[String]) -> Result<(), String> {
-    let service_names: Vec<_> = required_props.values().flat_map(|v| v.keys()).collect();
-    let not_found: Vec<String> = selected_sdks
+pub(crate) fn create_required_props_map() -> RequiredPropertiesMap {
+    RequiredPropertiesMap {
+        source: Source::Generated,
+        datasets: &[],
+    }
+}
+
+/// Errors with the SDKs that do not exist, otherwise returns the SDKs that exist but have no rows in our data,
+/// because calls of those will not be checked at all
+pub fn valid_sdks(required_props: &RequiredPropertiesMap, selected_sdks: &[String]) -> Result<Vec<String>, String> {
+    let (without_data, not_found): (Vec<String>, Vec<String>) = selected_sdks
         .iter()
         .map(|s| s.to_string())
-        .filter(|s| !service_names.contains(&&s.as_ref()))
-        .collect();
+        .filter(|s| !required_props.contains_sdk(s))
+        .partition(|s| data::is_known_sdk(s));
 
     if !not_found.is_empty() {
         Err(not_found.join(", "))
     } else {
-        Ok(())
+        Ok(without_data)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::datasets::dataset_for_tests;
+
+    #[test]
+    fn test_generated_table_contains_the_csv_data() {
+        let checks = create_required_props_map();
+
+        assert!(checks.contains_key("send_message"));
+        assert!(!checks.contains_key("not_an_sdk_method"));
+        assert_eq!(checks.get("receive_message").unwrap().get("sqs"), Some(&&["queue_url"][..]));
+        let send_message = checks.get("send_message").unwrap();
+        assert_eq!(send_message.keys().count(), 2);
+        assert_eq!(send_message.get("sqs"), Some(&&["queue_url", "message_body"][..]));
+        assert!(checks.contains_sdk("connectparticipant"));
+        assert!(!checks.contains_sdk("not_an_sdk"));
+        assert_eq!(checks.docs_url("send_message", "not_an_sdk"), None);
+        assert_eq!(checks.docs_url("not_an_sdk_method", "sqs"), None);
+    }
+
+    #[test]
+    fn test_waiters_require_the_props_of_their_operation() {
+        let checks = create_required_props_map();
+
+        assert!(checks.contains_key("wait_until_table_exists"));
+        assert_eq!(checks.get("wait_until_table_exists").unwrap().get("dynamodb"), Some(&&["table_name"][..]));
+        assert_eq!(checks.get("wait_until_object_exists").unwrap().get("s3"), Some(&&["bucket", "key"][..]));
+        assert!(!checks.contains_key("wait_until_not_a_waiter"));
+    }
 
     #[test]
-    fn test_create_required_props_creates_hashmap_with_entries_by_method_name_containing_hashmaps_by_service_key() {
-        let props = "s3,write,bucket object\ns3,associate,account_arn\ns3control,associate,account_id identity_center_arn";
+    fn test_datasets_are_merged_with_the_generated_table() {
+        let dataset: &'static [Dataset] = vec![dataset_for_tests("my_internal_sdk_", "orders,send_message,order_id\norders,create_order,customer_id\n")].leak();
+        let checks = create_required_props_map().with_datasets(dataset);
+
+        assert_eq!(checks.get("create_order").unwrap().get("orders"), Some(&&["customer_id"][..]));
+        let send_message = checks.get("send_message").unwrap();
+        assert_eq!(send_message.keys().count(), 3);
+        assert_eq!(send_message.get("orders"), Some(&&["order_id"][..]));
+        assert!(checks.contains_key("create_order"));
+        assert!(checks.contains_sdk("orders"));
+        assert!(checks.contains_sdk("sqs"));
+        assert_eq!(checks.dataset_prefixes().collect::<Vec<_>>(), vec!["my_internal_sdk_"]);
+        assert_eq!(checks.dataset_prefix_of("orders"), Some("my_internal_sdk_"));
+        assert_eq!(checks.dataset_prefix_of("sqs"), None);
+    }
 
-        let checks = create_required_props_for(props);
+    #[test]
+    fn test_generated_builder_table_maps_builders_to_operations() {
+        let checks = create_required_props_map();
 
-        assert_eq!(checks.keys().count(), 2);
-        let write = checks.get("write").unwrap();
-        let associate = checks.get("associate").unwrap();
-        assert_eq!(write.keys().count(), 1);
-        assert_eq!(write.get("s3"), Some(&vec!["bucket", "object"]));
-        assert_eq!(associate.keys().count(), 2);
-        assert_eq!(associate.get("s3"), Some(&vec!["account_arn"]));
-        assert_eq!(associate.get("s3control"), Some(&vec!["account_id", "identity_center_arn"]));
+        let mut send_message = checks.operations_of_builder("SendMessageFluentBuilder");
+        send_message.sort_unstable();
+        assert_eq!(send_message, vec![("connectparticipant", "send_message"), ("sqs", "send_message")]);
+        assert_eq!(checks.operations_of_builder("ListObjectsV2FluentBuilder"), vec![("s3", "list_objects_v2")]);
+        assert!(checks.operations_of_builder("NotAFluentBuilder").is_empty());
     }
 
     #[test]
@@ -77,8 +235,28 @@ mod tests {
         required_props.insert("something", HashMap::from([("s3", vec!["required_call"])]));
         required_props.insert("something_else", HashMap::from([("sqs", vec!["required_call"])]));
 
-        let actual = valid_sdks(&required_props, &["s3".to_string(), "sns".to_string()]).unwrap_err();
+        let actual = valid_sdks(&required_props.into(), &["s3".to_string(), "not_an_sdk".to_string()]).unwrap_err();
+
+        assert_eq!(actual, "not_an_sdk".to_string());
+    }
+
+    #[test]
+    fn test_known_sdk_without_data_is_valid_but_returned() {
+        let checks = create_required_props_map();
+
+        let actual = valid_sdks(&checks, &["sqs".to_string(), "qldbsession".to_string(), "chime".to_string()]).unwrap();
+
+        assert_eq!(actual, vec!["qldbsession".to_string(), "chime".to_string()]);
+    }
+
+    #[test]
+    fn test_sdk_of_client_type_by_name() {
+        let checks = create_required_props_map();
 
-        assert_eq!(actual, "sns".to_string());
+        assert_eq!(checks.sdk_of_client_type("SqsClient"), Some("sqs".to_string()));
+        assert_eq!(checks.sdk_of_client_type("DynamoClient"), Some("dynamodb".to_string()));
+        assert_eq!(checks.sdk_of_client_type("HttpClient"), None);
+        assert_eq!(checks.sdk_of_client_type("Client"), None);
+        assert_eq!(checks.sdk_of_client_type("SqsQueue"), None);
     }
 }
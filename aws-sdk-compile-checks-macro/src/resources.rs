@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use proc_macro2::Ident;
+use serde_json::Value;
+use syn::LitStr;
+
+use crate::findings::{UnknownResourceUsage, Usage, UsageFinds};
+use crate::literals::{BUCKET_SETTER, QUEUE_URL_SETTER};
+use crate::registered::registered_files;
+
+/// Output files of infrastructure as code, e.g. `cdk-outputs.json`
+const RESOURCES_ENV_VAR: &str = "AWS_SDK_COMPILE_CHECKS_RESOURCES";
+/// Setters that take the identifier of a resource that is usually defined in infrastructure as code
+pub(crate) const RESOURCE_SETTERS: [&str; 3] = [QUEUE_URL_SETTER, "table_name", BUCKET_SETTER];
+
+/// The identifiers of the resources in the output files of `AWS_SDK_COMPILE_CHECKS_RESOURCES`
+#[derive(Debug)]
+pub(crate) struct KnownResources {
+    files: Vec<String>,
+    identifiers: HashSet<String>,
+}
+
+/// The resources in the files registered with `AWS_SDK_COMPILE_CHECKS_RESOURCES` (see [`registered_files`]), `None` when there are none,
+/// or a description of why they could not be loaded
+pub(crate) fn known_resources() -> Result<Option<&'static KnownResources>, &'static str> {
+    static RESOURCES: OnceLock<Result<Option<KnownResources>, String>> = OnceLock::new();
+    RESOURCES
+        .get_or_init(|| {
+            let outputs = registered_files(RESOURCES_ENV_VAR)?;
+            if outputs.is_empty() {
+                return Ok(None);
+            }
+            let mut identifiers = HashSet::new();
+            for output in &outputs {
+                let json: Value = serde_json::from_str(&output.content).map_err(|e| format!("{}: {}", output.name, e))?;
+                identifiers.extend(string_values(&json).into_iter().flat_map(identifiers_of));
+            }
+            let files = outputs.into_iter().map(|output| output.name).collect();
+            Ok(Some(KnownResources { files, identifiers }))
+        })
+        .as_ref()
+        .map(Option::as_ref)
+        .map_err(String::as_str)
+}
+
+/// A warning for every literal identifier that is not one of the resources
+pub(crate) fn find_unknown_resources<'a>(literals: impl Iterator<Item = &'a (Ident, LitStr)>, resources: &'static KnownResources) -> Vec<UsageFinds> {
+    literals
+        .filter(|(_, literal)| {
+            let value = literal.value();
+            // empty values have a warning of their own
+            !value.trim().is_empty() && !resources.identifiers.contains(&value)
+        })
+        .map(|(setter, literal)| {
+            UsageFinds::new(literal.span(), Usage::UnknownResource(UnknownResourceUsage {
+                setter: setter.to_string(),
+                value: literal.value(),
+                files: &resources.files,
+            }))
+        })
+        .collect()
+}
+
+/// Every string in the document, however deeply nested, except for the keys of objects
+fn string_values(json: &Value) -> Vec<&str> {
+    match json {
+        Value::String(value) => vec![value.as_str()],
+        Value::Array(values) => values.iter().flat_map(string_values).collect(),
+        Value::Object(entries) => entries.values().flat_map(string_values).collect(),
+        Value::Null | Value::Bool(_) | Value::Number(_) => vec![],
+    }
+}
+
+/// The output itself, and the name in it when it is an ARN, e.g. `orders` for `arn:aws:dynamodb:eu-west-1:123456789012:table/orders`.
+/// CloudFormation, CDK and Terraform outputs are all JSON, with the values somewhere in them, so every string counts
+fn identifiers_of(output: &str) -> Vec<String> {
+    let mut identifiers = vec![output.to_string()];
+    if output.starts_with("arn:") {
+        if let Some(name) = output.rsplit(['/', ':']).next().filter(|n| !n.is_empty()) {
+            identifiers.push(name.to_string());
+        }
+    }
+    identifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    use super::*;
+
+    fn resources(outputs: &str) -> &'static KnownResources {
+        let identifiers = string_values(&serde_json::from_str(outputs).unwrap()).into_iter().flat_map(identifiers_of).collect();
+        Box::leak(Box::new(KnownResources {
+            files: vec!["cdk-outputs.json".to_string()],
+            identifiers,
+        }))
+    }
+
+    fn literal(setter: &str, value: &str) -> (Ident, LitStr) {
+        (Ident::new(setter, Span::call_site()), LitStr::new(value, Span::call_site()))
+    }
+
+    #[test]
+    fn identifiers_of_arns_include_the_name() {
+        assert_eq!(identifiers_of("arn:aws:dynamodb:eu-west-1:123456789012:table/orders"), vec!["arn:aws:dynamodb:eu-west-1:123456789012:table/orders", "orders"]);
+        assert_eq!(identifiers_of("arn:aws:s3:::my-bucket"), vec!["arn:aws:s3:::my-bucket", "my-bucket"]);
+        assert_eq!(identifiers_of("orders"), vec!["orders"]);
+    }
+
+    #[test]
+    fn find_unknown_resources_in_cdk_outputs() {
+        let outputs = r#"{"OrdersStack": {"QueueUrl": "https://sqs.eu-west-1.amazonaws.com/123456789012/orders", "TableArn": "arn:aws:dynamodb:eu-west-1:123456789012:table/orders"}}"#;
+        let literals = [
+            literal("queue_url", "https://sqs.eu-west-1.amazonaws.com/123456789012/orders"),
+            literal("table_name", "orders"),
+            literal("table_name", "order"),
+            literal("bucket", ""),
+        ];
+
+        let actual: Vec<(String, String)> = find_unknown_resources(literals.iter(), resources(outputs))
+            .into_iter()
+            .map(|f| match f.usage {
+                Usage::UnknownResource(u) => (u.setter, u.value),
+                _ => panic!("expected only unknown resources"),
+            })
+            .collect();
+
+        assert_eq!(actual, vec![("table_name".to_string(), "order".to_string())]);
+    }
+}
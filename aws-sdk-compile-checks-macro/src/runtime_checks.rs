@@ -0,0 +1,147 @@
+use aws_sdk_compile_checks_data::required_properties::alternatives;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote_spanned;
+use syn::visit_mut::{self, VisitMut};
+use syn::{parse_quote_spanned, Expr, ExprMethodCall, Item, ItemFn};
+
+use crate::visitor::PassedBuilder;
+
+const AWS_SDK_SEND: &str = "send";
+const GETTER_PREFIX: &str = "get_"; // fluent builders have a getter for every property, e.g. `get_queue_url()`
+const RAW_PREFIX: &str = "r#";
+
+/// For builders that are passed into the function, we cannot tell what setters were called before, so we check them when they are sent:
+/// `request.send()` becomes `({ let builder = request; ::core::debug_assert!(...); builder }).send()`.
+/// Like every `debug_assert!`, the check only runs in debug builds (and tests), but it needs the getters to exist in any build
+pub(crate) fn add_runtime_checks(item: &mut ItemFn, builders: &[PassedBuilder]) {
+    if !builders.is_empty() {
+        RuntimeChecks { builders }.visit_item_fn_mut(item);
+    }
+}
+
+struct RuntimeChecks<'a> {
+    builders: &'a [PassedBuilder],
+}
+
+impl VisitMut for RuntimeChecks<'_> {
+    // functions nested in this one have their own parameters, which might have the same name
+    fn visit_item_mut(&mut self, _node: &mut Item) {}
+
+    fn visit_expr_method_call_mut(&mut self, node: &mut ExprMethodCall) {
+        visit_mut::visit_expr_method_call_mut(self, node);
+        if node.method != AWS_SDK_SEND || !node.args.is_empty() {
+            return;
+        }
+        let Some(builder) = root_of_chain(&node.receiver).and_then(|root| self.builders.iter().find(|b| root == &b.binding)) else {
+            return;
+        };
+        let span = node.method.span();
+        let receiver = &node.receiver;
+        let condition = condition(&builder.required, span);
+        let message = format!(
+            "`{}` is sent without all the properties that are required, which are {}",
+            builder.binding,
+            describe(&builder.required)
+        );
+        node.receiver = parse_quote_spanned!(span=>
+            ({
+                let builder = #receiver;
+                ::core::debug_assert!(#condition, "{}", #message);
+                builder
+            })
+        );
+    }
+}
+
+/// The local or parameter at the start of a chain, e.g. `request` for `request.message_body(body)`
+fn root_of_chain(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::MethodCall(method_call) => root_of_chain(&method_call.receiver),
+        Expr::Paren(paren) => root_of_chain(&paren.expr),
+        Expr::Path(path) => path.path.get_ident(),
+        _ => None,
+    }
+}
+
+/// Every required property is set, and at least one of every 'at least one of' group
+fn condition(required: &[&str], span: Span) -> TokenStream {
+    let groups = required.iter().map(|prop| {
+        let getters = alternatives(prop).map(|alternative| getter(alternative, span));
+        quote_spanned!(span=> (#(builder.#getters().is_some())||*))
+    });
+    quote_spanned!(span=> #(#groups)&&*)
+}
+
+fn getter(prop: &str, span: Span) -> Ident {
+    Ident::new(&format!("{}{}", GETTER_PREFIX, prop.strip_prefix(RAW_PREFIX).unwrap_or(prop)), span)
+}
+
+fn describe(required: &[&str]) -> String {
+    required
+        .iter()
+        .map(|prop| {
+            let alternatives: Vec<String> = alternatives(prop).map(|a| format!("`{}`", a.strip_prefix(RAW_PREFIX).unwrap_or(a))).collect();
+            match alternatives.as_slice() {
+                [single] => single.to_string(),
+                _ => format!("one of {}", alternatives.join(" or ")),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::*;
+
+    fn checked(item: TokenStream, required: Vec<&'static str>) -> String {
+        let mut item: ItemFn = syn::parse2(item).unwrap();
+        let builders = [PassedBuilder {
+            binding: "request".to_string(),
+            required,
+        }];
+        add_runtime_checks(&mut item, &builders);
+        item.into_token_stream().to_string()
+    }
+
+    #[test]
+    fn add_runtime_checks_asserts_the_required_properties_before_the_send() {
+        let actual = checked(
+            quote::quote!(
+                async fn send(request: SendMessageFluentBuilder) {
+                    request.message_body("hello").send().await.unwrap();
+                }
+            ),
+            vec!["queue_url", "message_body"],
+        );
+
+        assert!(actual.contains("let builder = request . message_body (\"hello\") ;"));
+        assert!(actual.contains(":: core :: debug_assert ! ((builder . get_queue_url () . is_some ()) && (builder . get_message_body () . is_some ())"));
+        assert!(actual.contains("builder }) . send () . await"));
+    }
+
+    #[test]
+    fn add_runtime_checks_accepts_any_alternative_and_strips_the_raw_prefix() {
+        let actual = checked(quote::quote!(fn send(request: Builder) { request.send(); }), vec!["r#type", "update_expression|attribute_updates"]);
+
+        assert!(actual.contains("(builder . get_type () . is_some ()) && (builder . get_update_expression () . is_some () || builder . get_attribute_updates () . is_some ())"));
+        assert!(actual.contains("which are `type`, one of `update_expression` or `attribute_updates`"));
+    }
+
+    #[test]
+    fn add_runtime_checks_ignores_other_receivers_and_nested_functions() {
+        let item = quote::quote!(
+            fn send(request: Builder, other: Builder) {
+                other.send();
+                fn nested(request: Other) {
+                    request.send();
+                }
+            }
+        );
+        let expected = syn::parse2::<ItemFn>(item.clone()).unwrap().into_token_stream().to_string();
+
+        assert_eq!(checked(item, vec!["queue_url"]), expected);
+    }
+}
@@ -0,0 +1,210 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use aws_sdk_compile_checks_data::required_properties::{data_month, data_sdk_version_of};
+use aws_sdk_compile_checks_data::requirement_changes::{requirement_changes, Change, RequirementChange};
+use semver::Version;
+use serde::Deserialize;
+
+/// A missing property whose requirement changed between the version of the SDK our data is based on and the one the user pinned
+#[derive(Debug, PartialEq)]
+pub(crate) struct VersionNote {
+    pub(crate) property: &'static str,
+    pub(crate) required: bool, // whether the property is required in the pinned version
+    pub(crate) message: String,
+}
+
+/// Notes for missing properties whose requirement changed in a version between the one our data is based on and the one the user pinned.
+/// Only when the user pinned a different version of the SDK, otherwise our data is accurate.
+pub(crate) fn version_notes(sdk: &str, method: &str, missing: &[String]) -> Vec<VersionNote> {
+    let (Some(pinned), Some(snapshot)) = (pinned_sdk_version(sdk), data_sdk_version_of(sdk)) else {
+        return vec![];
+    };
+    notes_for(requirement_changes(), sdk, method, missing, &pinned, snapshot)
+}
+
+fn notes_for(changes: &[RequirementChange], sdk: &str, method: &str, missing: &[String], pinned: &str, snapshot: &str) -> Vec<VersionNote> {
+    let (Ok(pinned_version), Ok(snapshot_version)) = (Version::parse(pinned), Version::parse(snapshot)) else {
+        return vec![];
+    };
+    changes
+        .iter()
+        .filter(|c| c.service == sdk && c.method == method && missing.iter().any(|m| m == c.property))
+        .filter_map(|c| {
+            let changed_in = Version::parse(c.version).ok()?;
+            // our data already has the change when the snapshot is on the same side of it as the pinned version
+            let pinned_has_change = pinned_version >= changed_in;
+            if pinned_has_change == (snapshot_version >= changed_in) {
+                return None;
+            }
+            let required = pinned_has_change == (c.change == Change::Required);
+            let message = match c.change {
+                Change::Required if pinned_has_change => format!("`{}` is required since aws-sdk-{} {}, you are using {}", c.property, sdk, c.version, pinned),
+                Change::Required => format!("`{}` is only required since aws-sdk-{} {}, you are using {}", c.property, sdk, c.version, pinned),
+                Change::Optional if pinned_has_change => format!("`{}` is no longer required since aws-sdk-{} {}, you are using {}", c.property, sdk, c.version, pinned),
+                Change::Optional => format!("`{}` is still required before aws-sdk-{} {}, you are using {}", c.property, sdk, c.version, pinned),
+            };
+            Some(VersionNote {
+                property: c.property,
+                required,
+                message,
+            })
+        })
+        .collect()
+}
+
+/// Why our data does not meet the `min_data` of the attribute, when it was retrieved before that month
+pub(crate) fn stale_data(min_data: &str) -> Option<String> {
+    stale_data_for(data_month(), min_data)
+}
+
+fn stale_data_for(data_month: &str, min_data: &str) -> Option<String> {
+    // both are written like `2024-06`, so comparing them as strings compares the months
+    (data_month < min_data).then(|| format!("the data of this version of the macro is from {}, which is older than `min_data = \"{}\"`", data_month, min_data))
+}
+
+#[derive(Deserialize)]
+struct LockFile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    dependencies: Vec<String>, // e.g. `aws-sdk-s3`, or `aws-sdk-s3 1.21.0` when the lock file has several versions of it
+}
+
+/// The version of `aws-sdk-<sdk>` in the lock file of the project being compiled, if we can find it
+fn pinned_sdk_version(sdk: &str) -> Option<String> {
+    // the lock file does not change during a build, so read it once instead of for every annotated function
+    static LOCK_FILE: OnceLock<Option<LockFile>> = OnceLock::new();
+    let lock_file = LOCK_FILE.get_or_init(|| {
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")?;
+        // in a workspace, the lock file is in one of the parent directories
+        let content = Path::new(&manifest_dir)
+            .ancestors()
+            .find_map(|dir| std::fs::read_to_string(dir.join("Cargo.lock")).ok())?;
+        toml::from_str(&content).ok()
+    });
+    let package = std::env::var("CARGO_PKG_NAME").ok()?;
+    let package_version = std::env::var("CARGO_PKG_VERSION").ok()?;
+    version_in_lock_file(lock_file.as_ref()?, (&package, &package_version), &format!("aws-sdk-{}", sdk))
+}
+
+/// The version of the crate when the lock file has only one, else the one the package that is being compiled depends on
+fn version_in_lock_file(lock_file: &LockFile, (package, package_version): (&str, &str), crate_name: &str) -> Option<String> {
+    let versions: Vec<&str> = lock_file.package.iter().filter(|p| p.name == crate_name).map(|p| p.version.as_str()).collect();
+    if let [version] = versions.as_slice() {
+        return Some(version.to_string());
+    }
+    lock_file
+        .package
+        .iter()
+        .find(|p| p.name == package && p.version == package_version)?
+        .dependencies
+        .iter()
+        .find_map(|dependency| {
+            let mut words = dependency.split_whitespace();
+            (words.next() == Some(crate_name)).then(|| words.next()).flatten()
+        })
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANGES: &[RequirementChange] = &[
+        change("s3", "put_object", "bucket", Change::Required, "1.21.0"),
+        change("s3", "put_object", "key", Change::Optional, "1.22.0"),
+        change("sqs", "send_message", "queue_url", Change::Required, "1.3.0"),
+    ];
+
+    const fn change(service: &'static str, method: &'static str, property: &'static str, change: Change, version: &'static str) -> RequirementChange {
+        RequirementChange {
+            service,
+            method,
+            property,
+            change,
+            version,
+        }
+    }
+
+    #[test]
+    fn stale_data_for_a_month_after_the_data() {
+        assert_eq!(
+            stale_data_for("2024-03", "2024-06"),
+            Some("the data of this version of the macro is from 2024-03, which is older than `min_data = \"2024-06\"`".to_string())
+        );
+        assert_eq!(stale_data_for("2024-03", "2024-03"), None);
+        assert_eq!(stale_data_for("2024-03", "2023-12"), None);
+    }
+
+    #[test]
+    fn version_in_lock_file_finds_the_version_of_the_crate() {
+        let lock_file: LockFile = toml::from_str(
+            "[[package]]\nname = \"aws-sdk-s3\"\nversion = \"1.21.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"aws-sdk-s3control\"\nversion = \"1.2.0\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(version_in_lock_file(&lock_file, ("orders", "0.1.0"), "aws-sdk-s3"), Some("1.21.0".to_string()));
+        assert_eq!(version_in_lock_file(&lock_file, ("orders", "0.1.0"), "aws-sdk-s3control"), Some("1.2.0".to_string()));
+        assert_eq!(version_in_lock_file(&lock_file, ("orders", "0.1.0"), "aws-sdk-sqs"), None);
+    }
+
+    #[test]
+    fn version_in_lock_file_with_several_versions_of_the_crate() {
+        let lock_file: LockFile = toml::from_str(
+            "[[package]]\nname = \"aws-sdk-s3\"\nversion = \"1.21.0\"\n\n[[package]]\nname = \"aws-sdk-s3\"\nversion = \"1.40.0\"\n\n\
+             [[package]]\nname = \"orders\"\nversion = \"0.1.0\"\ndependencies = [\"aws-sdk-s3 1.40.0\", \"serde\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(version_in_lock_file(&lock_file, ("orders", "0.1.0"), "aws-sdk-s3"), Some("1.40.0".to_string()));
+        // another package of the workspace, which does not depend on the SDK
+        assert_eq!(version_in_lock_file(&lock_file, ("invoices", "0.1.0"), "aws-sdk-s3"), None);
+    }
+
+    #[test]
+    fn notes_for_missing_properties_with_changes() {
+        let missing = vec!["bucket".to_string(), "key".to_string()];
+
+        let actual = notes_for(CHANGES, "s3", "put_object", &missing, "1.22.1", "1.20.0");
+
+        assert_eq!(
+            actual,
+            vec![
+                VersionNote {
+                    property: "bucket",
+                    required: true,
+                    message: "`bucket` is required since aws-sdk-s3 1.21.0, you are using 1.22.1".to_string(),
+                },
+                VersionNote {
+                    property: "key",
+                    required: false,
+                    message: "`key` is no longer required since aws-sdk-s3 1.22.0, you are using 1.22.1".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn no_notes_for_changes_on_the_same_side_as_our_data() {
+        let missing = vec!["bucket".to_string(), "key".to_string()];
+
+        // both 1.19.0 and 1.18.0 are before either change
+        assert!(notes_for(CHANGES, "s3", "put_object", &missing, "1.19.0", "1.18.0").is_empty());
+        // semver, not text: 1.100.0 is after 1.22.0, like 1.30.0
+        assert!(notes_for(CHANGES, "s3", "put_object", &missing, "1.100.0", "1.30.0").is_empty());
+    }
+
+    #[test]
+    fn no_notes_when_using_the_version_of_our_data() {
+        let missing = vec!["bucket".to_string()];
+
+        assert!(notes_for(CHANGES, "s3", "put_object", &missing, "1.18.0", "1.18.0").is_empty());
+    }
+}
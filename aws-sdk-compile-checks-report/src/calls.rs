@@ -0,0 +1,71 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::exports::{export_files, without_duplicates};
+
+/// A call of an AWS SDK as exported by the macro, see `export.rs` in the macro crate
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct Call {
+    pub sdk: String,
+    pub method: String,
+    pub operation: String,
+    pub actions: Vec<String>, // like required properties, an action can be a group of alternatives separated by `|`
+    pub file: String,
+    pub line: usize,
+}
+
+/// The calls of every crate in the export directory (or of a single file), every call once
+pub fn read_calls(path: &Path) -> Result<Vec<Call>> {
+    let mut calls = vec![];
+    for path in export_files(path)? {
+        let file = File::open(&path).with_context(|| format!("failed to open exported calls at {}", path.display()))?;
+        calls.extend(parse_calls(BufReader::new(file)).with_context(|| format!("failed to read exported calls at {}", path.display()))?);
+    }
+    Ok(without_duplicates(calls))
+}
+
+/// Parses the JSON lines
+fn parse_calls(reader: impl BufRead) -> Result<Vec<Call>> {
+    let mut calls = vec![];
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        calls.push(serde_json::from_str(&line).with_context(|| format!("line {} is not a valid call", index + 1))?);
+    }
+
+    Ok(calls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALL: &str = r#"{"sdk":"sqs","method":"send_message","operation":"send_message","actions":["sqs:SendMessage"],"file":"src/main.rs","line":5}"#;
+
+    #[test]
+    fn parse_calls_skips_empty_lines() {
+        let input = format!("{}\n\n", CALL);
+
+        let actual = parse_calls(input.as_bytes()).unwrap();
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].actions, vec!["sqs:SendMessage"]);
+        assert_eq!(actual[0].line, 5);
+    }
+
+    #[test]
+    fn parse_calls_reports_the_invalid_line() {
+        let input = format!("{}\nnot json", CALL);
+
+        let actual = parse_calls(input.as_bytes()).unwrap_err();
+
+        assert_eq!(actual.to_string(), "line 2 is not a valid call");
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// The macro writes a file with this extension for every crate to the export directory
+const EXPORT_EXTENSION: &str = "jsonl";
+
+/// The files of an export: the file of every crate in the directory the macro wrote to, or a single file
+pub fn export_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = vec![];
+    for entry in std::fs::read_dir(path).with_context(|| format!("failed to read the export directory {}", path.display()))? {
+        let file = entry.with_context(|| format!("failed to read the export directory {}", path.display()))?.path();
+        if file.extension().is_some_and(|extension| extension == EXPORT_EXTENSION) {
+            files.push(file);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Drops the lines that were exported more than once, e.g. for a module that is part of both the library and a binary of a package
+pub fn without_duplicates<T: Clone + Eq + Hash>(mut lines: Vec<T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    lines.retain(|line| seen.insert(line.clone()));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_files_of_a_directory_are_the_files_of_the_crates() {
+        let directory = std::env::temp_dir().join(format!("aws-sdk-compile-checks-report-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        for file in ["orders.worker.jsonl", "orders.orders.jsonl", "notes.txt"] {
+            std::fs::write(directory.join(file), "").unwrap();
+        }
+
+        let actual = export_files(&directory).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(actual, vec![directory.join("orders.orders.jsonl"), directory.join("orders.worker.jsonl")]);
+        assert_eq!(export_files(Path::new("findings.jsonl")).unwrap(), vec![PathBuf::from("findings.jsonl")]);
+    }
+
+    #[test]
+    fn without_duplicates_keeps_the_first() {
+        assert_eq!(without_duplicates(vec!["a", "b", "a", "c", "b"]), vec!["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::exports::{export_files, without_duplicates};
+
+/// A finding as exported by the macro, see `export.rs` in the macro crate
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct Finding {
+    pub level: String,
+    pub code: Option<String>,
+    pub message: String,
+    pub notes: Vec<String>,
+    pub help: Vec<String>,
+    pub file: String,
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    /// Missing in findings exported before suggestions were added
+    #[serde(default)]
+    pub suggestion: Option<Suggestion>,
+}
+
+/// A replacement for part of the file of the finding, e.g. the attribute with the SDK it should check
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct Suggestion {
+    pub replacement: String,
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// The findings of every crate in the export directory (or of a single file), every finding once
+pub fn read_findings(path: &Path) -> Result<Vec<Finding>> {
+    let mut findings = vec![];
+    for path in export_files(path)? {
+        let file = File::open(&path).with_context(|| format!("failed to open exported findings at {}", path.display()))?;
+        findings.extend(parse_findings(BufReader::new(file)).with_context(|| format!("failed to read exported findings at {}", path.display()))?);
+    }
+    Ok(without_duplicates(findings))
+}
+
+/// Parses the JSON lines
+fn parse_findings(reader: impl BufRead) -> Result<Vec<Finding>> {
+    let mut findings = vec![];
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        findings.push(serde_json::from_str(&line).with_context(|| format!("line {} is not a valid finding", index + 1))?);
+    }
+
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FINDING: &str = r#"{"level":"error","code":"missing-required-props","message":"method `receive_message` (from sqs) is missing required argument(s): `queue_url`","notes":[],"help":[],"file":"src/main.rs","start_line":5,"start_column":24,"end_line":5,"end_column":39}"#;
+
+    #[test]
+    fn parse_findings_skips_empty_lines() {
+        let input = format!("{}\n\n", FINDING);
+
+        let actual = parse_findings(input.as_bytes()).unwrap();
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].code, Some("missing-required-props".to_string()));
+        assert_eq!(actual[0].start_line, 5);
+        assert_eq!(actual[0].suggestion, None);
+    }
+
+    #[test]
+    fn parse_findings_reads_the_suggestion() {
+        let input = r##"{"level":"error","code":"unknown-sdk","message":"method `create_app` is used in multiple SDKs","notes":[],"help":[],"file":"src/main.rs","start_line":7,"start_column":12,"end_line":7,"end_column":22,"suggestion":{"replacement":"#[required_props(sdk = amplify)]","start_line":3,"start_column":1,"end_line":3,"end_column":19}}"##;
+
+        let actual = parse_findings(input.as_bytes()).unwrap();
+
+        let suggestion = actual[0].suggestion.as_ref().unwrap();
+        assert_eq!(suggestion.replacement, "#[required_props(sdk = amplify)]");
+        assert_eq!(suggestion.start_line, 3);
+        assert_eq!(suggestion.end_column, 19);
+    }
+
+    #[test]
+    fn parse_findings_reports_the_invalid_line() {
+        let input = format!("{}\nnot json", FINDING);
+
+        let actual = parse_findings(input.as_bytes()).unwrap_err();
+
+        assert_eq!(actual.to_string(), "line 2 is not a valid finding");
+    }
+}
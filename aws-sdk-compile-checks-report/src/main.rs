@@ -0,0 +1,61 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::calls::read_calls;
+use crate::findings::read_findings;
+
+mod calls;
+mod exports;
+mod findings;
+mod policy;
+mod sarif;
+
+const USAGE: &str = "usage: aws-sdk-compile-checks-report sarif <export directory of the findings> [output file]
+       aws-sdk-compile-checks-report policy <export directory of the calls> [output file]";
+const DEFAULT_SARIF_OUTPUT: &str = "aws-sdk-compile-checks.sarif";
+const DEFAULT_POLICY_OUTPUT: &str = "aws-sdk-compile-checks-policy.json";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(|a| a.as_str()) {
+        Some("sarif") => {
+            let input = args.get(1).with_context(|| format!("missing the exported findings\n{}", USAGE))?;
+            let output = args.get(2).map(|o| o.as_str()).unwrap_or(DEFAULT_SARIF_OUTPUT);
+            write_sarif(Path::new(input), Path::new(output))
+        }
+        Some("policy") => {
+            let input = args.get(1).with_context(|| format!("missing the exported calls\n{}", USAGE))?;
+            let output = args.get(2).map(|o| o.as_str()).unwrap_or(DEFAULT_POLICY_OUTPUT);
+            write_policy(Path::new(input), Path::new(output))
+        }
+        _ => bail!(USAGE),
+    }
+}
+
+fn write_sarif(input: &Path, output: &Path) -> Result<()> {
+    let findings = read_findings(input)?;
+    let root: PathBuf = std::env::current_dir().context("failed to get the current directory")?;
+    let sarif = sarif::to_sarif(&findings, &root);
+
+    let writer = BufWriter::new(File::create(output).with_context(|| format!("failed to create {}", output.display()))?);
+    serde_json::to_writer_pretty(writer, &sarif).with_context(|| format!("failed to write SARIF to {}", output.display()))?;
+    println!("Wrote {} finding(s) to {}", findings.len(), output.display());
+    Ok(())
+}
+
+fn write_policy(input: &Path, output: &Path) -> Result<()> {
+    let calls = read_calls(input)?;
+    let (policy, choices) = policy::to_policy(&calls);
+
+    let writer = BufWriter::new(File::create(output).with_context(|| format!("failed to create {}", output.display()))?);
+    serde_json::to_writer_pretty(writer, &policy).with_context(|| format!("failed to write the policy to {}", output.display()))?;
+    for choice in choices {
+        println!("note: {}", choice);
+    }
+    println!("Wrote a policy for {} call(s) to {}", calls.len(), output.display());
+    Ok(())
+}
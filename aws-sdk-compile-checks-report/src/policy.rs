@@ -0,0 +1,91 @@
+use std::collections::BTreeSet;
+
+use serde_json::{json, Value};
+
+use crate::calls::Call;
+
+const POLICY_VERSION: &str = "2012-10-17";
+const ALTERNATIVES_SEPARATOR: char = '|';
+
+/// An identity-based policy that allows exactly the actions of the calls.
+/// When a call needs one of several actions (e.g. the PartiQL actions of DynamoDB `execute_statement`), the first one is used,
+/// and returned as a choice, so it can be reviewed
+pub fn to_policy(calls: &[Call]) -> (Value, Vec<String>) {
+    let mut actions = BTreeSet::new();
+    let mut choices = BTreeSet::new();
+
+    for call in calls {
+        for action in &call.actions {
+            let mut alternatives = action.split(ALTERNATIVES_SEPARATOR);
+            let chosen = alternatives.next().unwrap_or_default();
+            let others: Vec<&str> = alternatives.collect();
+            if !others.is_empty() {
+                choices.insert(format!(
+                    "`{}` of {} needs one of several actions, used `{}` instead of `{}`",
+                    call.method,
+                    call.sdk,
+                    chosen,
+                    others.join("`, `")
+                ));
+            }
+            actions.insert(chosen.to_string());
+        }
+    }
+
+    // resources are not known at compile time, so narrowing them down is left to the reader of the policy
+    let policy = json!({
+        "Version": POLICY_VERSION,
+        "Statement": [{
+            "Effect": "Allow",
+            "Action": actions.into_iter().collect::<Vec<_>>(),
+            "Resource": "*",
+        }]
+    });
+    (policy, choices.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(sdk: &str, method: &str, actions: &[&str]) -> Call {
+        Call {
+            sdk: sdk.to_string(),
+            method: method.to_string(),
+            operation: method.to_string(),
+            actions: actions.iter().map(|a| a.to_string()).collect(),
+            file: "src/main.rs".to_string(),
+            line: 5,
+        }
+    }
+
+    #[test]
+    fn to_policy_allows_every_action_once_and_sorted() {
+        let calls = vec![
+            call("sqs", "send_message", &["sqs:SendMessage"]),
+            call("s3", "copy_object", &["s3:GetObject", "s3:PutObject"]),
+            call("sqs", "send_message", &["sqs:SendMessage"]),
+        ];
+
+        let (policy, choices) = to_policy(&calls);
+
+        assert_eq!(policy["Version"], "2012-10-17");
+        assert_eq!(policy["Statement"][0]["Effect"], "Allow");
+        assert_eq!(policy["Statement"][0]["Action"], json!(["s3:GetObject", "s3:PutObject", "sqs:SendMessage"]));
+        assert_eq!(policy["Statement"][0]["Resource"], "*");
+        assert!(choices.is_empty());
+    }
+
+    #[test]
+    fn to_policy_uses_the_first_of_alternative_actions() {
+        let calls = vec![call("dynamodb", "execute_statement", &["dynamodb:PartiQLSelect|dynamodb:PartiQLInsert"])];
+
+        let (policy, choices) = to_policy(&calls);
+
+        assert_eq!(policy["Statement"][0]["Action"], json!(["dynamodb:PartiQLSelect"]));
+        assert_eq!(
+            choices,
+            vec!["`execute_statement` of dynamodb needs one of several actions, used `dynamodb:PartiQLSelect` instead of `dynamodb:PartiQLInsert`"]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::findings::Finding;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const TOOL_NAME: &str = "aws-sdk-compile-checks";
+const TOOL_URI: &str = "https://github.com/VanOvermeire/aws-sdk-compile-checks";
+
+/// The rules we know about, findings with other codes are still reported, but without a description
+const RULES: [(&str, &str); 34] = [
+    ("missing-required-props", "An AWS SDK call is missing properties that are required at runtime"),
+    ("ambiguous-sdk", "The SDK of an AWS SDK call could not be determined, so its required properties were not checked"),
+    ("exclusive-props", "An AWS SDK call sets properties that the service does not accept together"),
+    ("hardcoded-credentials", "AWS credentials are written in the code"),
+    ("malformed-arn", "A string literal passed to an AWS SDK call as an ARN is not a valid ARN"),
+    ("out-of-range", "A number passed to an AWS SDK call is outside the range the service accepts"),
+    ("invalid-bucket-name", "A string literal passed to an S3 call as a bucket breaks the naming rules of S3"),
+    ("unknown-region", "A string literal passed as a region is not a region of AWS"),
+    ("conditionally-required-props", "An AWS SDK call is missing properties that are required in some situations, e.g. for FIFO queues"),
+    ("deprecated", "An AWS SDK call uses a deprecated operation or property"),
+    ("unawaited-send", "An AWS SDK request is never sent, because the future of `send()` is never awaited"),
+    ("empty-required-prop", "An AWS SDK call sets a required property to an empty string, which the service rejects at runtime"),
+    ("unwrapped-send", "The result of an AWS SDK call is unwrapped, which panics when the call fails"),
+    ("unpaginated-call", "A paginated AWS SDK operation is called without going through its pages, so results can be missing"),
+    ("leading-slash-key", "The key passed to an S3 object operation starts with a `/`, which S3 keeps as part of the key"),
+    ("duplicate-setter", "An AWS SDK call sets the same property more than once, so only the last value is sent"),
+    ("malformed-queue-url", "A string literal passed to an AWS SDK call as a queue URL does not look like one, e.g. because it is the name of the queue"),
+    ("policy-violation", "An AWS SDK call does not set the properties that a policy enabled in the attribute requires, like encryption"),
+    ("batch-variant", "An AWS SDK call inside a loop has a batch variant that handles many items in one request"),
+    ("sequential-sends", "Independent AWS SDK calls are awaited one after the other, while they could run at the same time"),
+    ("recommended-props", "An AWS SDK call does not set a property that is not required, but recommended, reported with `recommend = true`"),
+    ("iam-not-allowed", "An AWS SDK call needs an IAM action that the policies in `AWS_SDK_COMPILE_CHECKS_IAM_POLICIES` do not allow"),
+    ("unavailable-in-region", "An AWS SDK call uses a service or operation that is not available in the region the code runs in"),
+    ("unknown-resource", "A queue URL, table name or bucket literal is not one of the resources in the outputs of the infrastructure as code"),
+    ("denied-sdk", "A crate uses an AWS SDK that `AWS_SDK_COMPILE_CHECKS_DENIED_SDKS` denies to it, e.g. IAM in an application crate"),
+    ("above-maximum", "A number passed to a setter is above the maximum set in the attribute, e.g. a provisioned capacity"),
+    ("unused-sdk", "An SDK in the `sdk` attribute is not matched by any call or client of the function, so the attribute might be stale"),
+    ("unselected-sdk", "An AWS SDK call is made on a client of an SDK that the `sdk` attribute does not list"),
+    ("missing-builder-props", "A chain of one of your own builders is missing methods that `AWS_SDK_COMPILE_CHECKS_BUILDERS` requires"),
+    ("hardcoded-resource", "A string literal is passed as a queue URL, table name, bucket or function name, instead of a value from the config"),
+    ("untimed-send", "An AWS SDK call is awaited without a timeout, while no config of the function sets one, so it can hang indefinitely"),
+    ("missing-idempotency-token", "An AWS SDK call of an operation that accepts an idempotency token does not set one, so retrying it can run the operation twice"),
+    ("undeleted-message", "An SQS receive in a function that never deletes the messages, so they are received again after the visibility timeout"),
+    ("sdk-without-data", "A selected SDK exists, but has no required properties in the data, so its calls are not checked"),
+];
+const UNKNOWN_RULE: &str = "unknown";
+
+pub fn to_sarif(findings: &[Finding], root: &Path) -> Value {
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|(id, description)| {
+            json!({
+                "id": id,
+                "shortDescription": { "text": description },
+            })
+        })
+        .collect();
+    let results: Vec<Value> = findings.iter().map(|f| to_result(f, root)).collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "informationUri": TOOL_URI,
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+fn to_result(finding: &Finding, root: &Path) -> Value {
+    let notes = finding.notes.iter().map(|n| format!("\nnote: {}", n));
+    let help = finding.help.iter().map(|h| format!("\nhelp: {}", h));
+    let text: String = std::iter::once(finding.message.clone()).chain(notes).chain(help).collect();
+
+    let artifact_location = json!({
+        "uri": relative_uri(&finding.file, root),
+        "uriBaseId": "%SRCROOT%",
+    });
+    let mut result = json!({
+        "ruleId": finding.code.as_deref().unwrap_or(UNKNOWN_RULE),
+        "level": finding.level,
+        "message": { "text": text },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": artifact_location,
+                "region": {
+                    "startLine": finding.start_line,
+                    "startColumn": finding.start_column,
+                    "endLine": finding.end_line,
+                    "endColumn": finding.end_column,
+                }
+            }
+        }]
+    });
+    if let Some(suggestion) = &finding.suggestion {
+        result["fixes"] = json!([{
+            "description": { "text": format!("replace with `{}`", suggestion.replacement) },
+            "artifactChanges": [{
+                "artifactLocation": artifact_location,
+                "replacements": [{
+                    "deletedRegion": {
+                        "startLine": suggestion.start_line,
+                        "startColumn": suggestion.start_column,
+                        "endLine": suggestion.end_line,
+                        "endColumn": suggestion.end_column,
+                    },
+                    "insertedContent": { "text": suggestion.replacement },
+                }]
+            }]
+        }]);
+    }
+    result
+}
+
+/// rustc receives absolute paths for some crates, SARIF consumers want paths relative to the root of the repository
+fn relative_uri(file: &str, root: &Path) -> String {
+    let path = Path::new(file);
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::findings::Suggestion;
+
+    fn finding(file: &str) -> Finding {
+        Finding {
+            level: "error".to_string(),
+            code: Some("missing-required-props".to_string()),
+            message: "method `receive_message` (from sqs) is missing required argument(s): `queue_url`".to_string(),
+            notes: vec!["a note".to_string()],
+            help: vec![],
+            file: file.to_string(),
+            start_line: 5,
+            start_column: 24,
+            end_line: 5,
+            end_column: 39,
+            suggestion: None,
+        }
+    }
+
+    #[test]
+    fn to_sarif_creates_a_result_with_location_for_every_finding() {
+        let sarif = to_sarif(&[finding("src/main.rs")], Path::new("/project"));
+
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(result["ruleId"], "missing-required-props");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["message"]["text"],
+            "method `receive_message` (from sqs) is missing required argument(s): `queue_url`\nnote: a note"
+        );
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(location["region"]["startLine"], 5);
+        assert_eq!(location["region"]["startColumn"], 24);
+    }
+
+    #[test]
+    fn to_sarif_adds_a_fix_for_a_suggestion() {
+        let without_suggestion = to_sarif(&[finding("src/main.rs")], Path::new("/project"));
+        let suggested = Finding {
+            suggestion: Some(Suggestion {
+                replacement: "#[required_props(sdk = amplify)]".to_string(),
+                start_line: 3,
+                start_column: 1,
+                end_line: 3,
+                end_column: 19,
+            }),
+            ..finding("src/main.rs")
+        };
+
+        let sarif = to_sarif(&[suggested], Path::new("/project"));
+
+        assert!(without_suggestion["runs"][0]["results"][0].get("fixes").is_none());
+        let change = &sarif["runs"][0]["results"][0]["fixes"][0]["artifactChanges"][0];
+        assert_eq!(change["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(change["replacements"][0]["deletedRegion"]["startLine"], 3);
+        assert_eq!(change["replacements"][0]["deletedRegion"]["endColumn"], 19);
+        assert_eq!(change["replacements"][0]["insertedContent"]["text"], "#[required_props(sdk = amplify)]");
+    }
+
+    #[test]
+    fn to_sarif_makes_absolute_paths_relative_to_root() {
+        let sarif = to_sarif(&[finding("/project/src/main.rs")], Path::new("/project"));
+
+        assert_eq!(
+            sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/main.rs"
+        );
+    }
+}
@@ -3,8 +3,10 @@
 
 use aws_config::BehaviorVersion;
 use aws_sdk_compile_checks_macro::required_props;
-use aws_sdk_dynamodb::types::Replica;
+use aws_sdk_dynamodb::types::{AttributeAction, AttributeValue, AttributeValueUpdate, Put, PutRequest, Replica, TransactWriteItem, WriteRequest};
 use aws_sdk_sagemaker::types::ServiceCatalogProvisioningDetails;
+use aws_sdk_bedrockruntime::primitives::Blob;
+use aws_sdk_bedrockruntime::types::Message;
 use aws_sdk_sqs::Client;
 
 // these are normal functions and should not error
@@ -142,6 +144,53 @@ async fn call_with_sqs_client_not_sns_or_ses(client: Client) {
     let _ = client.send_message().queue_url("").message_body("").send().await;
 }
 
+// sns publish needs a message and exactly one destination
+
+#[required_props(sdk = sns)]
+async fn publish_to_topic_or_phone_number(sns_client: aws_sdk_sns::Client) {
+    let _ = sns_client.publish().message("hi").topic_arn("arn:aws:sns:eu-west-1:123456789012:topic").send().await;
+    let _ = sns_client.publish().message("hi").phone_number("+32470000000").send().await;
+}
+
+// dynamodb transactions and batches, with the builders of their items
+
+#[required_props(sdk = dynamodb)]
+async fn transaction_and_batch(dynamodb_client: aws_sdk_dynamodb::Client, id: aws_sdk_dynamodb::types::AttributeValue) {
+    let put = Put::builder().table_name("table").item("id", id.clone()).build().unwrap();
+    let write = TransactWriteItem::builder().put(put).build();
+    let _ = dynamodb_client.transact_write_items().transact_items(write).send().await;
+    let put_request = PutRequest::builder().item("id", id).build().unwrap();
+    let _ = dynamodb_client
+        .batch_write_item()
+        .request_items("table", vec![WriteRequest::builder().put_request(put_request).build()])
+        .send()
+        .await;
+}
+
+// dynamodb updates, with an expression or with the legacy attribute updates
+
+#[required_props(sdk = dynamodb)]
+async fn update_with_expression_or_attribute_updates(dynamodb_client: aws_sdk_dynamodb::Client, id: AttributeValue, status: AttributeValue) {
+    let _ = dynamodb_client
+        .update_item()
+        .table_name("orders")
+        .key("id", id.clone())
+        .update_expression("SET order_status = :status")
+        .expression_attribute_values(":status", status.clone())
+        .send()
+        .await;
+    let update = AttributeValueUpdate::builder().value(status).action(AttributeAction::Put).build();
+    let _ = dynamodb_client.update_item().table_name("orders").key("id", id).attribute_updates("order_status", update).send().await;
+}
+
+// bedrock runtime, with the prompt as the body of `invoke_model` or as messages of `converse`
+
+#[required_props]
+async fn invoke_and_converse(runtime_client: aws_sdk_bedrockruntime::Client, body: Blob, message: Message) {
+    let _ = runtime_client.invoke_model().model_id("amazon.titan-text-express-v1").body(body).send().await;
+    let _ = runtime_client.converse().model_id("amazon.titan-text-express-v1").messages(message).send().await;
+}
+
 // multiple clients
 // (not fully supported)
 
@@ -162,7 +211,7 @@ async fn evidently_and_sagemaker(evidently_client: aws_sdk_evidently::Client, sa
     let _ = sagemaker_client
         .create_project()
         .project_name("name")
-        .service_catalog_provisioning_details(ServiceCatalogProvisioningDetails::builder().build())
+        .service_catalog_provisioning_details(ServiceCatalogProvisioningDetails::builder().product_id("id").build())
         .send()
         .await;
 }
@@ -176,11 +225,23 @@ async fn evidently_and_sagemaker_with_selected_sdks(
     let _ = sagemaker_client
         .create_project()
         .project_name("name")
-        .service_catalog_provisioning_details(ServiceCatalogProvisioningDetails::builder().build())
+        .service_catalog_provisioning_details(ServiceCatalogProvisioningDetails::builder().product_id("id").build())
         .send()
         .await;
 }
 
+#[required_props(instrument = true)]
+async fn receive_message_in_a_span(sqs_client: aws_sdk_sqs::Client) -> Result<usize, aws_sdk_sqs::Error> {
+    let received = sqs_client.receive_message().queue_url("url").send().await?;
+    Ok(received.messages().len())
+}
+
+// the builder is filled in by the caller, so the required properties are asserted when it is sent, in debug builds
+#[required_props(runtime_checks = true)]
+async fn send_prepared_message(request: aws_sdk_sqs::operation::send_message::builders::SendMessageFluentBuilder) {
+    let _ = request.delay_seconds(10).send().await;
+}
+
 // ideally, this would not cause a compile error (though on the other hand, why add the attribute to a call that is not an SDK call?)
 
 // struct SomeClient {}
@@ -3,3 +3,11 @@ fn should_not_compile() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/fails/*.rs");
 }
+
+/// Generated from the data for a sample of the operations of every SDK we depend on, see `aws-sdk-retrieve-required`
+#[test]
+fn generated_matrix() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/matrix/fails/*.rs");
+    t.pass("tests/matrix/passes/*.rs");
+}
@@ -0,0 +1,18 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+fn create() -> aws_sdk_sqs::Client {
+    todo!()
+}
+
+#[required_props(assume_sdk = sqs)]
+async fn do_calls() {
+    let client = create();
+    let _ = client.send_message()
+        .send()
+        .await;
+    let _ = client.receive_message()
+        .send()
+        .await;
+}
+
+fn main() {}
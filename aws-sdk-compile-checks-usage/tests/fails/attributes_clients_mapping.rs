@@ -0,0 +1,16 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+fn create() -> aws_sdk_sqs::Client {
+    todo!()
+}
+
+#[required_props(clients(queue = sqs))]
+async fn send(body: &str) {
+    let queue = create();
+    let _ = queue.send_message()
+        .message_body(body)
+        .send()
+        .await;
+}
+
+fn main() {}
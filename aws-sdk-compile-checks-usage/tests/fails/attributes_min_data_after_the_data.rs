@@ -0,0 +1,8 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(min_data = "2099-01")]
+async fn do_call() {
+    // irrelevant
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(skd = sqs)]
+async fn do_call() {
+    // irrelevant
+}
+
+fn main() {}
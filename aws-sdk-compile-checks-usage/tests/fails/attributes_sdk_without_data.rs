@@ -0,0 +1,12 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_sqs::Client;
+
+// qldbsession exists, but has no rows in our data, so only the sqs call is checked
+#[required_props(sdk = sqs, qldbsession)]
+async fn do_call(sqs_client: Client) {
+    let _ = sqs_client.receive_message()
+        .send()
+        .await;
+}
+
+fn main() {}
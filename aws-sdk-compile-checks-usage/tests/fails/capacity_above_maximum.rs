@@ -0,0 +1,13 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_dynamodb::types::ProvisionedThroughput;
+
+#[required_props(sdk = dynamodb, max(read_capacity_units = 100, write_capacity_units = 100))]
+fn throughput() -> ProvisionedThroughput {
+    ProvisionedThroughput::builder()
+        .read_capacity_units(5000)
+        .write_capacity_units(100)
+        .build()
+        .expect("Throughput to build")
+}
+
+fn main() {}
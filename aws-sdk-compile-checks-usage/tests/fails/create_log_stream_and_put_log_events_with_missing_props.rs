@@ -0,0 +1,24 @@
+use aws_sdk_cloudwatchlogs::types::InputLogEvent;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = cloudwatchlogs)]
+async fn do_call(logs_client: aws_sdk_cloudwatchlogs::Client, timestamp: i64) {
+    logs_client.create_log_stream()
+        .log_group_name("orders")
+        .send()
+        .await
+        .expect("Call to succeed");
+    let event = InputLogEvent::builder()
+        .timestamp(timestamp)
+        .build()
+        .expect("Event to build");
+    logs_client.put_log_events()
+        .log_group_name("orders")
+        .log_stream_name("processing")
+        .log_events(event)
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
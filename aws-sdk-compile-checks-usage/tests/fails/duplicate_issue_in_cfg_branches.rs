@@ -0,0 +1,15 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props]
+async fn receive(sqs_client: aws_sdk_sqs::Client) {
+    #[cfg(debug_assertions)]
+    let _ = sqs_client.receive_message()
+        .send()
+        .await;
+    #[cfg(not(debug_assertions))]
+    let _ = sqs_client.receive_message()
+        .send()
+        .await;
+}
+
+fn main() {}
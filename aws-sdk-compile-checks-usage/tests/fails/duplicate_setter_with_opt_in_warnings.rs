@@ -0,0 +1,21 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = sqs, warn(duplicate))]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
+    sqs_client.send_message()
+        .queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/first")
+        .message_body("body")
+        .queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/second")
+        .send()
+        .await
+        .expect("Call to succeed");
+    sqs_client.purge_queue()
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
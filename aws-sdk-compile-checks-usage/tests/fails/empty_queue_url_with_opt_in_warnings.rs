@@ -0,0 +1,19 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(warn(empty))]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
+    sqs_client.purge_queue()
+        .queue_url("")
+        .send()
+        .await
+        .expect("Call to succeed");
+    sqs_client.receive_message()
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_dynamodb::types::{AttributeValue, ComparisonOperator, Condition};
+
+#[required_props(sdk = dynamodb)]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let dynamodb_client = aws_sdk_dynamodb::Client::new(&aws_config);
+    let condition = Condition::builder()
+        .comparison_operator(ComparisonOperator::Eq)
+        .attribute_value_list(AttributeValue::S("value".to_string()))
+        .build()
+        .expect("Condition to be valid");
+    dynamodb_client.scan()
+        .table_name("table")
+        .scan_filter("key", condition)
+        .filter_expression("key = :value")
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
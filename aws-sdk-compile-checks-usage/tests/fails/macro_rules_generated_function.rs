@@ -0,0 +1,16 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+macro_rules! handler {
+    ($name:ident, $($operation:ident),*) => {
+        #[required_props(sdk = sqs)]
+        async fn $name(client: aws_sdk_sqs::Client) {
+            $(
+                let _ = client.$operation().send().await;
+            )*
+        }
+    };
+}
+
+handler!(queue_handler, receive_message, send_message);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = sqs)]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
+    sqs_client.start_message_move_task()
+        .source_arn("arn:aws:sqs:eu-west-1:1234567890:dead-letter-queue")
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
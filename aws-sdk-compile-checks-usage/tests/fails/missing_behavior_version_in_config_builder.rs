@@ -0,0 +1,17 @@
+use aws_config::Region;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = sqs)]
+async fn do_call() {
+    let config = aws_sdk_sqs::Config::builder()
+        .region(Region::new("eu-west-1"))
+        .build();
+    let sqs_client = aws_sdk_sqs::Client::from_conf(config);
+    sqs_client.purge_queue()
+        .queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/queue")
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
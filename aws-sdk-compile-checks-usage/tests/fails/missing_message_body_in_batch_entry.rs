@@ -0,0 +1,21 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_sqs::types::SendMessageBatchRequestEntry;
+
+#[required_props]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
+    let entry = SendMessageBatchRequestEntry::builder()
+        .id("1")
+        .build()
+        .expect("Entry to be valid");
+    sqs_client.send_message_batch()
+        .queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/queue")
+        .entries(entry)
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_bedrockruntime::types::{ContentBlock, ConversationRole, Message};
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let client = aws_sdk_bedrockruntime::Client::new(&aws_config);
+    let message = Message::builder()
+        .role(ConversationRole::User)
+        .content(ContentBlock::Text("Hello".to_string()))
+        .build()
+        .expect("Message to build");
+    client.converse()
+        .messages(message)
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_sagemaker::types::ServiceCatalogProvisioningDetails;
+
+#[required_props]
+fn provisioning_details() -> ServiceCatalogProvisioningDetails {
+    ServiceCatalogProvisioningDetails::builder()
+        .provisioning_artifact_id("pa-1234567890")
+        .build()
+}
+
+fn main() {}
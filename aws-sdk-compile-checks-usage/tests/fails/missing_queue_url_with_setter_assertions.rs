@@ -0,0 +1,17 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_sqs::Client;
+
+// the setters of every checked call exist in our version of the SDK, so only the missing property is reported
+#[required_props(sdk = sqs, assert_setters)]
+async fn do_calls(sqs_client: Client) {
+    let _ = sqs_client.receive_message()
+        .queue_url("url")
+        .send()
+        .await;
+    let _ = sqs_client.send_message()
+        .message_body("some message")
+        .send()
+        .await;
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_dynamodb::types::{AttributeValue, Put, TransactWriteItem};
+
+#[required_props(sdk = dynamodb)]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let dynamodb_client = aws_sdk_dynamodb::Client::new(&aws_config);
+    let put = Put::builder()
+        .item("id", AttributeValue::S("id".to_string()))
+        .build()
+        .expect("Put to build");
+    dynamodb_client.transact_write_items()
+        .transact_items(TransactWriteItem::builder().put(put).build())
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_dynamodb::client::Waiters;
+
+#[required_props]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let dynamodb_client = aws_sdk_dynamodb::Client::new(&aws_config);
+    dynamodb_client.wait_until_table_exists()
+        .wait(Duration::from_secs(60))
+        .await
+        .expect("Table to exist");
+}
+
+fn main() {}
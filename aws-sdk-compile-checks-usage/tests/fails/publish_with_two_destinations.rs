@@ -0,0 +1,17 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = sns)]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let sns_client = aws_sdk_sns::Client::new(&aws_config);
+    sns_client.publish()
+        .message("hi")
+        .topic_arn("arn:aws:sns:eu-west-1:123456789012:topic")
+        .phone_number("+32470000000")
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_dynamodb::types::AttributeValue;
+
+struct Settings;
+
+impl Settings {
+    fn table_name(&self) -> String {
+        todo!()
+    }
+}
+
+#[required_props]
+async fn put_all(client: aws_sdk_dynamodb::Client, settings: Settings, items: Vec<AttributeValue>) {
+    let _table = settings.table_name();
+    let requests: Vec<_> = items.iter().map(|i| client.put_item().item("id", i.clone())).collect();
+    for request in requests {
+        let _ = request.send().await;
+    }
+}
+
+fn main() {}
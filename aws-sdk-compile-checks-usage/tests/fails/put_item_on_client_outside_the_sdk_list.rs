@@ -0,0 +1,8 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = sqs, sns)]
+async fn store_order(dynamodb_client: aws_sdk_dynamodb::Client) {
+    let _ = dynamodb_client.put_item().send().await;
+}
+
+fn main() {}
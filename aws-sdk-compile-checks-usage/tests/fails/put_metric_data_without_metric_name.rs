@@ -0,0 +1,19 @@
+use aws_sdk_cloudwatch::types::{Dimension, MetricDatum, StandardUnit};
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = cloudwatch)]
+async fn do_call(cloudwatch_client: aws_sdk_cloudwatch::Client) {
+    let datum = MetricDatum::builder()
+        .dimensions(Dimension::builder().name("queue").value("orders").build())
+        .value(1.0)
+        .unit(StandardUnit::Count)
+        .build();
+    cloudwatch_client.put_metric_data()
+        .namespace("Orders")
+        .metric_data(datum)
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
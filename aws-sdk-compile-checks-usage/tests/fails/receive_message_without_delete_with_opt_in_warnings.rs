@@ -0,0 +1,20 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = sqs, warn(sqs_delete))]
+async fn do_call(sqs_client: aws_sdk_sqs::Client) {
+    let received = sqs_client.receive_message()
+        .queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/orders")
+        .send()
+        .await
+        .expect("Call to succeed");
+    for message in received.messages() {
+        sqs_client.send_message()
+            .queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/processed")
+            .send()
+            .await
+            .expect("Call to succeed");
+        println!("{:?}", message.body());
+    }
+}
+
+fn main() {}
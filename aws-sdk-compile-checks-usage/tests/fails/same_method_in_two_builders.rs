@@ -0,0 +1,12 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_sqs::Client;
+
+#[required_props]
+async fn do_calls(sqs_client: Client) {
+    let with_url = sqs_client.send_message().queue_url("url").message_body("body");
+    let without_url = sqs_client.send_message().message_body("body");
+    let _ = with_url.send().await;
+    let _ = without_url.send().await;
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = sqs, warn(batch))]
+async fn do_call(bodies: Vec<String>) {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
+    for body in bodies {
+        sqs_client.send_message()
+            .message_body(body)
+            .send()
+            .await
+            .expect("Call to succeed");
+    }
+}
+
+fn main() {}
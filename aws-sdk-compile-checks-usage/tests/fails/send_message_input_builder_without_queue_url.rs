@@ -0,0 +1,13 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_sqs::operation::send_message::SendMessageInput;
+
+#[required_props(sdk = sqs)]
+async fn do_call(sqs_client: aws_sdk_sqs::Client) {
+    SendMessageInput::builder()
+        .message_body("hello")
+        .send_with(&sqs_client)
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props]
+async fn do_call(maybe_client: Option<aws_sdk_sqs::Client>) {
+    let Some(client) = maybe_client else {
+        return;
+    };
+    let _ = client.send_message()
+        .message_body("hello")
+        .send()
+        .await;
+}
+
+fn main() {}
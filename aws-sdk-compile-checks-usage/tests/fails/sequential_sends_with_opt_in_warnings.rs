@@ -0,0 +1,24 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = sqs, warn(concurrent))]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
+    let _messages = sqs_client.receive_message()
+        .queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/queue")
+        .send()
+        .await
+        .expect("Call to succeed");
+    let _attributes = sqs_client.get_queue_attributes()
+        .queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/queue")
+        .send()
+        .await
+        .expect("Call to succeed");
+    sqs_client.purge_queue()
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
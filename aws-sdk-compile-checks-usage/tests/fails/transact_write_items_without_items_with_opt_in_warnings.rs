@@ -0,0 +1,14 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = dynamodb, warn(idempotency))]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let dynamodb_client = aws_sdk_dynamodb::Client::new(&aws_config);
+    dynamodb_client.transact_write_items()
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_dynamodb::types::TransactWriteItem;
+
+#[required_props(sdk = dynamodb, warn(idempotency))]
+async fn do_call(dynamodb_client: aws_sdk_dynamodb::Client, item: TransactWriteItem) {
+    dynamodb_client.transact_write_items()
+        .transact_items(item)
+        .send()
+        .await
+        .expect("Call to succeed");
+    dynamodb_client.get_item()
+        .table_name("table")
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(warn(unawaited))]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
+    let _ = sqs_client.purge_queue().queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/queue").send();
+    sqs_client.receive_message()
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_sqs::types::QueueAttributeName;
+
+#[required_props(sdk = sqs, policy = encryption)]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
+    sqs_client.create_queue()
+        .queue_name("queue")
+        .attributes(QueueAttributeName::DelaySeconds, "10")
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
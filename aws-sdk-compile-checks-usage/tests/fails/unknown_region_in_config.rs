@@ -0,0 +1,18 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = sqs)]
+async fn do_call() {
+    let aws_config = aws_config::defaults(BehaviorVersion::latest())
+        .region("eu-west1")
+        .load()
+        .await;
+    let sqs_client = aws_sdk_sqs::Client::new(&aws_config);
+    sqs_client.purge_queue()
+        .queue_url("https://sqs.eu-west-1.amazonaws.com/123456789012/queue")
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
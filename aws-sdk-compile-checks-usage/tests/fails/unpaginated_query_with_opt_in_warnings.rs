@@ -0,0 +1,22 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props(sdk = dynamodb, warn(pagination))]
+async fn do_call() {
+    let aws_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let dynamodb_client = aws_sdk_dynamodb::Client::new(&aws_config);
+    let output = dynamodb_client.query()
+        .table_name("table")
+        .key_condition_expression("id = :id")
+        .send()
+        .await
+        .expect("Call to succeed");
+    println!("{:?}", output.items());
+    dynamodb_client.get_item()
+        .table_name("table")
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props]
+struct Handler {
+    client: aws_sdk_sqs::Client,
+}
+
+#[required_props]
+impl Handler {
+    async fn handle(&self) {
+        let _ = self.client.receive_message().queue_url("url").send().await;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_dynamodb::types::AttributeValue;
+
+#[required_props(sdk = dynamodb)]
+async fn do_call(dynamodb_client: aws_sdk_dynamodb::Client) {
+    dynamodb_client.update_item()
+        .table_name("orders")
+        .update_expression("SET order_status = :status")
+        .expression_attribute_values(":status", AttributeValue::S("shipped".to_string()))
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
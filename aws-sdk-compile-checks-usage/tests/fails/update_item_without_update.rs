@@ -0,0 +1,14 @@
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_dynamodb::types::AttributeValue;
+
+#[required_props(sdk = dynamodb)]
+async fn do_call(dynamodb_client: aws_sdk_dynamodb::Client) {
+    dynamodb_client.update_item()
+        .table_name("orders")
+        .key("id", AttributeValue::S("order-1".to_string()))
+        .send()
+        .await
+        .expect("Call to succeed");
+}
+
+fn main() {}
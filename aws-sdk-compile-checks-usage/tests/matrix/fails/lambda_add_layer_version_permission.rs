@@ -0,0 +1,11 @@
+// generated with `cargo run -- matrix` in aws-sdk-retrieve-required, do not edit
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props]
+async fn call(client: aws_sdk_lambda::Client) {
+    let _ = client.add_layer_version_permission()
+        .send()
+        .await;
+}
+
+fn main() {}
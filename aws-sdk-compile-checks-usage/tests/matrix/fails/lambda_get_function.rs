@@ -0,0 +1,11 @@
+// generated with `cargo run -- matrix` in aws-sdk-retrieve-required, do not edit
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props]
+async fn call(client: aws_sdk_lambda::Client) {
+    let _ = client.get_function()
+        .send()
+        .await;
+}
+
+fn main() {}
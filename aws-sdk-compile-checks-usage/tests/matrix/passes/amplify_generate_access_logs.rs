@@ -0,0 +1,53 @@
+// generated with `cargo run -- matrix` in aws-sdk-retrieve-required, do not edit
+#![allow(dead_code)]
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_amplify::operation::generate_access_logs::builders::GenerateAccessLogsFluentBuilder as Builder;
+
+struct Getter<T>(fn(&Builder) -> &Option<T>);
+
+// takes precedence over `Value` (because of the extra reference) when the property is a list
+trait ListItem<T> {
+    fn value(&self) -> T;
+}
+
+impl<T> ListItem<T> for &Getter<Vec<T>> {
+    fn value(&self) -> T {
+        unimplemented!()
+    }
+}
+
+trait Value<T> {
+    fn value(&self) -> T;
+}
+
+impl<T> Value<T> for Getter<T> {
+    fn value(&self) -> T {
+        unimplemented!()
+    }
+}
+
+trait MapEntry<K, V> {
+    fn key(&self) -> K;
+    fn entry_value(&self) -> V;
+}
+
+impl<K, V> MapEntry<K, V> for Getter<std::collections::HashMap<K, V>> {
+    fn key(&self) -> K {
+        unimplemented!()
+    }
+
+    fn entry_value(&self) -> V {
+        unimplemented!()
+    }
+}
+
+#[required_props]
+async fn call(client: aws_sdk_amplify::Client) {
+    let _ = client.generate_access_logs()
+        .domain_name((&&Getter(Builder::get_domain_name)).value())
+        .app_id((&&Getter(Builder::get_app_id)).value())
+        .send()
+        .await;
+}
+
+fn main() {}
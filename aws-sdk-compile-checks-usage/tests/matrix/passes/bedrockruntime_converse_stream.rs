@@ -0,0 +1,52 @@
+// generated with `cargo run -- matrix` in aws-sdk-retrieve-required, do not edit
+#![allow(dead_code)]
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_bedrockruntime::operation::converse_stream::builders::ConverseStreamFluentBuilder as Builder;
+
+struct Getter<T>(fn(&Builder) -> &Option<T>);
+
+// takes precedence over `Value` (because of the extra reference) when the property is a list
+trait ListItem<T> {
+    fn value(&self) -> T;
+}
+
+impl<T> ListItem<T> for &Getter<Vec<T>> {
+    fn value(&self) -> T {
+        unimplemented!()
+    }
+}
+
+trait Value<T> {
+    fn value(&self) -> T;
+}
+
+impl<T> Value<T> for Getter<T> {
+    fn value(&self) -> T {
+        unimplemented!()
+    }
+}
+
+trait MapEntry<K, V> {
+    fn key(&self) -> K;
+    fn entry_value(&self) -> V;
+}
+
+impl<K, V> MapEntry<K, V> for Getter<std::collections::HashMap<K, V>> {
+    fn key(&self) -> K {
+        unimplemented!()
+    }
+
+    fn entry_value(&self) -> V {
+        unimplemented!()
+    }
+}
+
+#[required_props]
+async fn call(client: aws_sdk_bedrockruntime::Client) {
+    let _ = client.converse_stream()
+        .model_id((&&Getter(Builder::get_model_id)).value())
+        .send()
+        .await;
+}
+
+fn main() {}
@@ -2,9 +2,37 @@
 
 set -euo pipefail
 
+# stop when a new SDK is in neither of our input files, instead of leaving it out of the data
+cargo run -- unlisted
 rm -r output
-mkdir output
+mkdir -p output/types output/deprecated output/operations output/waiters
 
 cargo run
 cd output && cat *.csv >> ../required_props_info.csv && cd ..
-mv required_props_info.csv ../aws-sdk-compile-checks/aws-sdk-compile-checks-macro/required_props_info
\ No newline at end of file
+# types that only need one of their fields have no required fields in the documentation, so they get a row of their own
+cat output/types/*.csv input/at_least_one_of_types.csv | LC_ALL=C sort > required_type_props_info.csv
+cat output/deprecated/*.csv > deprecated.csv
+cat output/operations/*.csv > operation_metadata.csv
+cat output/waiters/*.csv > waiters.csv
+# the documentation does not tell us when one of several properties is required, so those groups are maintained by hand
+# they are added to the row of their method, as the macro does not accept duplicate rows
+awk -F, -v OFS=, 'NR == FNR { groups[$1 "," $2] = $3; next } ($1 "," $2) in groups { $3 = $3 " " groups[$1 "," $2] } 1' \
+  input/at_least_one_of.csv required_props_info.csv > merged_required_props_info.csv
+mv merged_required_props_info.csv required_props_info.csv
+echo "$(date +%Y-%m),$(cat output/sdk_version)" > data_version
+# the SDKs retrieved from the documentation of another version, so their data is described with that version
+awk -F, -v OFS=, -v month="$(date +%Y-%m)" 'NF == 2 { print $1, month, $2 }' input/docs_versions.csv > service_data_versions.csv
+# every SDK we know about, so the macro can tell an SDK without data apart from one that does not exist
+cat input/names_of_sdk_crates input/excluded_sdk_crates | grep -v '^$' | sort -u > known_sdks
+# record which properties became (or stopped being) required compared to the data we had
+cargo run -- changes ../aws-sdk-compile-checks-data/required_properties_info/required_props_info.csv required_props_info.csv ../aws-sdk-compile-checks-data/required_properties_info/requirement_changes.csv
+mv required_props_info.csv ../aws-sdk-compile-checks-data/required_properties_info/
+mv required_type_props_info.csv ../aws-sdk-compile-checks-data/required_properties_info/
+mv deprecated.csv ../aws-sdk-compile-checks-data/required_properties_info/
+mv operation_metadata.csv ../aws-sdk-compile-checks-data/required_properties_info/
+mv waiters.csv ../aws-sdk-compile-checks-data/required_properties_info/
+mv data_version ../aws-sdk-compile-checks-data/required_properties_info/
+mv service_data_versions.csv ../aws-sdk-compile-checks-data/required_properties_info/
+mv known_sdks ../aws-sdk-compile-checks-data/required_properties_info/
+# the regions of the SDKs do not come from their documentation, but from the endpoints of botocore
+./regions.sh
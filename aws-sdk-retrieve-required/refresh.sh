@@ -0,0 +1,55 @@
+#!/bin/bash
+
+set -euo pipefail
+
+# retrieves the data of only the given services (e.g. `./refresh.sh bedrock bedrockruntime`) and replaces their rows in the data crate,
+# for a service that was just launched or added to input/docs_versions.csv, without retrieving every SDK again.
+# With `--types`, only the required fields of the builders in their `types` modules are retrieved
+types_only=false
+if [ "${1:-}" = "--types" ]; then
+  types_only=true
+  shift
+fi
+if [ $# -eq 0 ]; then
+  echo "usage: ./refresh.sh [--types] <service>..."
+  exit 1
+fi
+data=../aws-sdk-compile-checks-data/required_properties_info
+refreshed="^($(IFS='|'; echo "$*")),"
+
+rm -rf output
+mkdir -p output/types output/deprecated output/operations output/waiters
+
+# the rows of the other services, with the new rows of the refreshed ones
+replace_rows() {
+  local file=$1
+  shift
+  { grep -v -E "$refreshed" "$data/$file" || true; cat "$@"; } | LC_ALL=C sort > "$file"
+}
+
+if $types_only; then
+  cargo run -- types "$@"
+  replace_rows required_type_props_info.csv output/types/*.csv <(grep -E "$refreshed" input/at_least_one_of_types.csv || true)
+  mv required_type_props_info.csv "$data/"
+  exit 0
+fi
+
+cargo run -- refresh "$@"
+{ for service in "$@"; do cat "output/$service.csv"; done; } > refreshed_props_info.csv
+# the same merge of the 'at least one of' groups as build.sh
+awk -F, -v OFS=, 'NR == FNR { groups[$1 "," $2] = $3; next } ($1 "," $2) in groups { $3 = $3 " " groups[$1 "," $2] } 1' \
+  input/at_least_one_of.csv refreshed_props_info.csv > merged_props_info.csv
+replace_rows required_props_info.csv merged_props_info.csv
+rm refreshed_props_info.csv merged_props_info.csv
+replace_rows required_type_props_info.csv output/types/*.csv <(grep -E "$refreshed" input/at_least_one_of_types.csv || true)
+replace_rows deprecated.csv output/deprecated/*.csv
+replace_rows operation_metadata.csv output/operations/*.csv
+replace_rows waiters.csv output/waiters/*.csv
+# data_version no longer describes the refreshed services, their month and version go in a row of their own
+replace_rows service_data_versions.csv <(for service in "$@"; do
+  version=$(grep "^$service," input/docs_versions.csv | cut -d, -f2)
+  echo "$service,$(date +%Y-%m),${version:-$(cat output/sdk_version)}"
+done)
+
+cargo run -- changes "$data/required_props_info.csv" required_props_info.csv "$data/requirement_changes.csv"
+mv required_props_info.csv required_type_props_info.csv deprecated.csv operation_metadata.csv waiters.csv service_data_versions.csv "$data/"
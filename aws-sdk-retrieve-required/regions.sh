@@ -0,0 +1,18 @@
+#!/bin/bash
+
+set -euo pipefail
+
+# retrieves the regions the SDKs are available in from the endpoints of botocore, without retrieving the documentation of the SDKs.
+# BOTOCORE_DATA can point to the `botocore/data` directory of a checkout or installation of botocore, otherwise the repository is cloned
+data=../aws-sdk-compile-checks-data/required_properties_info
+
+mkdir -p output
+botocore_data=${BOTOCORE_DATA:-output/botocore/botocore/data}
+if [ -z "${BOTOCORE_DATA:-}" ]; then
+  rm -rf output/botocore
+  git clone --depth 1 https://github.com/boto/botocore output/botocore
+fi
+cargo run -- regions "$botocore_data"
+# services (like s3vectors) and operations that botocore has no endpoints for are maintained by hand
+cat output/service_regions.csv input/service_regions.csv | LC_ALL=C sort > service_regions.csv
+mv service_regions.csv output/partitions.csv "$data/"
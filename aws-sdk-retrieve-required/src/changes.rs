@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+
+/// Compares the required properties of the previous data with the newly retrieved data,
+/// and appends every property that became required (or stopped being required) to the changes file.
+/// Methods that were added or removed are not changes in requirements, so those are ignored.
+/// `version` gives the SDK version of the documentation of a service, which is where the change was seen
+pub fn record_changes(previous_csv: &str, new_csv: &str, changes_file: &str, version: fn(&str) -> &str) -> Result<()> {
+    let previous = std::fs::read_to_string(previous_csv).with_context(|| format!("failed to read {}", previous_csv))?;
+    let new = std::fs::read_to_string(new_csv).with_context(|| format!("failed to read {}", new_csv))?;
+    let changes: String = changes_between(&previous, &new, version).into_iter().map(|c| format!("{}\n", c)).collect();
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(changes_file)
+        .and_then(|mut f| f.write_all(changes.as_bytes()))
+        .with_context(|| format!("failed to write changes to {}", changes_file))
+}
+
+fn changes_between(previous: &str, new: &str, version: fn(&str) -> &str) -> Vec<String> {
+    let previous = required_props_by_method(previous);
+    let new = required_props_by_method(new);
+    let mut changes = vec![];
+
+    for ((service, method), new_props) in &new {
+        if let Some(previous_props) = previous.get(&(service, method)) {
+            for prop in new_props.difference(previous_props) {
+                changes.push(format!("{},{},{},required,{}", service, method, prop, version(service)));
+            }
+            for prop in previous_props.difference(new_props) {
+                changes.push(format!("{},{},{},optional,{}", service, method, prop, version(service)));
+            }
+        }
+    }
+
+    changes
+}
+
+/// A method can have multiple lines (e.g. one for its 'at least one of' groups), so their properties are combined
+fn required_props_by_method(csv: &str) -> BTreeMap<(&str, &str), BTreeSet<&str>> {
+    let mut by_method: BTreeMap<(&str, &str), BTreeSet<&str>> = BTreeMap::new();
+    for line in csv.lines() {
+        // the other columns, with the docs url and fluent builder, are not relevant here
+        let mut parts = line.splitn(4, ',');
+        if let (Some(service), Some(method), Some(props)) = (parts.next(), parts.next(), parts.next()) {
+            by_method.entry((service, method)).or_default().extend(props.split_whitespace());
+        }
+    }
+    by_method
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_between_finds_new_and_removed_required_properties() {
+        let previous = "s3,put_object,bucket key,https://docs.rs\nsqs,send_message,queue_url message_body\nsqs,removed,queue_url";
+        let new = "s3,put_object,bucket\nsqs,send_message,queue_url message_body delay\nsqs,added,queue_url";
+
+        let actual = changes_between(previous, new, |_| "1.21.0");
+
+        assert_eq!(
+            actual,
+            vec!["s3,put_object,key,optional,1.21.0", "sqs,send_message,delay,required,1.21.0"]
+        );
+    }
+
+    #[test]
+    fn changes_between_uses_the_version_of_the_service() {
+        let previous = "s3,put_object,bucket key\ns3tables,create_table,name";
+        let new = "s3,put_object,bucket\ns3tables,create_table,name format";
+
+        let actual = changes_between(previous, new, |service| if service == "s3tables" { "1.71.0" } else { "1.21.0" });
+
+        assert_eq!(actual, vec!["s3,put_object,key,optional,1.21.0", "s3tables,create_table,format,required,1.71.0"]);
+    }
+
+    #[test]
+    fn changes_between_combines_lines_of_the_same_method() {
+        let previous = "sns,publish,message\nsns,publish,topic_arn|target_arn";
+        let new = "sns,publish,message\nsns,publish,topic_arn|target_arn|phone_number";
+
+        let actual = changes_between(previous, new, |_| "1.21.0");
+
+        assert_eq!(
+            actual,
+            vec!["sns,publish,topic_arn|target_arn|phone_number,required,1.21.0", "sns,publish,topic_arn|target_arn,optional,1.21.0"]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use reqwest::header::USER_AGENT;
+use serde::Deserialize;
+
+const CRATES_URL: &str = "https://crates.io/api/v1/crates";
+const SDK_CRATE_PREFIX: &str = "aws-sdk-";
+// other crates use the prefix as well (like ours), so we only keep those from the repository of the SDK
+const SDK_REPOSITORY: &str = "https://github.com/awslabs/aws-sdk-rust";
+const PER_PAGE: usize = 100; // the maximum of the crates.io API
+// crates.io rejects requests without a user agent
+const RETRIEVER_USER_AGENT: &str = "aws-sdk-retrieve-required (https://github.com/VanOvermeire/aws-sdk-compile-checks)";
+
+#[derive(Deserialize)]
+struct CratesPage {
+    crates: Vec<PublishedCrate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PublishedCrate {
+    name: String,
+    repository: Option<String>,
+}
+
+/// The SDKs published on crates.io that are not in `listed`, e.g. `s3vectors` when it was just launched
+pub fn unlisted_sdks(client: &Client, listed: &[String]) -> Result<Vec<String>> {
+    Ok(unlisted(&published_sdk_crates(client)?, listed))
+}
+
+fn published_sdk_crates(client: &Client) -> Result<Vec<PublishedCrate>> {
+    let mut published = vec![];
+    for page in 1.. {
+        let url = format!("{}?q={}&per_page={}&page={}", CRATES_URL, SDK_CRATE_PREFIX, PER_PAGE, page);
+        let crates = client
+            .get(&url)
+            .header(USER_AGENT, RETRIEVER_USER_AGENT)
+            .send()
+            .with_context(|| format!("call to url {} failed", url))?
+            .json::<CratesPage>()
+            .with_context(|| format!("call to get crates for url {} failed", url))?
+            .crates;
+        let last_page = crates.len() < PER_PAGE;
+        published.extend(crates);
+        if last_page {
+            break;
+        }
+    }
+    Ok(published)
+}
+
+fn unlisted(published: &[PublishedCrate], listed: &[String]) -> Vec<String> {
+    let mut unlisted: Vec<String> = published
+        .iter()
+        .filter(|c| c.repository.as_deref().is_some_and(|r| r.trim_end_matches('/') == SDK_REPOSITORY))
+        .filter_map(|c| c.name.strip_prefix(SDK_CRATE_PREFIX))
+        .filter(|service| !listed.iter().any(|l| l == service))
+        .map(|service| service.to_string())
+        .collect();
+    unlisted.sort();
+    unlisted.dedup();
+    unlisted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn published(name: &str, repository: Option<&str>) -> PublishedCrate {
+        PublishedCrate {
+            name: name.to_string(),
+            repository: repository.map(|r| r.to_string()),
+        }
+    }
+
+    #[test]
+    fn unlisted_only_keeps_new_crates_of_the_sdk() {
+        let published = vec![
+            published("aws-sdk-s3vectors", Some("https://github.com/awslabs/aws-sdk-rust")),
+            published("aws-sdk-s3", Some("https://github.com/awslabs/aws-sdk-rust")),
+            published("aws-sdk-s3tables", Some("https://github.com/awslabs/aws-sdk-rust/")),
+            published("aws-sdk-compile-checks", Some("https://github.com/VanOvermeire/aws-sdk-compile-checks")),
+            published("aws-sdk-unknown", None),
+        ];
+
+        assert_eq!(unlisted(&published, &["s3".to_string()]), vec!["s3tables", "s3vectors"]);
+    }
+}
@@ -0,0 +1,54 @@
+use scraper::{Html, Selector};
+
+/// Lines of `service,method,,note` for the deprecated methods of a client, the note being the deprecation message of the docs.
+/// The property column is for deprecated properties, which the client documentation does not mark
+pub fn deprecated_methods(class_selector: &Selector, method_selector: &Selector, deprecated_selector: &Selector, docs: &str, service: &str) -> Vec<String> {
+    let document = Html::parse_document(docs);
+
+    document
+        .select(class_selector)
+        .filter_map(|element| {
+            let deprecated = element.select(deprecated_selector).next()?;
+            let method_name = element.select(method_selector).next()?.inner_html();
+            Some(format!("{},{},,{}", service, method_name, deprecation_note(&deprecated.text().collect::<String>())))
+        })
+        .collect()
+}
+
+/// Turns e.g. `👎Deprecated: use something else` into `use something else`, on a single line, and quoted when it contains a comma or quote
+fn deprecation_note(text: &str) -> String {
+    let note = text.split_once("Deprecated").map(|(_, note)| note).unwrap_or(text);
+    let note = note.trim_start_matches(':').split_whitespace().collect::<Vec<_>>().join(" ");
+    if note.contains([',', '"']) {
+        format!("\"{}\"", note.replace('"', "\"\""))
+    } else {
+        note
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deprecated_methods_with_and_without_note() {
+        let docs = r##"<div class="impl-items">
+            <details><summary><section><h4><a href="#method.old">old</a></h4></section></summary>
+                <div class="stab deprecated"><span class="emoji">👎</span><span>Deprecated: use
+                `new`, it is faster</span></div></details>
+            <details><summary><section><h4><a href="#method.older">older</a></h4></section></summary>
+                <div class="stab deprecated"><span class="emoji">👎</span><span>Deprecated</span></div></details>
+            <details><summary><section><h4><a href="#method.new">new</a></h4></section></summary></details>
+        </div>"##;
+
+        let actual = deprecated_methods(
+            &Selector::parse(".impl-items > details").unwrap(),
+            &Selector::parse("summary > section h4 > a").unwrap(),
+            &Selector::parse(".stab.deprecated").unwrap(),
+            docs,
+            "s3",
+        );
+
+        assert_eq!(actual, vec!["s3,old,,\"use `new`, it is faster\"", "s3,older,,"]);
+    }
+}
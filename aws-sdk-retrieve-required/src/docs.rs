@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+
+use crate::docs_version;
+
+const DOCS_RS: &str = "https://docs.rs/";
+/// When set, pages are read from this directory (the `target/doc` of a `cargo doc` of the SDK crates) instead of docs.rs.
+/// The urls in the output still point to docs.rs, so the directory should have the documentation of the versions we would retrieve
+const DOCS_DIR_ENV: &str = "AWS_SDK_DOCS_DIR";
+
+/// The url of a page of the documentation of the SDK, e.g. `client/struct.Client.html`
+pub fn crate_url(service: &str, page: &str) -> String {
+    format!("{}aws-sdk-{}/{}/aws_sdk_{}/{}", DOCS_RS, service, docs_version(service), service, page)
+}
+
+/// The text of the page
+pub fn retrieve(client: &Client, url: &str) -> Result<String> {
+    retrieve_if_present(client, url)?.with_context(|| format!("no page at url {}", url))
+}
+
+/// The text of the page, or `None` when it does not exist
+pub fn retrieve_if_present(client: &Client, url: &str) -> Result<Option<String>> {
+    if let Ok(docs_dir) = std::env::var(DOCS_DIR_ENV) {
+        let path = local_path(&docs_dir, url).with_context(|| format!("url {} is not a page of the documentation of an SDK", url))?;
+        return Ok(std::fs::read_to_string(path).ok());
+    }
+    let response = client.get(url).send().with_context(|| format!("call to url {} failed", url))?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    response.text().map(Some).with_context(|| format!("call to get text for url {} failed", url))
+}
+
+/// E.g. `target/doc/aws_sdk_sqs/client/struct.Client.html` for `https://docs.rs/aws-sdk-sqs/1.18.0/aws_sdk_sqs/client/struct.Client.html`
+fn local_path(docs_dir: &str, url: &str) -> Option<PathBuf> {
+    let page = url.strip_prefix(DOCS_RS)?.split(['#', '?']).next()?;
+    // skip the name and the version of the crate, which `cargo doc` does not put in its paths
+    let mut parts = page.splitn(3, '/');
+    let (_, _, page) = (parts.next()?, parts.next()?, parts.next()?);
+    Some(PathBuf::from(docs_dir).join(page))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_path_leaves_out_the_crate_and_version() {
+        assert_eq!(
+            local_path("target/doc", "https://docs.rs/aws-sdk-sqs/1.18.0/aws_sdk_sqs/client/struct.Client.html#method.send_message"),
+            Some(PathBuf::from("target/doc/aws_sdk_sqs/client/struct.Client.html"))
+        );
+        assert_eq!(local_path("target/doc", "https://crates.io/crates/aws-sdk-sqs"), None);
+    }
+}
@@ -2,28 +2,80 @@ use anyhow::{Context, Result};
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 use reqwest::blocking::Client;
+use reqwest::Url;
 use scraper::selectable::Selectable;
 use scraper::{Html, Selector};
 use serde::Serialize;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Error};
 
+mod changes;
+mod crates;
+mod deprecations;
+mod docs;
+mod matrix;
+mod operations;
+mod regions;
+mod types;
+
+const SDK_DOCS_VERSION: &str = "1.18.0"; // version of the SDK crates whose documentation we check
+/// `service,version` for SDKs that need the documentation of another version, like those launched after `SDK_DOCS_VERSION` was published
+const DOCS_VERSIONS: &str = include_str!("../input/docs_versions.csv");
+
 #[derive(Serialize)]
 struct Record<'a> {
     service: &'a str,
     method_name: String,
     property_names: String,
+    docs_url: String, // documentation of the builder
+    builder: String,  // name of the fluent builder, e.g. `SendMessageFluentBuilder`, empty for the builders of types
 }
 
 fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if let [_, command, previous_csv, new_csv, changes_file] = &args[..] {
+        if command == "changes" {
+            return changes::record_changes(previous_csv, new_csv, changes_file, docs_version);
+        }
+    }
+    if let [_, command, required_props_csv, usage_dir] = &args[..] {
+        if command == "matrix" {
+            return matrix::write_matrix(required_props_csv, usage_dir);
+        }
+    }
+    if let [_, command, botocore_data] = &args[..] {
+        if command == "regions" {
+            return regions::write_regions(botocore_data, &retrieve_services_from_file()?);
+        }
+    }
+    if let [_, command] = &args[..] {
+        if command == "unlisted" {
+            return report_unlisted_sdks();
+        }
+    }
+    if let [_, command, services @ ..] = &args[..] {
+        if command == "refresh" && !services.is_empty() {
+            return retrieve_services(services);
+        }
+        if command == "types" && !services.is_empty() {
+            return retrieve_types(services);
+        }
+    }
+
+    retrieve_services(&retrieve_services_from_file()?)
+}
+
+/// Writes the data of the services to the `output` folder
+fn retrieve_services(services: &[String]) -> Result<()> {
     // setup
-    let services = retrieve_services_from_file()?;
     let client = Client::new();
     let class_selector = Selector::parse(".impl-items > details").unwrap();
     // `section h4` because there are sometimes one or more code tags between the two
     let method_selector = Selector::parse("summary > section h4 > a").unwrap();
     let properties_selector = Selector::parse("div > ul > li > ul > li").unwrap();
     let property_selector = Selector::parse("code").unwrap();
+    let link_selector = Selector::parse("a").unwrap();
+    let deprecated_selector = Selector::parse(".stab.deprecated").unwrap();
 
     // retrieve props per service
     let results = services
@@ -37,10 +89,26 @@ fn main() -> Result<()> {
                 &method_selector,
                 &properties_selector,
                 &property_selector,
+                &link_selector,
                 &docs,
                 service,
             )?;
-            write_to_file(service, required_props_per_method)?;
+            write_to_file(&format!("output/{}.csv", service), service, required_props_per_method)?;
+            // written by hand, because the deprecations already are csv lines
+            let deprecated = deprecations::deprecated_methods(&class_selector, &method_selector, &deprecated_selector, &docs, service);
+            std::fs::write(format!("output/deprecated/{}.csv", service), deprecated.iter().map(|d| format!("{}\n", d)).collect::<String>())
+                .with_context(|| format!("failed to write deprecations for {}", service))?;
+            let waiters = operations::waiters(&client, &link_selector, service)?;
+            std::fs::write(
+                format!("output/waiters/{}.csv", service),
+                waiters.iter().map(|(waiter, operation)| format!("{},{},{}\n", service, waiter, operation)).collect::<String>(),
+            )
+            .with_context(|| format!("failed to write waiters for {}", service))?;
+            let metadata = operations::operation_metadata(&client, &class_selector, &method_selector, &link_selector, &docs, service, &waiters)?;
+            std::fs::write(format!("output/operations/{}.csv", service), metadata.iter().map(|m| format!("{}\n", m)).collect::<String>())
+                .with_context(|| format!("failed to write operation metadata for {}", service))?;
+            let required_props_per_type = types::retrieve_required_type_props(&client, service)?;
+            write_to_file(&format!("output/types/{}.csv", service), service, required_props_per_type)?;
             Ok(())
         })
         .collect::<Vec<Result<()>>>();
@@ -54,28 +122,63 @@ fn main() -> Result<()> {
         }
     }
 
+    write_sdk_version()?;
+
     Ok(())
 }
 
+/// Writes only the required fields of the builders in the `types` modules of the services to `output/types`
+fn retrieve_types(services: &[String]) -> Result<()> {
+    let client = Client::new();
+    services
+        .par_iter()
+        .map(|service| {
+            println!("Retrieving the types of {}", service);
+            let required_props_per_type = types::retrieve_required_type_props(&client, service)?;
+            write_to_file(&format!("output/types/{}.csv", service), service, required_props_per_type)
+        })
+        .collect()
+}
+
+/// Fails when crates.io has SDK crates that are in neither of our input files, so a new service does not go unnoticed
+fn report_unlisted_sdks() -> Result<()> {
+    let mut listed = retrieve_services_from_file()?;
+    listed.extend(read_lines("./input/excluded_sdk_crates")?);
+    let unlisted = crates::unlisted_sdks(&Client::new(), &listed)?;
+    if unlisted.is_empty() {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "these SDKs are not in input/names_of_sdk_crates or input/excluded_sdk_crates: {}",
+        unlisted.join(", ")
+    )
+}
+
 fn retrieve_services_from_file() -> Result<Vec<String>, Error> {
-    let file = File::open("./input/names_of_sdk_crates")?; // these names are based on the directories of the aws_rust_sdk on GitHub
-    let reader = BufReader::new(file);
-    let services: Vec<String> = reader.lines().collect::<Result<Vec<_>, Error>>()?;
-    Ok(services)
+    read_lines("./input/names_of_sdk_crates") // these names are based on the directories of the aws_rust_sdk on GitHub
+}
+
+fn read_lines(path: &str) -> Result<Vec<String>, Error> {
+    let reader = BufReader::new(File::open(path)?);
+    reader.lines().collect::<Result<Vec<_>, Error>>()
+}
+
+/// The version of the documentation we check for the service, `SDK_DOCS_VERSION` unless `input/docs_versions.csv` has another one
+fn docs_version(service: &str) -> &'static str {
+    DOCS_VERSIONS
+        .lines()
+        .filter_map(|line| line.split_once(','))
+        .find(|(name, _)| *name == service)
+        .map(|(_, version)| version.trim())
+        .unwrap_or(SDK_DOCS_VERSION)
+}
+
+fn client_docs_url(service: &str) -> String {
+    docs::crate_url(service, "client/struct.Client.html")
 }
 
 fn retrieve_aws_docs(client: &Client, service: &str) -> Result<String> {
-    let url = format!(
-        "https://docs.rs/aws-sdk-{}/1.18.0/aws_sdk_{}/client/struct.Client.html",
-        service, service
-    );
-    let result = client
-        .get(&url)
-        .send()
-        .with_context(|| format!("call to url {} for {} failed", &url, service))?;
-    result
-        .text()
-        .with_context(|| format!("call to get text for url {} for {} failed", &url, service))
+    docs::retrieve(client, &client_docs_url(service)).with_context(|| format!("failed to retrieve the client docs of {}", service))
 }
 
 fn sanitize_property(prop_name: String) -> String {
@@ -91,10 +194,12 @@ fn analyze_text<'a>(
     method_selector: &Selector,
     properties_selector: &Selector,
     property_selector: &Selector,
+    link_selector: &Selector,
     docs: &str,
     service: &'a str,
 ) -> Result<Vec<Record<'a>>> {
     let document = Html::parse_document(docs);
+    let client_url = Url::parse(&client_docs_url(service))?;
 
     let mut required_props_per_method = vec![];
 
@@ -118,10 +223,21 @@ fn analyze_text<'a>(
         }
 
         if !property_names.is_empty() {
+            // the docs of a method link to its fluent builder, relative to the page of the client
+            let builder_link = element
+                .select(link_selector)
+                .filter_map(|link| link.value().attr("href"))
+                .find(|href| href.contains("FluentBuilder"));
+            let docs_url = builder_link
+                .and_then(|href| client_url.join(href).ok())
+                .map(|url| url.to_string())
+                .unwrap_or_default();
             required_props_per_method.push(Record {
                 service,
                 method_name,
                 property_names: property_names.join(" "),
+                docs_url,
+                builder: builder_link.and_then(builder_name).unwrap_or_default(),
             })
         }
     }
@@ -129,13 +245,23 @@ fn analyze_text<'a>(
     Ok(required_props_per_method)
 }
 
-fn write_to_file(service: &str, required_props_per_method: Vec<Record>) -> Result<()> {
+/// E.g. `SendMessageFluentBuilder` for `../operation/send_message/builders/struct.SendMessageFluentBuilder.html`
+fn builder_name(href: &str) -> Option<String> {
+    let page = href.rsplit('/').next()?;
+    page.strip_prefix("struct.")?.strip_suffix(".html").map(|name| name.to_string())
+}
+
+fn write_sdk_version() -> Result<()> {
+    std::fs::write("output/sdk_version", SDK_DOCS_VERSION).context("failed to write the sdk version")
+}
+
+fn write_to_file(path: &str, service: &str, records: Vec<Record>) -> Result<()> {
     let mut writer = csv::WriterBuilder::new()
         .has_headers(false)
-        .from_path(format!("output/{}.csv", service))
+        .from_path(path)
         .with_context(|| format!("failed to created writer for {}", &service))?;
 
-    for el in required_props_per_method {
+    for el in records {
         writer
             .serialize(el)
             .with_context(|| format!("failed to write record for {}", &service))?;
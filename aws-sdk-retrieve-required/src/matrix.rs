@@ -0,0 +1,315 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Number of operations of every SDK that get a test case, spread over its (alphabetically sorted) methods
+const SAMPLE_SIZE: usize = 3;
+/// `service,method` of operations that are newer than the SDK versions the usage crate is tested with
+const SKIPPED_OPERATIONS: &str = "./input/matrix_skipped_operations.csv";
+const GENERATED_NOTICE: &str = "// generated with `cargo run -- matrix` in aws-sdk-retrieve-required, do not edit";
+/// The start of the return type of the getters of maps on the fluent builders, e.g. of `get_item` of DynamoDB `put_item`
+const MAP_GETTER_TYPE: &str = "-> &::std::option::Option<::std::collections::HashMap<";
+
+#[derive(Deserialize)]
+struct CargoMetadata {
+    packages: Vec<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    name: String,
+    manifest_path: PathBuf,
+}
+
+/// Writes trybuild cases for a sample of the operations of every SDK the usage crate depends on:
+/// one in `tests/matrix/fails` that calls none of the required properties, and (when possible) one in `tests/matrix/passes` that sets all of them.
+/// Other SDKs are not compiled by the usage crate, so those cannot be tested there
+pub fn write_matrix(required_props_csv: &str, usage_dir: &str) -> Result<()> {
+    let csv = std::fs::read_to_string(required_props_csv).with_context(|| format!("failed to read {}", required_props_csv))?;
+    let manifest_path = Path::new(usage_dir).join("Cargo.toml");
+    let manifest = std::fs::read_to_string(&manifest_path).with_context(|| format!("failed to read {}", manifest_path.display()))?;
+    let sdks = sdk_dependencies(&manifest);
+    let sources = sdk_sources(usage_dir)?;
+
+    let skipped = std::fs::read_to_string(SKIPPED_OPERATIONS).with_context(|| format!("failed to read {}", SKIPPED_OPERATIONS))?;
+    let sample = sample(&csv, &sdks, &skipped);
+
+    let fails_dir = Path::new(usage_dir).join("tests/matrix/fails");
+    let passes_dir = Path::new(usage_dir).join("tests/matrix/passes");
+    for dir in [&fails_dir, &passes_dir] {
+        // start from scratch, so operations that are no longer sampled do not linger
+        if dir.exists() {
+            std::fs::remove_dir_all(dir).with_context(|| format!("failed to remove {}", dir.display()))?;
+        }
+        std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    for (sdk, method, props) in sample {
+        let file_name = format!("{}_{}.rs", sdk, method);
+        let cases = [
+            (&fails_dir, Some(fail_case(sdk, method))),
+            (&passes_dir, map_properties(&sources, sdk, method, &props).map(|maps| pass_case(sdk, method, &props, &maps))),
+        ];
+        for (dir, case) in cases {
+            if let Some(case) = case {
+                let file = dir.join(&file_name);
+                std::fs::write(&file, case).with_context(|| format!("failed to write {}", file.display()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The SDKs (without `aws-sdk-`) in the dependencies of a manifest, e.g. `sqs` for `aws-sdk-sqs = "1.9.0"`
+fn sdk_dependencies(manifest: &str) -> Vec<&str> {
+    manifest
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .filter_map(|(name, _)| name.trim().strip_prefix("aws-sdk-"))
+        .filter(|sdk| !sdk.starts_with("compile-checks"))
+        .collect()
+}
+
+/// The source directories of the SDKs the usage crate depends on, in the versions it is compiled with, e.g. `sqs` with `.../aws-sdk-sqs-1.9.0`
+fn sdk_sources(usage_dir: &str) -> Result<HashMap<String, PathBuf>> {
+    let manifest_path = Path::new(usage_dir).join("Cargo.toml");
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1", "--manifest-path"])
+        .arg(&manifest_path)
+        .output()
+        .context("failed to run cargo metadata")?;
+    anyhow::ensure!(output.status.success(), "cargo metadata failed: {}", String::from_utf8_lossy(&output.stderr));
+    let metadata: CargoMetadata = serde_json::from_slice(&output.stdout).context("failed to parse the output of cargo metadata")?;
+    Ok(metadata
+        .packages
+        .into_iter()
+        .filter_map(|p| {
+            let sdk = p.name.strip_prefix("aws-sdk-")?.to_string();
+            Some((sdk, p.manifest_path.parent()?.to_path_buf()))
+        })
+        .collect())
+}
+
+/// The properties whose getter on the fluent builder of the method returns a map, whose setters take a key and a value.
+/// `None` when we cannot read the fluent builder, so we do not know
+fn map_properties<'a>(sources: &HashMap<String, PathBuf>, sdk: &str, method: &str, props: &[&'a str]) -> Option<Vec<&'a str>> {
+    let builders = sources.get(sdk)?.join("src/operation").join(method).join("builders.rs");
+    let builders = std::fs::read_to_string(builders).ok()?;
+    Some(props.iter().copied().filter(|prop| returns_map(&builders, prop)).collect())
+}
+
+/// Whether the getter of the property (the first one of an 'at least one of' group) in the source of the fluent builder returns a map
+fn returns_map(builders: &str, prop: &str) -> bool {
+    let prop = prop.split('|').next().unwrap_or(prop);
+    let getter = format!("pub fn get_{}(&self) ", prop.strip_prefix("r#").unwrap_or(prop));
+    builders
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix(&getter))
+        .any(|signature| signature.starts_with(MAP_GETTER_TYPE))
+}
+
+/// `SAMPLE_SIZE` (service, method, required properties) of every SDK, evenly spread over its methods
+fn sample<'a>(csv: &'a str, sdks: &[&str], skipped: &str) -> Vec<(&'a str, &'a str, Vec<&'a str>)> {
+    let mut by_sdk: BTreeMap<&str, BTreeMap<&str, Vec<&str>>> = BTreeMap::new();
+    for line in csv.lines() {
+        // the other columns, with the docs url and fluent builder, are not relevant here
+        let mut parts = line.splitn(4, ',');
+        if let (Some(service), Some(method), Some(props)) = (parts.next(), parts.next(), parts.next()) {
+            if sdks.contains(&service) && !skipped.lines().any(|s| s.trim() == format!("{},{}", service, method)) {
+                by_sdk
+                    .entry(service)
+                    .or_default()
+                    .entry(method)
+                    .or_default()
+                    .extend(props.split_whitespace());
+            }
+        }
+    }
+
+    by_sdk
+        .into_iter()
+        .flat_map(|(sdk, methods)| {
+            let methods: Vec<(&str, Vec<&str>)> = methods.into_iter().collect();
+            let step = (methods.len() / SAMPLE_SIZE).max(1);
+            methods
+                .into_iter()
+                .step_by(step)
+                .take(SAMPLE_SIZE)
+                .map(move |(method, props)| (sdk, method, props))
+        })
+        .collect()
+}
+
+fn fail_case(sdk: &str, method: &str) -> String {
+    format!(
+        "{}
+use aws_sdk_compile_checks_macro::required_props;
+
+#[required_props]
+async fn call(client: aws_sdk_{}::Client) {{
+    let _ = client.{}()
+        .send()
+        .await;
+}}
+
+fn main() {{}}
+",
+        GENERATED_NOTICE, sdk, method
+    )
+}
+
+/// Sets every required property (the first one of 'at least one of' groups) with a value of the type of its getter on the fluent builder,
+/// of the items of that type for lists (whose setters append a single item), or a key and a value for maps, so the case compiles whatever the type is.
+/// `maps` are the properties whose getter returns a map
+fn pass_case(sdk: &str, method: &str, props: &[&str], maps: &[&str]) -> String {
+    let setters: String = props
+        .iter()
+        .map(|group| {
+            let prop = group.split('|').next().unwrap_or(group);
+            let getter = format!("Getter(Builder::get_{})", prop.strip_prefix("r#").unwrap_or(prop));
+            match maps.contains(group) {
+                true => format!("        .{}({}.key(), {}.entry_value())\n", prop, getter, getter),
+                false => format!("        .{}((&&{}).value())\n", prop, getter),
+            }
+        })
+        .collect();
+    format!(
+        "{}
+#![allow(dead_code)]
+use aws_sdk_compile_checks_macro::required_props;
+use aws_sdk_{}::operation::{}::builders::{} as Builder;
+
+struct Getter<T>(fn(&Builder) -> &Option<T>);
+
+// takes precedence over `Value` (because of the extra reference) when the property is a list
+trait ListItem<T> {{
+    fn value(&self) -> T;
+}}
+
+impl<T> ListItem<T> for &Getter<Vec<T>> {{
+    fn value(&self) -> T {{
+        unimplemented!()
+    }}
+}}
+
+trait Value<T> {{
+    fn value(&self) -> T;
+}}
+
+impl<T> Value<T> for Getter<T> {{
+    fn value(&self) -> T {{
+        unimplemented!()
+    }}
+}}
+
+trait MapEntry<K, V> {{
+    fn key(&self) -> K;
+    fn entry_value(&self) -> V;
+}}
+
+impl<K, V> MapEntry<K, V> for Getter<std::collections::HashMap<K, V>> {{
+    fn key(&self) -> K {{
+        unimplemented!()
+    }}
+
+    fn entry_value(&self) -> V {{
+        unimplemented!()
+    }}
+}}
+
+#[required_props]
+async fn call(client: aws_sdk_{}::Client) {{
+    let _ = client.{}()
+{}        .send()
+        .await;
+}}
+
+fn main() {{}}
+",
+        GENERATED_NOTICE,
+        sdk,
+        method,
+        fluent_builder_of(method),
+        sdk,
+        method,
+        setters
+    )
+}
+
+/// The name smithy-rs gives the fluent builder of a method, e.g. `SendMessageFluentBuilder` for `send_message`
+fn fluent_builder_of(method: &str) -> String {
+    let pascal_case: String = method
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_ascii_uppercase()).into_iter().chain(chars)
+        })
+        .collect();
+    format!("{}FluentBuilder", pascal_case)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sdk_dependencies_skips_the_other_crates() {
+        let manifest = "[dependencies]\naws-sdk-compile-checks-macro = { path = \"../macro\" }\naws-config = \"1.1.8\"\naws-sdk-sqs = \"1.9.0\"\n\n[dev-dependencies]\naws-sdk-lambda = \"1.20.0\"\n";
+
+        assert_eq!(sdk_dependencies(manifest), vec!["sqs", "lambda"]);
+    }
+
+    #[test]
+    fn sample_spreads_over_the_methods_of_the_sdks() {
+        let csv = "s3,get_object,bucket key\nsqs,a,queue_url\nsqs,b,queue_url\nsqs,c,queue_url\nsqs,d,queue_url\nsqs,e,queue_url\nsqs,f,queue_url\nsqs,a,entries|ids\n";
+
+        let actual = sample(csv, &["sqs"], "sqs,b\n");
+
+        assert_eq!(
+            actual,
+            vec![
+                ("sqs", "a", vec!["queue_url", "entries|ids"]),
+                ("sqs", "c", vec!["queue_url"]),
+                ("sqs", "d", vec!["queue_url"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn pass_case_sets_the_first_property_of_groups_with_a_value_of_the_type_of_its_getter() {
+        let actual = pass_case("sns", "publish", &["message", "topic_arn|target_arn", "r#type"], &[]);
+
+        assert!(actual.contains("use aws_sdk_sns::operation::publish::builders::PublishFluentBuilder as Builder;"));
+        assert!(actual.contains(
+            "client.publish()\n        .message((&&Getter(Builder::get_message)).value())\n        .topic_arn((&&Getter(Builder::get_topic_arn)).value())\n        .r#type((&&Getter(Builder::get_type)).value())\n        .send()"
+        ));
+    }
+
+    #[test]
+    fn pass_case_sets_maps_with_a_key_and_a_value() {
+        let actual = pass_case("dynamodb", "put_item", &["item", "table_name"], &["item"]);
+
+        assert!(actual.contains(
+            "client.put_item()\n        .item(Getter(Builder::get_item).key(), Getter(Builder::get_item).entry_value())\n        .table_name((&&Getter(Builder::get_table_name)).value())\n"
+        ));
+    }
+
+    #[test]
+    fn returns_map_checks_the_type_of_the_getter() {
+        let builders = "    pub fn get_item(&self) -> &::std::option::Option<::std::collections::HashMap<::std::string::String, crate::types::AttributeValue>> {
+        self.inner.get_item()
+    }
+    pub fn get_table_name(&self) -> &::std::option::Option<::std::string::String> {
+        self.inner.get_table_name()
+    }
+    pub fn get_expected(&self) -> &::std::option::Option<::std::collections::HashMap<::std::string::String, crate::types::ExpectedAttributeValue>> {
+        self.inner.get_expected()
+    }";
+
+        assert!(returns_map(builders, "item"));
+        assert!(returns_map(builders, "expected|item"));
+        assert!(!returns_map(builders, "table_name"));
+        assert!(!returns_map(builders, "status"));
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use reqwest::blocking::Client;
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::docs::{crate_url, retrieve_if_present};
+
+const PAGINATOR: &str = "paginator";
+const PRESIGN: &str = "presign";
+const WAITER: &str = "waiter";
+const IDEMPOTENCY_TOKEN: &str = "idempotency_token"; // followed by `=` and the setter, e.g. `idempotency_token=client_token`
+const IDEMPOTENCY_TOKEN_SETTERS: [&str; 3] = ["client_token", "client_request_token", "idempotency_token"];
+const WAITER_PREFIX: &str = "wait_until_"; // the methods of the `Waiters` trait are named after the module of their builder
+
+/// Lines of `service,method,capability capability` for the operations that support pagination, presigning, waiters or an idempotency token.
+/// `waiters` are the (waiter, operation) pairs of the client, see `waiters`
+pub fn operation_metadata(
+    client: &Client,
+    class_selector: &Selector,
+    method_selector: &Selector,
+    link_selector: &Selector,
+    docs: &str,
+    service: &str,
+    waiters: &[(String, String)],
+) -> Result<Vec<String>> {
+    let document = Html::parse_document(docs);
+    let client_url = Url::parse(&crate_url(service, "client/struct.Client.html"))?;
+    let mut capabilities: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    // only SDKs with presignable operations have a `presigning` module, so we can skip the builders of the others
+    let has_presigning = retrieve_if_present(client, &crate_url(service, "presigning/index.html"))?.is_some();
+
+    for element in document.select(class_selector) {
+        let Some(method_name) = element.select(method_selector).next().map(|m| m.inner_html()) else {
+            continue;
+        };
+        if element.inner_html().contains("into_paginator") {
+            capabilities.entry(method_name.clone()).or_default().push(PAGINATOR.to_string());
+        }
+        if let Some(setter) = idempotency_token_setter(&element.inner_html()) {
+            capabilities.entry(method_name.clone()).or_default().push(format!("{}={}", IDEMPOTENCY_TOKEN, setter));
+        }
+        if has_presigning {
+            if let Some(builder_url) = link_containing(element, link_selector, "FluentBuilder", &client_url) {
+                let builder = retrieve_if_present(client, builder_url.as_str())?.unwrap_or_default();
+                if builder.contains("id=\"method.presigned\"") {
+                    capabilities.entry(method_name).or_default().push(PRESIGN.to_string());
+                }
+            }
+        }
+    }
+
+    let mut waiter_operations: Vec<&String> = waiters.iter().map(|(_, operation)| operation).collect();
+    waiter_operations.sort();
+    waiter_operations.dedup();
+    for operation in waiter_operations {
+        capabilities.entry(operation.clone()).or_default().push(WAITER.to_string());
+    }
+
+    Ok(capabilities
+        .into_iter()
+        .map(|(method, capabilities)| format!("{},{},{}", service, method, capabilities.join(" ")))
+        .collect())
+}
+
+/// The waiters of the client, with the operation they use (e.g. `wait_until_bucket_exists` and `head_bucket`).
+/// Every waiter has a fluent builder that links to the input builder of its operation
+pub fn waiters(client: &Client, link_selector: &Selector, service: &str) -> Result<Vec<(String, String)>> {
+    let waiters_url = crate_url(service, "client/trait.Waiters.html");
+    let Some(waiters) = retrieve_if_present(client, &waiters_url)? else {
+        return Ok(vec![]);
+    };
+    let waiters_url = Url::parse(&waiters_url)?;
+    let mut waiter_builders: Vec<(String, Url)> = Html::parse_document(&waiters)
+        .select(link_selector)
+        .filter_map(|link| link.value().attr("href"))
+        .filter(|href| href.contains("FluentBuilder"))
+        .filter_map(|href| Some((waiter_of_link(href)?, waiters_url.join(href).ok()?)))
+        .collect();
+    waiter_builders.sort();
+    waiter_builders.dedup();
+
+    let mut waiters = vec![];
+    for (waiter, builder_url) in waiter_builders {
+        let Some(builder) = retrieve_if_present(client, builder_url.as_str())? else {
+            continue;
+        };
+        let operation = Html::parse_document(&builder)
+            .select(link_selector)
+            .filter_map(|link| link.value().attr("href"))
+            .find_map(operation_of_link);
+        waiters.extend(operation.map(|operation| (waiter, operation)));
+    }
+    Ok(waiters)
+}
+
+/// The setter of the idempotency token in the docs of a method of the client, which list the setters of its fluent builder,
+/// e.g. `client_token` for `<code>client_token(impl Into&lt;String&gt;)</code>`
+fn idempotency_token_setter(method_docs: &str) -> Option<&'static str> {
+    IDEMPOTENCY_TOKEN_SETTERS.into_iter().find(|setter| method_docs.contains(&format!("<code>{}(", setter)))
+}
+
+fn link_containing(element: ElementRef, link_selector: &Selector, part: &str, base: &Url) -> Option<Url> {
+    element
+        .select(link_selector)
+        .filter_map(|link| link.value().attr("href"))
+        .find(|href| href.contains(part))
+        .and_then(|href| base.join(href).ok())
+}
+
+/// E.g. `head_bucket` for `../../operation/head_bucket/builders/struct.HeadBucketInputBuilder.html`
+fn operation_of_link(href: &str) -> Option<String> {
+    let (_, after_operation) = href.split_once("operation/")?;
+    let (operation, _) = after_operation.split_once('/')?;
+    Some(operation.to_string())
+}
+
+/// E.g. `wait_until_bucket_exists` for `../waiters/bucket_exists/struct.BucketExistsFluentBuilder.html`
+fn waiter_of_link(href: &str) -> Option<String> {
+    let (_, after_waiters) = href.split_once("waiters/")?;
+    let (waiter, _) = after_waiters.split_once('/')?;
+    Some(format!("{}{}", WAITER_PREFIX, waiter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operation_of_link_finds_the_operation_module() {
+        assert_eq!(
+            operation_of_link("../../operation/head_bucket/builders/struct.HeadBucketInputBuilder.html"),
+            Some("head_bucket".to_string())
+        );
+        assert_eq!(operation_of_link("../../types/struct.Replica.html"), None);
+    }
+
+    #[test]
+    fn idempotency_token_setter_of_the_docs_of_a_method() {
+        let docs = "<li><a href=\"#\"><code>table_name(impl Into&lt;String&gt;)</code></a></li><li><a href=\"#\"><code>client_token(impl Into&lt;String&gt;)</code></a></li>";
+
+        assert_eq!(idempotency_token_setter(docs), Some("client_token"));
+        assert_eq!(idempotency_token_setter("<code>get_client_token(&amp;self)</code>"), None);
+    }
+
+    #[test]
+    fn waiter_of_link_finds_the_waiter_module() {
+        assert_eq!(
+            waiter_of_link("../waiters/bucket_exists/struct.BucketExistsFluentBuilder.html"),
+            Some("wait_until_bucket_exists".to_string())
+        );
+        assert_eq!(waiter_of_link("../operation/head_bucket/builders/struct.HeadBucketFluentBuilder.html"), None);
+    }
+}
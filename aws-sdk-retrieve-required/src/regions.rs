@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+const ENDPOINTS_FILE: &str = "endpoints.json";
+const SERVICE_MODEL_FILE: &str = "service-2.json";
+// the suffixes smithy-rs drops from the service id when it names the crate, e.g. `config` for `Config Service`
+const DROPPED_SUFFIXES: [&str; 2] = ["service", "api"];
+
+#[derive(Deserialize)]
+struct Endpoints {
+    partitions: Vec<Partition>,
+}
+
+#[derive(Deserialize)]
+struct Partition {
+    partition: String,
+    regions: BTreeMap<String, IgnoredAny>,
+    services: HashMap<String, Service>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Service {
+    is_regionalized: Option<bool>,
+    #[serde(default)]
+    endpoints: BTreeMap<String, IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+struct ServiceModel {
+    metadata: Metadata,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Metadata {
+    service_id: String,
+    endpoint_prefix: String,
+}
+
+/// Writes the regions of every partition (`output/partitions.csv`) and the regions the SDKs are available in (`output/service_regions.csv`),
+/// based on the `data` directory of botocore, which has the endpoints of all services and a model per service
+pub fn write_regions(botocore_data: &str, sdks: &[String]) -> Result<()> {
+    let endpoints_path = Path::new(botocore_data).join(ENDPOINTS_FILE);
+    let endpoints: Endpoints = serde_json::from_str(
+        &std::fs::read_to_string(&endpoints_path).with_context(|| format!("failed to read {}", endpoints_path.display()))?,
+    )
+    .with_context(|| format!("failed to parse {}", endpoints_path.display()))?;
+    let prefixes = endpoint_prefixes(botocore_data)?;
+
+    let partitions = endpoints
+        .partitions
+        .iter()
+        .map(|p| format!("{},{}\n", p.partition, p.regions.keys().cloned().collect::<Vec<_>>().join(" ")))
+        .collect::<String>();
+    std::fs::write("output/partitions.csv", partitions).context("failed to write partitions")?;
+    let rows = service_region_rows(&endpoints, &prefixes, sdks).iter().map(|r| format!("{}\n", r)).collect::<String>();
+    std::fs::write("output/service_regions.csv", rows).context("failed to write service regions")
+}
+
+/// E.g. `apprunner,*,aws,ap-northeast-1 eu-west-1 us-east-1` for every SDK that is only available in some regions of a partition.
+/// SDKs that are available in all regions of a partition (like global services) have no row for it, and neither do partitions without the SDK
+fn service_region_rows(endpoints: &Endpoints, prefixes: &HashMap<String, String>, sdks: &[String]) -> Vec<String> {
+    let mut rows = vec![];
+    for sdk in sdks {
+        let Some(prefix) = prefixes.get(sdk) else {
+            continue;
+        };
+        for partition in &endpoints.partitions {
+            let Some(service) = partition.services.get(prefix) else {
+                continue;
+            };
+            if service.is_regionalized == Some(false) {
+                continue;
+            }
+            // leaves out the other endpoints, like `fips-us-east-1` or `aws-global`
+            let regions: Vec<_> = service.endpoints.keys().filter(|e| partition.regions.contains_key(*e)).cloned().collect();
+            if !regions.is_empty() && regions.len() < partition.regions.len() {
+                rows.push(format!("{},*,{},{}", sdk, partition.partition, regions.join(" ")));
+            }
+        }
+    }
+    rows
+}
+
+/// The endpoint prefix of every SDK, e.g. `bedrock-runtime` for `bedrockruntime`, from the newest model of the services
+fn endpoint_prefixes(botocore_data: &str) -> Result<HashMap<String, String>> {
+    let mut prefixes = HashMap::new();
+    for service_dir in std::fs::read_dir(botocore_data).with_context(|| format!("failed to read directory {}", botocore_data))? {
+        let service_dir = service_dir?.path();
+        if !service_dir.is_dir() {
+            continue;
+        }
+        // the versions of the api are dates, so the last one is the newest
+        let mut versions: Vec<PathBuf> = std::fs::read_dir(&service_dir)?.filter_map(|v| v.ok().map(|v| v.path())).collect();
+        versions.sort();
+        let Some(model) = versions.iter().rev().find_map(|v| read_service_model(v)) else {
+            continue;
+        };
+        let metadata = serde_json::from_str::<ServiceModel>(&model)
+            .with_context(|| format!("failed to parse the model in {}", service_dir.display()))?
+            .metadata;
+        prefixes.insert(sdk_name(&metadata.service_id), metadata.endpoint_prefix);
+    }
+    Ok(prefixes)
+}
+
+/// The model as it is in the botocore repository, or gzipped, as it is in the botocore package
+fn read_service_model(version_dir: &Path) -> Option<String> {
+    let path = version_dir.join(SERVICE_MODEL_FILE);
+    if let Ok(model) = std::fs::read_to_string(&path) {
+        return Some(model);
+    }
+    let mut model = String::new();
+    GzDecoder::new(File::open(path.with_extension("json.gz")).ok()?).read_to_string(&mut model).ok()?;
+    Some(model)
+}
+
+/// The name of the SDK crate of the service, e.g. `cloudwatchlogs` for `CloudWatch Logs` or `costandusagereport` for `Cost and Usage Report Service`
+fn sdk_name(service_id: &str) -> String {
+    let name: String = service_id.chars().filter(char::is_ascii_alphanumeric).collect::<String>().to_lowercase();
+    DROPPED_SUFFIXES
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .map(str::to_string)
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENDPOINTS: &str = r#"{
+        "partitions": [
+            {
+                "partition": "aws",
+                "regions": { "eu-west-1": {}, "us-east-1": {}, "us-west-2": {} },
+                "services": {
+                    "apprunner": { "endpoints": { "eu-west-1": {}, "fips-us-east-1": {}, "us-east-1": {} } },
+                    "iam": { "isRegionalized": false, "partitionEndpoint": "aws-global", "endpoints": { "aws-global": {} } },
+                    "sqs": { "endpoints": { "eu-west-1": {}, "us-east-1": {}, "us-west-2": {} } }
+                }
+            },
+            {
+                "partition": "aws-cn",
+                "regions": { "cn-north-1": {}, "cn-northwest-1": {} },
+                "services": {
+                    "sqs": { "endpoints": { "cn-north-1": {} } }
+                }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn service_region_rows_only_for_partitions_with_some_of_the_regions() {
+        let endpoints: Endpoints = serde_json::from_str(ENDPOINTS).unwrap();
+        let prefixes = HashMap::from([
+            ("apprunner".to_string(), "apprunner".to_string()),
+            ("iam".to_string(), "iam".to_string()),
+            ("sqs".to_string(), "sqs".to_string()),
+        ]);
+        let sdks = ["apprunner", "iam", "sqs", "s3vectors"].map(String::from);
+
+        assert_eq!(
+            service_region_rows(&endpoints, &prefixes, &sdks),
+            vec!["apprunner,*,aws,eu-west-1 us-east-1", "sqs,*,aws-cn,cn-north-1"]
+        );
+    }
+
+    #[test]
+    fn sdk_name_drops_spaces_and_suffixes() {
+        assert_eq!(sdk_name("CloudWatch Logs"), "cloudwatchlogs");
+        assert_eq!(sdk_name("Bedrock Runtime"), "bedrockruntime");
+        assert_eq!(sdk_name("Config Service"), "config");
+        assert_eq!(sdk_name("ApiGatewayManagementApi"), "apigatewaymanagement");
+        assert_eq!(sdk_name("API Gateway"), "apigateway");
+    }
+}
@@ -0,0 +1,145 @@
+use anyhow::Result;
+use reqwest::blocking::Client;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::docs::{crate_url, retrieve};
+use crate::Record;
+
+/// Setters of required fields in the builders of the `types` module mention this in their documentation
+const REQUIRED_FIELD: &str = "This field is required.";
+/// Required collections do not get that sentence, but `build` lists every field it fails without, as a link to its setter
+const BUILD_METHOD: &str = "build";
+const SETTER_ANCHOR: &str = "#method.";
+const BUILDER_SUFFIX: &str = "Builder";
+
+/// Retrieves the required properties of the builders in the `types` module (e.g. `ReplicaBuilder`), by type name (e.g. `Replica`)
+pub fn retrieve_required_type_props<'a>(client: &Client, service: &'a str) -> Result<Vec<Record<'a>>> {
+    let builders_selector = Selector::parse("a.struct").unwrap();
+    let method_selector = Selector::parse(".impl-items > details").unwrap();
+    let method_name_selector = Selector::parse("summary > section h4 > a").unwrap();
+    let docs_selector = Selector::parse(".docblock").unwrap();
+    let link_selector = Selector::parse("a").unwrap();
+
+    let index = retrieve(client, &builders_url(service, "index.html"))?;
+    let builders = builder_pages(&Html::parse_document(&index), &builders_selector);
+
+    let mut records = vec![];
+    for builder_page in builders {
+        let docs = retrieve(client, &builders_url(service, &builder_page))?;
+        let document = Html::parse_document(&docs);
+        let property_names = required_setters(&document, &method_selector, &method_name_selector, &docs_selector, &link_selector);
+
+        if let (false, Some(type_name)) = (property_names.is_empty(), type_name(&builder_page)) {
+            records.push(Record {
+                service,
+                method_name: type_name,
+                property_names: property_names.join(" "),
+                docs_url: builders_url(service, &builder_page),
+                builder: String::new(),
+            });
+        }
+    }
+    Ok(records)
+}
+
+fn builders_url(service: &str, page: &str) -> String {
+    crate_url(service, &format!("types/builders/{}", page))
+}
+
+/// The pages of the builders, e.g. `struct.ReplicaBuilder.html`
+fn builder_pages(index: &Html, builders_selector: &Selector) -> Vec<String> {
+    let mut pages: Vec<String> = index
+        .select(builders_selector)
+        .filter_map(|link| link.value().attr("href"))
+        .filter(|href| href.starts_with("struct.") && href.ends_with(&format!("{}.html", BUILDER_SUFFIX)))
+        .map(|href| href.to_string())
+        .collect();
+    // the sidebar links to the same pages
+    pages.sort();
+    pages.dedup();
+    pages
+}
+
+fn type_name(builder_page: &str) -> Option<String> {
+    builder_page
+        .strip_prefix("struct.")?
+        .strip_suffix(".html")?
+        .strip_suffix(BUILDER_SUFFIX)
+        .map(|t| t.to_string())
+}
+
+/// The setters whose documentation says the field is required, or that `build` fails without, skipping the `set_` and `get_` variants of those setters
+fn required_setters(
+    document: &Html,
+    method_selector: &Selector,
+    method_name_selector: &Selector,
+    docs_selector: &Selector,
+    link_selector: &Selector,
+) -> Vec<String> {
+    let methods: Vec<(String, ElementRef)> = document
+        .select(method_selector)
+        .filter_map(|method| method.select(method_name_selector).next().map(|name| (name.inner_html(), method)))
+        .collect();
+    let checked_by_build: Vec<&str> = methods
+        .iter()
+        .filter(|(name, _)| name == BUILD_METHOD)
+        .flat_map(|(_, method)| method.select(docs_selector))
+        .flat_map(|docs| docs.select(link_selector))
+        .filter_map(|link| link.value().attr("href")?.split_once(SETTER_ANCHOR).map(|(_, setter)| setter))
+        .collect();
+    methods
+        .iter()
+        .filter(|(name, method)| {
+            checked_by_build.contains(&name.as_str()) || method.select(docs_selector).any(|d| d.inner_html().contains(REQUIRED_FIELD))
+        })
+        .map(|(name, _)| name.clone())
+        .filter(|name| name != BUILD_METHOD && !name.starts_with("set_") && !name.starts_with("get_"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUILDER_PAGE: &str = r##"<html><body><div class="impl-items">
+        <details><summary><section><h4><a href="#method.region_name">region_name</a></h4></section></summary>
+            <div class="docblock"><p>The Region where the replica needs to be created.</p><p>This field is required.</p></div></details>
+        <details><summary><section><h4><a href="#method.set_region_name">set_region_name</a></h4></section></summary>
+            <div class="docblock"><p>The Region where the replica needs to be created.</p><p>This field is required.</p></div></details>
+        <details><summary><section><h4><a href="#method.kms_key">kms_key</a></h4></section></summary>
+            <div class="docblock"><p>An optional key.</p></div></details>
+        <details><summary><section><h4><a href="#method.tags">tags</a></h4></section></summary>
+            <div class="docblock"><p>Appends an item to <code>tags</code>.</p></div></details>
+        <details><summary><section><h4><a href="#method.build">build</a></h4></section></summary>
+            <div class="docblock"><p>This method will fail if any of the following fields are not set:</p>
+            <ul><li><a href="struct.ReplicaBuilder.html#method.region_name"><code>region_name</code></a></li>
+            <li><a href="struct.ReplicaBuilder.html#method.tags"><code>tags</code></a></li></ul></div></details>
+    </div></body></html>"##;
+
+    #[test]
+    fn required_setters_only_returns_required_fields() {
+        let document = Html::parse_document(BUILDER_PAGE);
+
+        let actual = required_setters(
+            &document,
+            &Selector::parse(".impl-items > details").unwrap(),
+            &Selector::parse("summary > section h4 > a").unwrap(),
+            &Selector::parse(".docblock").unwrap(),
+            &Selector::parse("a").unwrap(),
+        );
+
+        assert_eq!(actual, vec!["region_name", "tags"]);
+    }
+
+    #[test]
+    fn builder_pages_and_type_names() {
+        let index = Html::parse_document(
+            r#"<a class="struct" href="struct.ReplicaBuilder.html">ReplicaBuilder</a><a class="struct" href="struct.ReplicaBuilder.html">ReplicaBuilder</a><a class="struct" href="../struct.Replica.html">Replica</a>"#,
+        );
+
+        let pages = builder_pages(&index, &Selector::parse("a.struct").unwrap());
+
+        assert_eq!(pages, vec!["struct.ReplicaBuilder.html"]);
+        assert_eq!(type_name(&pages[0]), Some("Replica".to_string()));
+    }
+}